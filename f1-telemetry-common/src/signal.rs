@@ -0,0 +1,44 @@
+use std::future::Future;
+use std::io;
+
+use log::error;
+
+/// Resolves on Ctrl-C, or when the process is asked to stop with SIGTERM, e.g. by a supervisor.
+/// If SIGTERM cannot be listened for, the error is logged and only Ctrl-C is waited for.
+pub async fn shutdown_signal() -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => tokio::select! {
+                res = tokio::signal::ctrl_c() => res,
+                _ = terminate.recv() => Ok(()),
+            },
+            Err(e) => {
+                error!("Unable to listen for SIGTERM, only Ctrl-C will stop: {}", e);
+                tokio::signal::ctrl_c().await
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await
+}
+
+/// Runs `future` to completion, unless a [`shutdown_signal`] is received first, in which case
+/// `future` is dropped at its current await point and `None` is returned. If no signal can be
+/// listened for, the error is logged and `future` runs to completion.
+pub async fn until_shutdown<F: Future>(future: F) -> Option<F::Output> {
+    let shutdown = async {
+        if let Err(e) = shutdown_signal().await {
+            error!("Unable to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    tokio::select! {
+        output = future => Some(output),
+        _ = shutdown => None,
+    }
+}
@@ -0,0 +1,257 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use log::{error, info};
+
+use f1_telemetry::analysis::{SessionStatus, SessionTracker};
+use f1_telemetry::packet::car_telemetry::CarTelemetryData;
+use f1_telemetry::packet::{Packet, UnpackError};
+use f1_telemetry::Stream;
+use f1_telemetry_common::signal::until_shutdown;
+
+const HEADER: &str = "session_time,speed,throttle,brake,gear,engine_rpm,drs,\
+tyre_temperature_fl,tyre_temperature_fr,tyre_temperature_rl,tyre_temperature_rr";
+
+/// Shortest interval between writes to disk, `tokio::time::interval` panicking on zero
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Writes the telemetry of the player car to CSV files, one row per telemetry packet.
+///
+/// A new file named after the session UID is started every time a new session is detected, so
+/// each file holds a single session. Late packets of the previous session are dropped.
+pub struct CsvLogger {
+    output_dir: PathBuf,
+    flush_interval: Duration,
+    session: SessionTracker,
+    writer: Option<BufWriter<File>>,
+}
+
+impl CsvLogger {
+    pub fn new<P: Into<PathBuf>>(output_dir: P) -> CsvLogger {
+        CsvLogger {
+            output_dir: output_dir.into(),
+            flush_interval: Duration::from_secs(1),
+            session: SessionTracker::default(),
+            writer: None,
+        }
+    }
+
+    /// Interval between writes to disk, at least 1 ms.
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> CsvLogger {
+        self.flush_interval = flush_interval.max(MIN_FLUSH_INTERVAL);
+        self
+    }
+
+    /// Logs the packets received by `stream` until interrupted with Ctrl-C or SIGTERM.
+    pub async fn run(&mut self, stream: &Stream) -> io::Result<()> {
+        if let Some(res) = until_shutdown(self.log_stream(stream)).await {
+            return res;
+        }
+
+        info!("Interrupted, exiting");
+        self.flush()
+    }
+
+    async fn log_stream(&mut self, stream: &Stream) -> io::Result<()> {
+        let mut flush = tokio::time::interval(self.flush_interval);
+
+        loop {
+            tokio::select! {
+                packet = stream.next() => match packet {
+                    Ok(p) => self.log(&p)?,
+                    Err(UnpackError::NotF1Packet) => {}
+                    Err(e) => error!("{}", e),
+                },
+                _ = flush.tick() => self.flush()?,
+            }
+        }
+    }
+
+    /// Appends a row for the player car of a telemetry packet. Other packets only serve to detect
+    /// new sessions, and packets without a player car (e.g. when spectating) are ignored.
+    pub fn log(&mut self, packet: &Packet) -> io::Result<()> {
+        match self.session.observe(packet) {
+            SessionStatus::Started(_) => {
+                // The file of the new session is only created once it has a row
+                self.flush()?;
+                self.writer = None;
+            }
+            SessionStatus::Current => {}
+            SessionStatus::Stale => return Ok(()),
+        }
+
+        let Packet::CarTelemetry(p) = packet else {
+            return Ok(());
+        };
+
+        let player = match p.player_data() {
+            Some(player) => player,
+            None => return Ok(()),
+        };
+
+        let writer = self.writer_for(p.header.session_uid)?;
+        write_row(writer, p.header.session_time, player)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.writer {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+
+    fn writer_for(&mut self, session_uid: u64) -> io::Result<&mut BufWriter<File>> {
+        if self.writer.is_none() {
+            self.writer = Some(self.open(session_uid)?);
+        }
+
+        Ok(self.writer.as_mut().unwrap())
+    }
+
+    fn open(&self, session_uid: u64) -> io::Result<BufWriter<File>> {
+        let path = self
+            .output_dir
+            .join(format!("telemetry-{:016x}.csv", session_uid));
+
+        // Append to the file of a session that was already logged, e.g. after a restart
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let is_empty = file.metadata()?.len() == 0;
+
+        let mut writer = BufWriter::new(file);
+        if is_empty {
+            writeln!(writer, "{}", HEADER)?;
+        }
+
+        info!("Logging session {:016x} to {}", session_uid, path.display());
+
+        Ok(writer)
+    }
+}
+
+fn write_row<W: Write>(
+    writer: &mut W,
+    session_time: u32,
+    car: &CarTelemetryData,
+) -> io::Result<()> {
+    let tyres = &car.tyres_surface_temperature;
+
+    writeln!(
+        writer,
+        "{:.3},{},{:.3},{:.3},{},{},{},{},{},{},{}",
+        session_time as f32 / 1000.0,
+        car.speed,
+        car.throttle,
+        car.brake,
+        car.gear,
+        car.engine_rpm,
+        car.drs as u8,
+        tyres.front_left,
+        tyres.front_right,
+        tyres.rear_left,
+        tyres.rear_right,
+    )
+}
+
+#[cfg(test)]
+mod test_csv_logger {
+    use std::fs;
+
+    use f1_telemetry::packet::car_telemetry::{MFDPanel, PacketCarTelemetryData};
+    use f1_telemetry::packet::generic::WheelData;
+    use f1_telemetry::packet::PacketType;
+    use f1_telemetry::test_util::PacketHeaderBuilder;
+
+    use super::*;
+
+    fn packet(session_uid: u64, session_time: u32, speed: u16) -> PacketCarTelemetryData {
+        let header = PacketHeaderBuilder::default()
+            .with_packet_type(PacketType::CarTelemetry)
+            .with_session_uid(session_uid)
+            .with_session_time(session_time)
+            .with_player_car_index(1)
+            .build();
+
+        let player = CarTelemetryData {
+            speed,
+            throttle: 0.5,
+            gear: 7,
+            engine_rpm: 11000,
+            drs: true,
+            tyres_surface_temperature: WheelData {
+                front_left: 90,
+                front_right: 91,
+                rear_left: 92,
+                rear_right: 93,
+            },
+            ..Default::default()
+        };
+
+        PacketCarTelemetryData {
+            header,
+            car_telemetry_data: vec![CarTelemetryData::default(), player],
+            button_status: None,
+            mfd_panel: MFDPanel::NotSet,
+            secondary_player_mfd_panel: MFDPanel::NotSet,
+            suggested_gear: None,
+        }
+    }
+
+    #[test]
+    fn test_write_row() {
+        let mut buf = Vec::new();
+        write_row(&mut buf, 61500, &packet(1, 0, 301).car_telemetry_data[1]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "61.500,301,0.500,0.000,7,11000,1,90,91,92,93\n"
+        );
+    }
+
+    #[test]
+    fn test_new_session_starts_new_file() {
+        let dir = std::env::temp_dir().join(format!("f1-telemetry-csv-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut logger = CsvLogger::new(&dir);
+        for (session_uid, session_time, speed) in [(1, 1000, 100), (1, 2000, 200), (2, 1000, 300)] {
+            let packet = Packet::CarTelemetry(packet(session_uid, session_time, speed));
+            logger.log(&packet).unwrap();
+        }
+        logger.flush().unwrap();
+
+        let first = fs::read_to_string(dir.join("telemetry-0000000000000001.csv")).unwrap();
+        let second = fs::read_to_string(dir.join("telemetry-0000000000000002.csv")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.lines().count(), 3);
+        assert_eq!(first.lines().next(), Some(HEADER));
+        assert_eq!(second.lines().count(), 2);
+        assert!(second.lines().nth(1).unwrap().starts_with("1.000,300,"));
+    }
+
+    #[test]
+    fn test_late_packets_do_not_reopen_previous_session() {
+        let dir =
+            std::env::temp_dir().join(format!("f1-telemetry-csv-late-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let mut logger = CsvLogger::new(&dir);
+        for (session_uid, session_time, speed) in [(1, 1000, 100), (2, 1000, 200), (1, 1100, 110)] {
+            let packet = Packet::CarTelemetry(packet(session_uid, session_time, speed));
+            logger.log(&packet).unwrap();
+        }
+        logger
+            .log(&Packet::CarTelemetry(packet(2, 2000, 300)))
+            .unwrap();
+        logger.flush().unwrap();
+
+        let first = fs::read_to_string(dir.join("telemetry-0000000000000001.csv")).unwrap();
+        let second = fs::read_to_string(dir.join("telemetry-0000000000000002.csv")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.lines().count(), 2);
+        assert_eq!(second.lines().count(), 3);
+    }
+}
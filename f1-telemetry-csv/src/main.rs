@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use log::{error, info, LevelFilter};
+use simplelog::{ColorChoice, TerminalMode};
+
+use f1_telemetry::packet::PacketType;
+use f1_telemetry::Stream;
+use f1_telemetry_common::logging::LogBuilder;
+
+use crate::logger::CsvLogger;
+
+mod logger;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None, propagate_version = true)]
+struct AppArgs {
+    /// Address to bind on for the UDP packet listener
+    #[clap(long, default_value = "0.0.0.0:20777")]
+    bind: String,
+
+    /// Directory in which to write the CSV files, one per session
+    #[clap(short, long, default_value = ".")]
+    output_dir: PathBuf,
+
+    /// Interval between writes to disk, in milliseconds
+    #[clap(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    flush_interval: u64,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = AppArgs::parse();
+
+    LogBuilder::new()
+        .with_term_logger(LevelFilter::Info, TerminalMode::Mixed, ColorChoice::Auto)
+        .build()
+        .expect("Error initializing loggger.");
+
+    let mut stream = Stream::new(&args.bind)
+        .await
+        .expect("Unable to bind packet socket");
+    stream.subscribe(&[PacketType::CarTelemetry]);
+    info!("Listening for telemetry packets on: {}", args.bind);
+
+    let mut logger = CsvLogger::new(args.output_dir)
+        .with_flush_interval(Duration::from_millis(args.flush_interval));
+
+    if let Err(e) = logger.run(&stream).await {
+        error!("Error writing telemetry: {}", e);
+    }
+}
@@ -1,10 +1,57 @@
 use std::borrow::Cow;
 use std::fmt::Write as _;
 
-use f1_telemetry::packet::generic::ResultStatus;
+use f1_telemetry::analysis::{LeaderGap, WeatherChange};
+use f1_telemetry::packet::event::{Event, Penalty, PenaltyType};
+use f1_telemetry::packet::generic::{ResultStatus, Team};
 use f1_telemetry::packet::participants::Driver;
 
-use crate::models::{EventInfo, Participant, SessionInfo};
+use crate::models::{EventInfo, LapDelta, Participant, SessionInfo};
+
+/// Units used to display speeds and temperatures. The game always reports metric values.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum UnitSystem {
+    #[default]
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    pub fn toggle(self) -> UnitSystem {
+        match self {
+            UnitSystem::Metric => UnitSystem::Imperial,
+            UnitSystem::Imperial => UnitSystem::Metric,
+        }
+    }
+
+    pub fn speed_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "km/h",
+            UnitSystem::Imperial => "mph",
+        }
+    }
+
+    pub fn temperature_unit(self) -> &'static str {
+        match self {
+            UnitSystem::Metric => "C",
+            UnitSystem::Imperial => "F",
+        }
+    }
+}
+
+pub fn convert_speed(kph: f32, units: UnitSystem) -> f32 {
+    match units {
+        UnitSystem::Metric => kph,
+        UnitSystem::Imperial => kph * 0.621_371,
+    }
+}
+
+pub fn convert_temperature(celsius: f32, units: UnitSystem) -> f32 {
+    match units {
+        UnitSystem::Metric => celsius,
+        UnitSystem::Imperial => celsius * 9.0 / 5.0 + 32.0,
+    }
+}
 
 pub trait AsPercentage {
     fn as_percentage(&self) -> String;
@@ -30,8 +77,13 @@ pub trait AsMinuteTimeString {
     fn as_minute_time_string(&self) -> String;
 }
 
+/// Lap times and gaps, hours are only shown past an hour
 impl AsMinuteTimeString for u32 {
     fn as_minute_time_string(&self) -> String {
+        if *self >= 3_600_000 {
+            return self.as_hour_time_string();
+        }
+
         let minutes = self / 60000;
         let seconds = self % 60000 / 1000;
         let millis = self % 1000;
@@ -56,13 +108,18 @@ impl AsHourTimeString for u32 {
     }
 }
 
+/// Session clock in seconds, as `m:ss` below an hour and `hh:mm:ss` above, like lap times
 impl AsHourTimeString for u16 {
     fn as_hour_time_string(&self) -> String {
         let hours = self / 3600;
         let minutes = (self - hours * 3600) / 60;
         let seconds = self % 60;
 
-        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+        if hours > 0 {
+            format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{}:{:02}", minutes, seconds)
+        }
     }
 }
 
@@ -74,6 +131,18 @@ pub fn get_lap_count(sinfo: &SessionInfo) -> String {
     format!("Lap {} of {}", sinfo.current_lap, sinfo.number_of_laps)
 }
 
+/// Fuel surplus or deficit at the end of the race, as shown on the MFD. Sessions without a lap
+/// count (e.g. practice or time trial) have no target.
+pub fn format_fuel_target(fuel_remaining_laps: f32, total_laps: u8) -> String {
+    if total_laps == 0 {
+        "N/A".to_string()
+    } else if fuel_remaining_laps < 0.0 {
+        format!("short by {:.2} laps", -fuel_remaining_laps)
+    } else {
+        format!("long by {:.2} laps", fuel_remaining_laps)
+    }
+}
+
 pub fn get_session_time(sinfo: &SessionInfo) -> String {
     format!(
         "{} / {}",
@@ -93,6 +162,15 @@ pub fn format_driver_name(participant: &Participant, is_online: bool) -> Cow<str
     }
 }
 
+/// Team name, telling apart the custom teams that may group drivers of different teams
+pub fn format_team_name(team: Team) -> &'static str {
+    match team {
+        Team::MyTeam => "My Team (custom)",
+        Team::F1CustomTeam => "Custom team",
+        _ => team.name(),
+    }
+}
+
 pub fn format_position(position: u8, status: &ResultStatus) -> String {
     match status {
         ResultStatus::Retired => String::from("RET"),
@@ -147,11 +225,83 @@ pub fn format_gear(gear: i8) -> Cow<'static, str> {
     }
 }
 
-pub fn format_speed(speed: u16) -> String {
-    format!("{:3} km/h", speed)
+pub fn format_speed(speed: u16, units: UnitSystem) -> String {
+    format!(
+        "{:3.0} {}",
+        convert_speed(speed as f32, units),
+        units.speed_unit()
+    )
+}
+
+pub fn format_temperature(celsius: i16, units: UnitSystem) -> String {
+    format!(
+        "{:.0}{}",
+        convert_temperature(celsius as f32, units),
+        units.temperature_unit()
+    )
+}
+
+pub fn format_lap_delta(delta: Option<LapDelta>) -> String {
+    let delta = match delta {
+        Some(LapDelta::Time(t)) if t.abs() < 100.0 => format!("{:+.3}", t),
+        Some(LapDelta::Time(t)) => format!("{:+.1}", t),
+        Some(LapDelta::LapAhead) => "+LAP".to_string(),
+        Some(LapDelta::LapBehind) => "-LAP".to_string(),
+        None => String::new(),
+    };
+
+    format!("{:>7}", delta)
+}
+
+/// Gap to the race leader, in the same width as [`format_lap_delta`]
+pub fn format_leader_gap(gap: Option<&LeaderGap>) -> String {
+    let gap = match gap {
+        Some(g) if g.laps_down > 0 => format!("+{} LAP", g.laps_down),
+        Some(g) if g.position == 1 => "LEADER".to_string(),
+        Some(LeaderGap { gap: Some(t), .. }) => format!("{:+.3}", t),
+        _ => String::new(),
+    };
+
+    format!("{:>7}", gap)
+}
+
+/// Wet/dry crossover hint, e.g. "Rain expected in ~10 min (~7 laps)". The number of laps is
+/// estimated from `lap_time`, in milliseconds, and left out when it is 0.
+pub fn format_weather_change(change: WeatherChange, lap_time: u32) -> String {
+    let (mut s, minutes) = match change {
+        WeatherChange::Rain(m) => (format!("Rain expected in ~{} min", m), m),
+        WeatherChange::Drying(m) => (format!("Drying in ~{} min", m), m),
+    };
+
+    if lap_time > 0 {
+        let laps = (minutes as u32 * 60_000).div_ceil(lap_time);
+        let _ = write!(s, " (~{} laps)", laps);
+    }
+
+    s
+}
+
+/// Gap from the player to the car one position ahead, e.g. "+1.234s to car ahead"
+pub fn format_gap_ahead(delta: Option<LapDelta>) -> String {
+    match delta {
+        Some(LapDelta::Time(t)) => format!("{:+.3}s to car ahead", t),
+        Some(LapDelta::LapAhead) => "+1 LAP to car ahead".to_string(),
+        _ => String::new(),
+    }
 }
 
-pub fn format_event_info(event_info: &EventInfo) -> String {
+/// Penalty and its reason, e.g. "5s time penalty: Corner cutting gained time"
+pub fn format_penalty(penalty: &Penalty) -> String {
+    match penalty.penalty_type {
+        PenaltyType::TimePenalty => format!(
+            "{}s time penalty: {}",
+            penalty.time, penalty.infringement_type
+        ),
+        penalty_type => format!("{}: {}", penalty_type, penalty.infringement_type),
+    }
+}
+
+pub fn format_event_info(event_info: &EventInfo, units: UnitSystem) -> String {
     let mut msg = format!(
         "{}: {}",
         event_info.timestamp.as_hour_time_string(),
@@ -162,7 +312,17 @@ pub fn format_event_info(event_info: &EventInfo) -> String {
         let _ = write!(msg, ": {}", driver); // Safe to ignore Err
     }
 
-    if let Some(detail) = &event_info.detail {
+    // Speeds are formatted here rather than when the event is received, to follow unit changes
+    let detail = match &event_info.event {
+        Event::SpeedTrap(s) => Some(Cow::Owned(format!(
+            "{:.1} {}",
+            convert_speed(s.speed, units),
+            units.speed_unit()
+        ))),
+        _ => event_info.detail.as_deref().map(Cow::Borrowed),
+    };
+
+    if let Some(detail) = detail {
         let _ = write!(msg, " ({})", detail); // Safe to ignore Err
     }
 
@@ -228,3 +388,193 @@ mod test_fmt_delta_time {
         assert_eq!(expected, actual)
     }
 }
+
+#[cfg(test)]
+mod test_fmt_time_string {
+    use super::*;
+
+    #[test]
+    fn test_session_time_under_a_minute() {
+        assert_eq!(45u16.as_hour_time_string(), "0:45");
+    }
+
+    #[test]
+    fn test_session_time_over_a_minute() {
+        assert_eq!(90u16.as_hour_time_string(), "1:30");
+    }
+
+    #[test]
+    fn test_session_time_over_an_hour() {
+        assert_eq!(3725u16.as_hour_time_string(), "01:02:05");
+    }
+
+    #[test]
+    fn test_lap_time_over_a_minute() {
+        assert_eq!(90_500u32.as_minute_time_string(), "01:30.500");
+    }
+
+    #[test]
+    fn test_lap_time_over_an_hour() {
+        assert_eq!(3_725_001u32.as_minute_time_string(), "01:02:05.001");
+    }
+}
+
+#[cfg(test)]
+mod test_fmt_fuel_target {
+    use super::*;
+
+    #[test]
+    fn test_fuel_target() {
+        assert_eq!("long by 0.35 laps", format_fuel_target(0.35, 50));
+        assert_eq!("short by 1.20 laps", format_fuel_target(-1.2, 50));
+        assert_eq!("N/A", format_fuel_target(2.0, 0));
+    }
+}
+
+#[cfg(test)]
+mod test_fmt_lap_delta {
+    use super::*;
+
+    #[test]
+    fn test_time_delta_is_signed() {
+        assert_eq!(" +1.234", format_lap_delta(Some(LapDelta::Time(1.234))));
+        assert_eq!("-12.345", format_lap_delta(Some(LapDelta::Time(-12.345))));
+        assert_eq!(" +123.5", format_lap_delta(Some(LapDelta::Time(123.456))));
+    }
+
+    #[test]
+    fn test_lapped_cars_show_lap() {
+        assert_eq!("   +LAP", format_lap_delta(Some(LapDelta::LapAhead)));
+        assert_eq!("   -LAP", format_lap_delta(Some(LapDelta::LapBehind)));
+    }
+
+    #[test]
+    fn test_no_delta_is_blank() {
+        assert_eq!("       ", format_lap_delta(None));
+    }
+
+    #[test]
+    fn test_gap_ahead() {
+        assert_eq!(
+            "+1.234s to car ahead",
+            format_gap_ahead(Some(LapDelta::Time(1.234)))
+        );
+        assert_eq!("", format_gap_ahead(None));
+    }
+}
+
+#[cfg(test)]
+mod test_fmt_units {
+    use super::*;
+
+    #[test]
+    fn test_metric_speed_is_unchanged() {
+        assert_eq!("312 km/h", format_speed(312, UnitSystem::Metric));
+    }
+
+    #[test]
+    fn test_imperial_speed_is_converted_to_mph() {
+        assert_eq!("194 mph", format_speed(312, UnitSystem::Imperial));
+    }
+
+    #[test]
+    fn test_imperial_temperature_is_converted_to_fahrenheit() {
+        assert_eq!("32F", format_temperature(0, UnitSystem::Imperial));
+        assert_eq!("212F", format_temperature(100, UnitSystem::Imperial));
+        assert_eq!("-4F", format_temperature(-20, UnitSystem::Imperial));
+    }
+
+    #[test]
+    fn test_toggle_switches_unit_system() {
+        assert_eq!(UnitSystem::Imperial, UnitSystem::Metric.toggle());
+        assert_eq!(UnitSystem::Metric, UnitSystem::Imperial.toggle());
+    }
+}
+
+#[cfg(test)]
+mod test_fmt_penalty {
+    use f1_telemetry::packet::event::InfringementType;
+
+    use super::*;
+
+    fn penalty(penalty_type: PenaltyType, infringement_type: InfringementType) -> Penalty {
+        Penalty {
+            vehicle_idx: 0,
+            penalty_type,
+            infringement_type,
+            other_vehicle_idx: 255,
+            time: 5,
+            lap_num: 3,
+            places_gained: 0,
+        }
+    }
+
+    #[test]
+    fn test_time_penalties_show_the_time() {
+        assert_eq!(
+            "5s time penalty: Corner cutting gained time",
+            format_penalty(&penalty(
+                PenaltyType::TimePenalty,
+                InfringementType::CornerCuttingGainedTime
+            ))
+        );
+    }
+
+    #[test]
+    fn test_other_penalties() {
+        assert_eq!(
+            "Drive-through: Ignoring blue flags",
+            format_penalty(&penalty(
+                PenaltyType::DriveThrough,
+                InfringementType::IgnoringBlueFlags
+            ))
+        );
+        assert_eq!(
+            "Penalty 42: Infringement 99",
+            format_penalty(&penalty(
+                PenaltyType::Other(42),
+                InfringementType::Other(99)
+            ))
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_fmt_leader_gap {
+    use super::*;
+
+    fn gap(position: u8, gap: Option<f32>, laps_down: u8) -> LeaderGap {
+        LeaderGap {
+            car_index: 0,
+            position,
+            gap,
+            laps_down,
+        }
+    }
+
+    #[test]
+    fn test_format_leader_gap() {
+        assert_eq!(" LEADER", format_leader_gap(Some(&gap(1, Some(0.0), 0))));
+        assert_eq!(" +1.500", format_leader_gap(Some(&gap(2, Some(1.5), 0))));
+        assert_eq!(" +2 LAP", format_leader_gap(Some(&gap(3, None, 2))));
+        assert_eq!("       ", format_leader_gap(Some(&gap(4, None, 0))));
+        assert_eq!("       ", format_leader_gap(None));
+    }
+}
+
+#[cfg(test)]
+mod test_fmt_weather_change {
+    use super::*;
+
+    #[test]
+    fn test_format_weather_change() {
+        assert_eq!(
+            "Rain expected in ~10 min (~7 laps)",
+            format_weather_change(WeatherChange::Rain(10), 90_000)
+        );
+        assert_eq!(
+            "Drying in ~5 min",
+            format_weather_change(WeatherChange::Drying(5), 0)
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use std::fs::OpenOptions;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use f1_telemetry::packet::{Packet, UnpackError};
+use f1_telemetry::PacketSource;
+use f1_telemetry_common::signal::until_shutdown;
+
+use crate::Recovery;
+
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Writes every received packet to `path` as JSON lines, without any user interface, until
+/// interrupted with Ctrl-C or SIGTERM, or until `source` is exhausted. Progress is logged every
+/// `PROGRESS_INTERVAL`.
+pub(crate) async fn run<S: PacketSource>(mut source: S, path: &Path) -> io::Result<()> {
+    // Append, so that restarting the logger does not overwrite a previous recording
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut writer = BufWriter::new(file);
+    let mut count: u64 = 0;
+
+    info!("Recording packets to {}", path.display());
+
+    match until_shutdown(record(&mut source, &mut writer, &mut count)).await {
+        Some(res) => res?,
+        None => info!("Interrupted, {} packets recorded", count),
+    }
+
+    writer.flush()
+}
+
+async fn record<S: PacketSource, W: Write>(
+    source: &mut S,
+    writer: &mut W,
+    count: &mut u64,
+) -> io::Result<()> {
+    let mut recovery = Recovery::new();
+    let mut progress = tokio::time::interval(PROGRESS_INTERVAL);
+    let mut session_uid = None;
+
+    loop {
+        tokio::select! {
+            packet = source.next_packet() => match packet {
+                Ok(Some(p)) => {
+                    recovery.reset();
+                    session_uid = Some(p.header().session_uid);
+                    write_packet(writer, &p)?;
+                    *count += 1;
+                }
+                Ok(None) => {
+                    info!("No more packets, {} packets recorded", count);
+                    return Ok(());
+                }
+                Err(UnpackError::NotF1Packet) => {}
+                // The socket is unusable, e.g. the network interface went down
+                Err(e) if e.is_fatal() => recovery.recover(source, &e).await,
+                Err(e) => error!("{}", e),
+            },
+            _ = progress.tick() => {
+                writer.flush()?;
+
+                match session_uid {
+                    Some(uid) => info!("{} packets recorded, session {:016x}", count, uid),
+                    None => info!("Waiting for packets"),
+                }
+            }
+        }
+    }
+}
+
+fn write_packet<W: Write>(writer: &mut W, packet: &Packet) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, packet)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod test_headless {
+    use f1_telemetry::test_util::PacketSessionDataBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_packets_are_written_as_json_lines() {
+        let packet = Packet::Session(PacketSessionDataBuilder::default().build());
+
+        let mut buf = Vec::new();
+        write_packet(&mut buf, &packet).unwrap();
+        write_packet(&mut buf, &packet).unwrap();
+
+        let output = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with(r#"{"packet_type":"Session""#));
+    }
+}
@@ -3,19 +3,24 @@ extern crate lazy_static;
 #[macro_use]
 extern crate log;
 
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
 use clap::{Parser, ValueEnum};
 use simplelog::*;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::sync::RwLock;
 
-use f1_telemetry::packet::Packet;
-use f1_telemetry::Stream;
+use f1_telemetry::packet::{hex_dump, Packet, UnpackError};
+use f1_telemetry::{PacketSource, Stream};
 use f1_telemetry_common::logging::LogBuilder;
 
-use crate::ui::get_ui;
+use crate::ui::{get_ui, Theme};
 
 mod fmt;
+mod headless;
 mod models;
 mod ui;
 
@@ -28,16 +33,63 @@ enum UserInterface {
 #[derive(Parser)]
 #[command(author, version, about, long_about = None, propagate_version = true)]
 struct AppArgs {
-    /// Host to bind on for the UDP packet listener
-    #[clap(long, default_value = "0.0.0.0")]
-    host: String,
+    /// Address to bind on for the UDP packet listener
+    #[clap(long, default_value = "0.0.0.0:20777", env = "F1_TELEMETRY_BIND")]
+    bind: String,
+
+    /// Deprecated, use --bind instead
+    #[clap(long, hide = true)]
+    host: Option<String>,
+
+    /// Deprecated, use --bind instead
+    #[clap(long, hide = true)]
+    port: Option<u16>,
 
-    /// port to bind on for the UDP packet listener
-    #[clap(long, default_value = "20777")]
-    port: u16,
+    /// Addresses the received packets are re-sent to, so that other tools can use them too
+    #[clap(long, value_delimiter = ',')]
+    forward: Vec<SocketAddr>,
 
     #[arg(long, value_enum, default_value = "gtk")]
     ui: UserInterface,
+
+    /// Record packets to a JSON lines file instead of showing a user interface, e.g. over SSH
+    #[clap(long)]
+    headless: bool,
+
+    /// File the packets are appended to in headless mode
+    #[clap(long, default_value = "f1-telemetry-session.jsonl")]
+    output: PathBuf,
+
+    /// Maximum number of frames drawn per second by the ncurses UI, 0 to redraw on every packet
+    #[clap(long, default_value_t = 20)]
+    fps: u32,
+
+    /// Colors of the ncurses UI
+    #[arg(long, value_enum, default_value = "default")]
+    theme: Theme,
+
+    /// Number of telemetry packets shown in the throttle, brake and steering traces of the
+    /// ncurses UI
+    #[clap(long, default_value_t = 40)]
+    input_history: usize,
+}
+
+impl AppArgs {
+    /// Address of the UDP packet listener, built from the deprecated --host and --port options
+    /// when any of them is given, for compatibility with older scripts.
+    fn bind_address(&self) -> String {
+        if self.host.is_none() && self.port.is_none() {
+            return self.bind.clone();
+        }
+
+        warn!("--host and --port are deprecated, use --bind instead");
+
+        format!(
+            "{}:{}",
+            self.host.as_deref().unwrap_or("0.0.0.0"),
+            self.port.unwrap_or(20777)
+        )
+    }
 }
 
 struct StaticChannel {
@@ -64,30 +116,109 @@ async fn main() {
         .with_file_logger(LevelFilter::Info, "f1-telemetry-display.log")
         .expect("Unable to open log file.");
 
-    if args.ui == UserInterface::Gtk {
+    if args.headless {
+        log_builder = log_builder.with_term_logger(
+            LevelFilter::Info,
+            TerminalMode::Stderr,
+            ColorChoice::Auto,
+        );
+    } else if args.ui == UserInterface::Gtk {
         log_builder =
             log_builder.with_term_logger(LevelFilter::Info, TerminalMode::Mixed, ColorChoice::Auto);
     }
 
     log_builder.build().expect("Error initializing loggger.");
 
-    start_stream(args.host, args.port).await;
-    run(&args.ui).await;
+    let bind = args.bind_address();
+    let mut stream = match Stream::new(&bind).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            error!("Unable to bind socket on {}: {}", bind, e);
+            eprintln!("Unable to bind socket on {}: {}", bind, e);
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = stream.forward_to(&args.forward) {
+        error!("Unable to forward packets: {}", e);
+        eprintln!("Unable to forward packets: {}", e);
+        std::process::exit(1);
+    }
+
+    if args.headless {
+        if let Err(e) = headless::run(stream, &args.output).await {
+            error!("Error recording packets: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    configure_stream(&mut stream);
+    start_source(stream);
+    run(&args.ui, args.fps, args.theme, args.input_history).await;
 }
 
-async fn start_stream(host: String, port: u16) {
-    let stream = Stream::new(format!("{}:{}", host, port))
-        .await
-        .expect("Unable to bind socket");
+const REBIND_MIN_BACKOFF: Duration = Duration::from_secs(1);
+const REBIND_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Recreates a packet source after a fatal error, e.g. when the network interface went down,
+/// waiting longer after every consecutive failure.
+pub(crate) struct Recovery {
+    backoff: Duration,
+}
+
+impl Recovery {
+    pub(crate) fn new() -> Recovery {
+        Recovery {
+            backoff: REBIND_MIN_BACKOFF,
+        }
+    }
+
+    /// A packet was received, the next failure is recovered from quickly again.
+    pub(crate) fn reset(&mut self) {
+        self.backoff = REBIND_MIN_BACKOFF;
+    }
+
+    /// Waits for the current backoff, then recovers `source` from the fatal error `e`.
+    pub(crate) async fn recover<S: PacketSource>(&mut self, source: &mut S, e: &UnpackError) {
+        error!("{}, rebinding in {:?}", e, self.backoff);
+        tokio::time::sleep(self.backoff).await;
+        self.backoff = (self.backoff * 2).min(REBIND_MAX_BACKOFF);
+
+        if let Err(e) = source.recover().await {
+            error!("Unable to recover packet source: {}", e);
+        }
+    }
+}
 
+fn configure_stream(stream: &mut Stream) {
     info!("Listening on {}", stream.socket().local_addr().unwrap());
 
+    stream.on_parse_error(|data, e| {
+        if !matches!(e, UnpackError::NotF1Packet) {
+            debug!("Unable to parse packet ({}):\n{}", e, hex_dump(data));
+        }
+    });
+}
+
+/// Sends the packets of `source` to the user interface, until the source is exhausted.
+fn start_source<S: PacketSource + Send + 'static>(mut source: S) {
     tokio::spawn(async move {
+        let mut recovery = Recovery::new();
+
         loop {
-            match stream.next().await {
-                Ok(p) => {
+            match source.next_packet().await {
+                Ok(Some(p)) => {
+                    recovery.reset();
                     let _ = CHANNEL.tx.send(p);
                 }
+                Ok(None) => {
+                    info!("No more packets");
+                    break;
+                }
+                Err(UnpackError::NotF1Packet) => {}
+                // The socket is unusable, e.g. the network interface went down
+                Err(e) if e.is_fatal() => recovery.recover(&mut source, &e).await,
                 Err(_e) => {
                     error!("{:?}", _e);
                 }
@@ -96,11 +227,16 @@ async fn start_stream(host: String, port: u16) {
     });
 }
 
-async fn run(ui_type: &UserInterface) {
-    let mut ui = get_ui(match ui_type {
-        UserInterface::Gtk => "gtk",
-        UserInterface::Ncurses => "ncurses",
-    });
+async fn run(ui_type: &UserInterface, fps: u32, theme: Theme, input_history: usize) {
+    let mut ui = get_ui(
+        match ui_type {
+            UserInterface::Gtk => "gtk",
+            UserInterface::Ncurses => "ncurses",
+        },
+        fps,
+        theme,
+        input_history,
+    );
     ui.run().await;
     ui.destroy();
 }
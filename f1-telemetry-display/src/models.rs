@@ -1,20 +1,30 @@
 use std::cmp::Ordering;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::f32::INFINITY;
 
+use f1_telemetry::analysis::{
+    drs_allowed, Battle, DistanceHistory, FrameMonitor, LeaderGap, LeaderGaps, PersonalBests,
+    PositionChanges, TimeTrial, TimeTrialInfo,
+};
 use f1_telemetry::packet::car_damage::PacketCarDamageData;
-use f1_telemetry::packet::car_status::PacketCarStatusData;
-use f1_telemetry::packet::car_telemetry::PacketCarTelemetryData;
+use f1_telemetry::packet::car_setup::PacketCarSetupData;
+use f1_telemetry::packet::car_status::{
+    ERSDeployMode, FuelMix, PacketCarStatusData, TractionControl, DRS,
+};
+use f1_telemetry::packet::car_telemetry::{CarTelemetryData, PacketCarTelemetryData};
 use f1_telemetry::packet::event::{Event, PacketEventData};
 use f1_telemetry::packet::final_classification::PacketFinalClassificationData;
 use f1_telemetry::packet::generic::{
-    ResultStatus, SessionType, Team, TyreCompoundVisual, WheelData,
+    Flag, ResultStatus, SessionType, Team, TyreCompoundVisual, WheelData,
 };
-use f1_telemetry::packet::lap::{PacketLapData, PitStatus};
+use f1_telemetry::packet::header::PacketHeader;
+use f1_telemetry::packet::lap::{InvalidationReason, PacketLapData, PitStatus};
 use f1_telemetry::packet::motion::PacketMotionData;
 use f1_telemetry::packet::motion_ex::PacketMotionExData;
 use f1_telemetry::packet::participants::{Driver, PacketParticipantsData};
-use f1_telemetry::packet::session::{PacketSessionData, SafetyCar, Weather};
+use f1_telemetry::packet::session::{
+    ForecastAccuracy, MarshalZone, PacketSessionData, SafetyCar, Weather, WeatherForecastSample,
+};
 use f1_telemetry::packet::Packet;
 
 use crate::fmt;
@@ -38,18 +48,124 @@ pub struct GameState {
     pub participants: Vec<Participant>,
     pub car_status: CarStatus,
     pub telemetry_info: TelemetryInfo,
+    /// Telemetry of every car, by car index
+    pub car_telemetry: Vec<TelemetryInfo>,
+    pub tyre_info: TyreInfo,
     pub relative_positions: RelativePositions,
     pub final_classifications: Vec<FinalClassificationInfo>,
     pub motion_info: MotionInfo,
     pub player_index: u8,
+    pub spectator_index: Option<u8>,
     pub historical_race_data: HistoricalRaceData,
+    pub lap_history: LapHistory,
+    /// Recent inputs of the focused car
+    pub input_trace: InputTrace,
+    pub track_map: TrackMap,
+    personal_bests: PersonalBests,
+    position_changes: PositionChanges,
+    time_trial: TimeTrial,
+    frame_monitor: FrameMonitor,
+    leader_gaps: LeaderGaps,
+    /// Session time the focused car last set a personal best at, in milliseconds
+    pub personal_best_at: Option<u32>,
+    /// Session time of the last packet, in milliseconds
+    pub session_time: u32,
 }
 
+/// How long a personal best is highlighted for, in milliseconds
+const PERSONAL_BEST_HIGHLIGHT: u32 = 3000;
+/// Points scored by the first ten cars of a race
+const RACE_POINTS: [u16; 10] = [25, 18, 15, 12, 10, 8, 6, 4, 2, 1];
+
 impl GameState {
+    /// Keeps the inputs of the last `samples` telemetry packets in the [`InputTrace`].
+    pub fn with_input_history(mut self, samples: usize) -> Self {
+        self.input_trace = InputTrace::new(samples);
+        self
+    }
+
     pub fn update(&mut self, packet: &Packet) {
         self.validate_session(packet);
 
         self.parse(packet);
+        self.track_personal_bests(packet);
+        self.time_trial.observe(packet);
+        self.frame_monitor.observe(packet);
+
+        // The game only reports being paused in network games, where the session goes on: every
+        // packet is applied, but the values derived from the time elapsed are frozen so that the
+        // paused time is not accounted for
+        if !self.session_info.game_paused {
+            self.position_changes.observe(packet);
+            self.leader_gaps.observe(packet);
+        }
+    }
+
+    /// Whether a car is about to enter a DRS activation zone, and DRS is not ruled out there by the
+    /// session, see [`drs_allowed`]. Only F1 2021 and later report the zones.
+    pub fn drs_zone_ahead(&self, car_index: usize) -> bool {
+        let approaching = self
+            .car_telemetry
+            .get(car_index)
+            .and_then(|ti| ti.drs_activation_distance)
+            .is_some_and(|d| d > 0);
+
+        let available = || {
+            let info = &self.session_info;
+            // The lap distance is negative before crossing the line for the first time
+            let lap_distance = self
+                .lap_infos
+                .get(car_index)
+                .map(|li| li.lap_distance)
+                .filter(|d| *d >= 0.0)?;
+            let zone = MarshalZone::at(&info.marshal_zones, info.track_length, lap_distance)?;
+
+            Some(drs_allowed(zone, info.safety_car, info.weather))
+        };
+
+        approaching && available() == Some(true)
+    }
+
+    /// Gap of a running car to the race leader
+    pub fn leader_gap(&self, car_index: usize) -> Option<&LeaderGap> {
+        self.leader_gaps
+            .gaps()
+            .iter()
+            .find(|g| g.car_index as usize == car_index)
+    }
+
+    /// Number of packets lost in transit since the session started
+    pub fn dropped_packets(&self) -> u32 {
+        self.frame_monitor.dropped()
+    }
+
+    /// Player, personal best and rival laps, `None` outside of time trial
+    pub fn time_trial_info(&self) -> Option<TimeTrialInfo> {
+        self.time_trial.info()
+    }
+
+    /// Whether the focused car just set a personal best
+    pub fn is_personal_best_highlighted(&self) -> bool {
+        self.personal_best_at
+            .is_some_and(|at| self.session_time.saturating_sub(at) < PERSONAL_BEST_HIGHLIGHT)
+    }
+
+    fn track_personal_bests(&mut self, packet: &Packet) {
+        let header = packet.header();
+
+        if !self.session_info.game_paused {
+            self.session_time = header.session_time;
+        }
+
+        let focused = self.focused_car_index(header);
+        let personal_bests = self.personal_bests.observe(packet);
+
+        if personal_bests
+            .iter()
+            .any(|pb| pb.car_index as usize == focused)
+        {
+            self.personal_best_at = Some(header.session_time);
+        }
     }
 
     fn parse(&mut self, packet: &Packet) {
@@ -58,7 +174,7 @@ impl GameState {
             Packet::LapData(p) => self.parse_lap_data(p),
             Packet::Event(p) => self.parse_event_data(p),
             Packet::Participants(p) => self.parse_participants(p),
-            // Packet::CarqSetups(p) => p.header(),
+            Packet::CarSetups(p) => self.parse_car_setups(p),
             Packet::CarTelemetry(p) => self.parse_telemetry_data(p),
             Packet::CarStatus(p) => self.parse_car_status(p),
             Packet::FinalClassification(p) => self.parse_final_classification(p),
@@ -80,12 +196,19 @@ impl GameState {
 
         self.participants = Vec::new();
         self.lap_infos = Vec::new();
+        self.lap_history.clear();
+        self.input_trace.clear();
+        self.track_map = TrackMap::default();
+        self.personal_best_at = None;
+        self.session_info.game_paused = false;
     }
 
     fn parse_session_data(&mut self, session: &PacketSessionData) {
         self.session_info.session_type = session.session_type;
         self.session_info.track_name = session.track.name().into();
-        self.session_info.elapsed_time = session.session_duration - session.session_time_left;
+        if !session.game_paused {
+            self.session_info.elapsed_time = session.elapsed_time();
+        }
         self.session_info.duration = session.session_duration;
         self.session_info.number_of_laps = session.total_laps;
         self.session_info.safety_car = session.safety_car_status;
@@ -93,13 +216,44 @@ impl GameState {
         self.session_info.track_temperature = session.track_temperature;
         self.session_info.air_temperature = session.air_temperature;
         self.session_info.is_online = session.network_game;
-        self.player_index = session.header.player_car_index
+        self.session_info.game_paused = session.game_paused;
+        self.session_info.track_length = session.track_length;
+        self.session_info.pit_stop_window = parse_pit_stop_window(session);
+        self.session_info.marshal_zones = session.marshal_zones.clone();
+
+        if let Some(forecast) = &session.weather_forecast {
+            self.session_info.weather_forecast = forecast
+                .samples
+                .iter()
+                .filter(|s| s.session_type == session.session_type)
+                .copied()
+                .collect();
+            self.session_info.forecast_accuracy = forecast.accuracy;
+        }
+
+        self.spectator_index =
+            Some(session.spectator_car_index).filter(|idx| session.is_spectating && *idx != 255);
+
+        let focused_index = self.focused_car_index(&session.header) as u8;
+        if focused_index != self.player_index {
+            // The laps of the previously followed car are of no use as a reference
+            self.lap_history.clear();
+            self.input_trace.clear();
+        }
+        self.player_index = focused_index;
+    }
+
+    /// Index of the car followed by the dashboard: the spectated car when spectating, the
+    /// player's car otherwise.
+    fn focused_car_index(&self, header: &PacketHeader) -> usize {
+        self.spectator_index.unwrap_or(header.player_car_index) as usize
     }
 
     fn parse_lap_data(&mut self, lap_data: &PacketLapData) {
         self.parse_lap_data_times(lap_data);
         self.parse_lap_data_current_lap(lap_data);
         self.parse_lap_data_relative_positions(lap_data);
+        self.parse_lap_data_deltas(lap_data);
     }
 
     fn parse_lap_data_times(&mut self, lap_data: &PacketLapData) {
@@ -112,6 +266,14 @@ impl GameState {
             let ld = &lap_data.lap_data[idx];
             let li = &mut self.lap_infos[idx];
 
+            // The flag is reset when a new lap starts, keep the one of the lap being completed
+            let completed_lap_invalid = li.lap_invalid;
+
+            // The penalty may invalidate the next lap too, in which case the reason still holds
+            if ld.current_lap_num != li.current_lap_num && !ld.current_lap_invalid {
+                li.lap_invalid_reason = None;
+            }
+
             li.position = ld.car_position;
             li.current_lap_time = ld.current_lap_time;
             li.best_lap_time = ld.best_lap_time;
@@ -122,12 +284,9 @@ impl GameState {
             li.penalties = ld.penalties;
             li.lap_distance = ld.lap_distance;
             li.total_distance = ld.total_distance;
-            li.best_sector_1 = ld.best_overall_sector_1_time as u32;
-            li.best_sector_2 = ld.best_overall_sector_2_time as u32;
-            li.best_sector_3 = ld.best_overall_sector_3_time as u32;
 
-            let new_s1 = ld.sector_1_time as u32;
-            let new_s2 = ld.sector_2_time as u32;
+            let new_s1 = ld.sector_1_total_time();
+            let new_s2 = ld.sector_2_total_time();
             let new_ll = ld.last_lap_time;
 
             if new_s1 != li.sector_1 && new_s1 > 0 {
@@ -144,11 +303,10 @@ impl GameState {
                 li.last_lap_time = new_ll;
 
                 if li.sector_1 != 0 && li.sector_2 != 0 {
-                    // Hack to prevent inaccuracies with last_lap_time being a float, if possible.
-                    if ld.best_overall_sector_3_lap_num == li.current_lap_num - 1 {
-                        li.sector_3 = li.best_sector_3;
-                    } else {
-                        li.sector_3 = li.last_lap_time - li.sector_2 - li.sector_1;
+                    li.sector_3 = li.last_lap_time.saturating_sub(li.sector_1 + li.sector_2);
+
+                    if !completed_lap_invalid {
+                        li.update_best_sectors();
                     }
                 }
             }
@@ -222,6 +380,39 @@ impl GameState {
         }
     }
 
+    fn parse_lap_data_deltas(&mut self, lap_data: &PacketLapData) {
+        let time = lap_data.header.session_time;
+
+        if !self.session_info.game_paused {
+            for (li, ld) in self.lap_infos.iter_mut().zip(&lap_data.lap_data) {
+                li.distance_history.push(time, ld.total_distance);
+            }
+        }
+
+        let player_idx = self.focused_car_index(&lap_data.header);
+        let track_length = self.session_info.track_length as f32;
+
+        let deltas: Vec<Option<LapDelta>> = match self.lap_infos.get(player_idx) {
+            Some(player) => self
+                .lap_infos
+                .iter()
+                .enumerate()
+                .map(|(idx, li)| {
+                    if idx == player_idx || li.status != ResultStatus::Active {
+                        None
+                    } else {
+                        compute_lap_delta(li, player, time, track_length)
+                    }
+                })
+                .collect(),
+            None => vec![None; self.lap_infos.len()],
+        };
+
+        for (li, delta) in self.lap_infos.iter_mut().zip(deltas) {
+            li.delta = delta;
+        }
+    }
+
     fn parse_event_data(&mut self, event_data: &PacketEventData) {
         let evt = event_data.event;
 
@@ -236,15 +427,23 @@ impl GameState {
             None => None,
         };
 
+        if let Event::Penalty(p) = evt {
+            if let Some(reason) = InvalidationReason::from_penalty(&p) {
+                if let Some(li) = self.lap_infos.get_mut(p.vehicle_idx as usize) {
+                    li.lap_invalid_reason = Some(reason);
+                }
+            }
+        }
+
         let detail = match evt {
             Event::FastestLap(f) => Some(fmt::milliseconds_to_msf(f.lap_time)),
-            Event::Penalty(p) => Some(format!("{:?}", p.penalty_type)),
-            Event::SpeedTrap(s) => Some(format!("{:.1} km/h", s.speed)),
+            Event::Penalty(p) => Some(fmt::format_penalty(&p)),
             Event::StartLights(s) => Some(format!(
                 "{}{}",
                 "●".repeat(s.number_of_lights as usize),
                 "○".repeat(5 - s.number_of_lights as usize)
             )),
+            Event::Other(o) => Some(o.code()),
             _ => None,
         };
 
@@ -296,26 +495,95 @@ impl GameState {
     }
 
     fn parse_telemetry_data(&mut self, telemetry_data: &PacketCarTelemetryData) {
-        let player_index = telemetry_data.header.player_car_index;
-        let td = &telemetry_data.car_telemetry_data[player_index as usize];
-
-        self.telemetry_info.speed = td.speed;
-        self.telemetry_info.throttle = td.throttle;
-        self.telemetry_info.brake = td.brake;
-        self.telemetry_info.gear = td.gear;
-        self.telemetry_info.engine_rpm = td.engine_rpm;
-        self.telemetry_info.drs = td.drs;
-        self.telemetry_info.rev_lights_percent = td.rev_lights_percent;
-        self.telemetry_info.engine_temperature = td.engine_temperature;
+        let td = match telemetry_data
+            .car_telemetry_data
+            .get(self.focused_car_index(&telemetry_data.header))
+        {
+            Some(td) => td,
+            None => return,
+        };
+
+        // DRS permission and zones come from the car status packets
+        self.telemetry_info = TelemetryInfo {
+            drs_allowed: self.telemetry_info.drs_allowed,
+            drs_activation_distance: self.telemetry_info.drs_activation_distance,
+            ..TelemetryInfo::from(td)
+        };
+        self.car_telemetry
+            .resize_with(telemetry_data.car_telemetry_data.len(), Default::default);
+        for (ti, td) in self
+            .car_telemetry
+            .iter_mut()
+            .zip(&telemetry_data.car_telemetry_data)
+        {
+            *ti = TelemetryInfo {
+                drs_allowed: ti.drs_allowed,
+                drs_activation_distance: ti.drs_activation_distance,
+                ..TelemetryInfo::from(td)
+            };
+        }
 
         self.car_status.drs = td.drs;
-        self.telemetry_info.tyre_inner_temperature = td.tyres_inner_temperature;
-        self.telemetry_info.tyre_surface_temperature = td.tyres_surface_temperature;
+
+        self.tyre_info.surface_temperature = Some(td.tyres_surface_temperature);
+        self.tyre_info.inner_temperature = Some(td.tyres_inner_temperature);
+        self.tyre_info.brakes_temperature = Some(td.brakes_temperature);
+
+        self.input_trace.push(InputSample {
+            throttle: td.throttle,
+            brake: td.brake,
+            steer: td.steer,
+        });
+
+        let player_index = self.focused_car_index(&telemetry_data.header);
+        if let Some(li) = self.lap_infos.get(player_index) {
+            // The lap distance is negative until the line is crossed for the first time
+            if li.lap_distance >= 0.0 {
+                self.lap_history.push(
+                    li.current_lap_num,
+                    TelemetrySample {
+                        lap_distance: li.lap_distance,
+                        speed: td.speed,
+                        throttle: td.throttle,
+                        brake: td.brake,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Telemetry of the player's previous lap, at the player's current position on track.
+    pub(crate) fn previous_lap_sample(&self) -> Option<&TelemetrySample> {
+        let li = self.lap_infos.get(self.player_index as usize)?;
+
+        self.lap_history
+            .previous_lap(li.current_lap_num)
+            .and_then(|samples| LapHistory::sample_at(samples, li.lap_distance))
     }
 
     fn parse_motion_data(&mut self, motion_data: &PacketMotionData) {
-        let player_index = motion_data.header.player_car_index;
-        let md = &motion_data.motion_data[player_index as usize];
+        let focused_idx = self.focused_car_index(&motion_data.header);
+        let md = match motion_data.motion_data.get(focused_idx) {
+            Some(md) => md,
+            None => return,
+        };
+
+        self.track_map.positions = motion_data
+            .motion_data
+            .iter()
+            .map(|md| (md.world_position_x, md.world_position_z))
+            .collect();
+
+        if let Some(li) = self.lap_infos.get(focused_idx) {
+            // The pit lane is not part of the outline
+            if !li.in_pit {
+                self.track_map.record(
+                    li.lap_distance,
+                    self.session_info.track_length as f32,
+                    (md.world_position_x, md.world_position_z),
+                );
+            }
+        }
 
         if let Some(pcd) = &motion_data.player_car_data {
             self.motion_info.suspension_position = pcd.suspension_position;
@@ -393,6 +661,17 @@ impl GameState {
         }
     }
 
+    fn parse_car_setups(&mut self, car_setups: &PacketCarSetupData) {
+        let setup = car_setups
+            .car_setups
+            .get(self.focused_car_index(&car_setups.header));
+
+        // The setups of other players are blank in multiplayer sessions
+        self.car_status.differential = setup
+            .filter(|s| s.on_throttle > 0)
+            .map(|s| (s.on_throttle, s.off_throttle));
+    }
+
     fn parse_car_status(&mut self, car_status_data: &PacketCarStatusData) {
         for idx in 0..self.lap_infos.len() {
             let cs = &car_status_data.car_status_data[idx];
@@ -401,15 +680,43 @@ impl GameState {
             li.tyre_compound = cs.visual_tyre_compound;
         }
 
-        let player_index = car_status_data.header.player_car_index as usize;
-        let csd = &car_status_data.car_status_data[player_index];
+        for (ti, cs) in self
+            .car_telemetry
+            .iter_mut()
+            .zip(&car_status_data.car_status_data)
+        {
+            ti.drs_allowed = cs.drs_status == DRS::Allowed;
+            ti.drs_activation_distance = cs.drs_activation_distance;
+        }
+
+        let csd = match car_status_data
+            .car_status_data
+            .get(self.focused_car_index(&car_status_data.header))
+        {
+            Some(csd) => csd,
+            None => return,
+        };
 
         self.car_status.fuel_in_tank = csd.fuel_in_tank;
         self.car_status.fuel_remaining_laps = csd.fuel_remaining_laps;
         self.car_status.tyre_compound = csd.visual_tyre_compound;
         self.car_status.tyre_age_laps = csd.tyre_age_laps.unwrap_or_default();
+        self.car_status.fuel_capacity = csd.fuel_capacity;
+        self.car_status.fuel_mix = csd.fuel_mix;
+        self.car_status.front_brake_bias = csd.front_brake_bias;
+        self.car_status.traction_control = csd.traction_control;
+        self.car_status.anti_lock_brakes = csd.anti_lock_brakes;
+        self.car_status.ers_store_energy = csd.ers_store_energy;
+        self.car_status.ers_deploy_mode = csd.ers_deploy_mode;
+        self.car_status.ers_harvested_this_lap_mguk = csd.ers_harvested_this_lap_mguk;
+        self.car_status.ers_harvested_this_lap_mguh = csd.ers_harvested_this_lap_mguh;
+        self.car_status.ers_deployed_this_lap = csd.ers_deployed_this_lap;
+        self.telemetry_info.drs_allowed = csd.drs_status == DRS::Allowed;
+        self.telemetry_info.drs_activation_distance = csd.drs_activation_distance;
+        self.car_status.fia_flag = csd.vehicle_fia_flag;
 
         if car_status_data.header.packet_format <= 2020 {
+            self.tyre_info.wear = csd.tyres_wear.map(|w| w.map(f32::from));
             self.car_status.tyres_damage = csd.tyres_damage.unwrap_or_default();
             self.car_status.left_front_wing_damage = csd.front_left_wing_damage.unwrap_or_default();
             self.car_status.right_front_wing_damage =
@@ -420,7 +727,7 @@ impl GameState {
         }
 
         // TODO: Deduplicate this
-        let lap = self.get_player_current_lap(player_index);
+        let lap = self.get_player_current_lap(self.focused_car_index(&car_status_data.header));
         if lap.is_none() {
             return;
         }
@@ -465,10 +772,16 @@ impl GameState {
     }
 
     fn parse_car_damage(&mut self, car_damage_data: &PacketCarDamageData) {
-        let player_index = car_damage_data.header.player_car_index as usize;
-        let dmg = &car_damage_data.car_damage_data[player_index];
+        let dmg = match car_damage_data
+            .car_damage_data
+            .get(self.focused_car_index(&car_damage_data.header))
+        {
+            Some(dmg) => dmg,
+            None => return,
+        };
 
         self.car_status.tyres_damage = dmg.tyres_damage;
+        self.tyre_info.wear = Some(dmg.tyres_wear);
         self.car_status.left_front_wing_damage = dmg.front_left_wing_damage;
         self.car_status.right_front_wing_damage = dmg.front_right_wing_damage;
         self.car_status.rear_wing_damage = dmg.rear_wing_damage;
@@ -476,7 +789,7 @@ impl GameState {
         self.car_status.gearbox_damage = dmg.gear_box_damage;
 
         // TODO: Deduplicate this
-        let lap = self.get_player_current_lap(player_index);
+        let lap = self.get_player_current_lap(self.focused_car_index(&car_damage_data.header));
         if lap.is_none() {
             return;
         }
@@ -501,6 +814,145 @@ impl GameState {
         }
     }
 
+    /// Time gap between two cars, positive when `car` is ahead of `reference`. See [`LapDelta`].
+    pub fn time_gap(&self, car: usize, reference: usize) -> Option<LapDelta> {
+        let car = self.lap_infos.get(car)?;
+        let reference = self.lap_infos.get(reference)?;
+        let time = reference.distance_history.last_time()?;
+
+        compute_lap_delta(car, reference, time, self.session_info.track_length as f32)
+    }
+
+    /// Car following `car` in race position, wrapping around to the leader, to cycle through the
+    /// telemetry of the cars. Inactive cars are skipped.
+    pub fn next_telemetry_car(&self, car: usize) -> Option<usize> {
+        let mut cars: Vec<(u8, usize)> = self
+            .lap_infos
+            .iter()
+            .enumerate()
+            .filter(|(_, li)| li.status.is_valid() && li.status != ResultStatus::Inactive)
+            .map(|(idx, li)| (li.position, idx))
+            .collect();
+        cars.sort();
+
+        let next = match cars.iter().position(|(_, idx)| *idx == car) {
+            Some(i) => (i + 1) % cars.len(),
+            None => 0,
+        };
+
+        cars.get(next).map(|(_, idx)| *idx)
+    }
+
+    /// Cars close behind a car they recently swapped positions with, see
+    /// [`PositionChanges::battles`].
+    pub fn battles(&self) -> Vec<Battle> {
+        self.position_changes
+            .battles(|ahead, behind| match self.time_gap(ahead, behind)? {
+                LapDelta::Time(gap) => Some(gap),
+                _ => None,
+            })
+    }
+
+    /// Index of the car running one position ahead of `car`, if any.
+    pub fn car_ahead(&self, car: usize) -> Option<usize> {
+        let position = self.lap_infos.get(car)?.position;
+
+        if position <= 1 {
+            return None;
+        }
+
+        self.lap_infos
+            .iter()
+            .position(|li| li.position == position - 1 && li.status == ResultStatus::Active)
+    }
+
+    /// Teams ranked by the points their cars would score at their current positions, then by
+    /// their best placed car. Inactive and retired cars are left out.
+    pub fn team_standings(&self) -> Vec<TeamStanding> {
+        let mut standings: Vec<TeamStanding> = Vec::new();
+
+        for (participant, li) in self.participants.iter().zip(&self.lap_infos) {
+            if !matches!(li.status, ResultStatus::Active | ResultStatus::Finished)
+                || li.position == 0
+            {
+                continue;
+            }
+
+            let points = RACE_POINTS
+                .get(li.position as usize - 1)
+                .copied()
+                .unwrap_or(0);
+
+            match standings.iter_mut().find(|s| s.team == participant.team) {
+                Some(standing) => {
+                    standing.points += points;
+                    standing.positions.push(li.position);
+                }
+                None => standings.push(TeamStanding {
+                    team: participant.team,
+                    points,
+                    positions: vec![li.position],
+                }),
+            }
+        }
+
+        for standing in &mut standings {
+            standing.positions.sort();
+        }
+
+        standings.sort_by_key(|s| (std::cmp::Reverse(s.points), s.best_position()));
+        standings
+    }
+
+    /// Cars closest to `car` on track whatever their lap, up to `count` ahead and `count` behind,
+    /// from the furthest ahead to the furthest behind, with `car` itself in between. Cars that are
+    /// not running are left out.
+    pub fn relative_cars(&self, car: usize, count: usize) -> Vec<RelativeCar> {
+        let track_length = self.session_info.track_length as f32;
+
+        let reference = match self.lap_infos.get(car) {
+            Some(li) if track_length > 0.0 => li,
+            _ => return Vec::new(),
+        };
+
+        let time = reference.distance_history.last_time();
+
+        let (mut ahead, mut behind): (Vec<RelativeCar>, Vec<RelativeCar>) = self
+            .lap_infos
+            .iter()
+            .enumerate()
+            .filter(|(idx, li)| *idx != car && li.status == ResultStatus::Active)
+            .map(|(idx, li)| {
+                let gap = li.total_distance - reference.total_distance;
+                // Between half a lap behind and half a lap ahead
+                let distance = gap - (gap / track_length).round() * track_length;
+
+                RelativeCar {
+                    index: idx,
+                    distance,
+                    gap: time.and_then(|t| compute_relative_gap(li, reference, distance, t)),
+                    laps: ((gap - distance) / track_length).round() as i32,
+                }
+            })
+            .partition(|rc| rc.distance >= 0.0);
+
+        ahead.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        ahead.truncate(count);
+        ahead.reverse();
+
+        behind.sort_by(|a, b| b.distance.total_cmp(&a.distance));
+        behind.truncate(count);
+
+        ahead.push(RelativeCar {
+            index: car,
+            distance: 0.0,
+            gap: Some(0.0),
+            laps: 0,
+        });
+        ahead.extend(behind);
+        ahead
+    }
+
     fn get_player_current_lap(&self, player_index: usize) -> Option<u8> {
         if player_index >= self.lap_infos.len() {
             warn!(
@@ -540,6 +992,35 @@ pub struct Participant {
     pub team: Team,
 }
 
+/// Combined running order of a team's cars, see [`GameState::team_standings`]
+#[derive(Debug, PartialEq)]
+pub struct TeamStanding {
+    pub team: Team,
+    /// Points the team's cars would score if the race ended now
+    pub points: u16,
+    /// Positions of the team's cars, best first
+    pub positions: Vec<u8>,
+}
+
+impl TeamStanding {
+    pub fn best_position(&self) -> u8 {
+        self.positions[0]
+    }
+}
+
+/// Car running close to the focused car on track, see [`GameState::relative_cars`]
+#[derive(Debug, PartialEq)]
+pub struct RelativeCar {
+    pub index: usize,
+    /// Distance along the track in metres, positive when the car is ahead on track
+    pub distance: f32,
+    /// Time in seconds between the two cars on track, positive when the car is ahead on track
+    pub gap: Option<f32>,
+    /// Laps between the cars in addition to `distance`: positive when the car is that many laps
+    /// ahead in the race, e.g. about to lap the focused car, negative when it is being lapped
+    pub laps: i32,
+}
+
 pub struct EventInfo {
     pub timestamp: u32,
     pub description: String,
@@ -570,6 +1051,8 @@ pub struct LapInfo {
     pub status: ResultStatus,
     pub in_pit: bool,
     pub lap_invalid: bool,
+    /// Why the current lap was invalidated, from the last penalty event invalidating it
+    pub lap_invalid_reason: Option<InvalidationReason>,
     pub penalties: u8,
     pub lap_distance: f32,
     pub total_distance: f32,
@@ -580,6 +1063,100 @@ pub struct LapInfo {
     pub sector_1: u32,
     pub sector_2: u32,
     pub sector_3: u32,
+    pub delta: Option<LapDelta>,
+    pub distance_history: DistanceHistory,
+}
+
+impl LapInfo {
+    /// Sum of the driver's best sectors, or 0 until a valid lap has been completed.
+    pub fn theoretical_best_lap(&self) -> u32 {
+        if self.best_sector_3 > 0 {
+            self.best_sector_1 + self.best_sector_2 + self.best_sector_3
+        } else {
+            0
+        }
+    }
+
+    /// Updates the best sectors with the sectors of the lap that was just completed.
+    fn update_best_sectors(&mut self) {
+        for (best, sector) in [
+            (&mut self.best_sector_1, self.sector_1),
+            (&mut self.best_sector_2, self.sector_2),
+            (&mut self.best_sector_3, self.sector_3),
+        ] {
+            if sector > 0 && (*best == 0 || sector < *best) {
+                *best = sector;
+            }
+        }
+    }
+}
+
+/// Time gap between a car and the player's car, positive when the car is ahead of the player.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LapDelta {
+    /// Gap in seconds
+    Time(f32),
+    LapAhead,
+    LapBehind,
+}
+
+/// Time gap between two cars `distance` metres apart on track, whatever their lap: how long ago
+/// the car in front on track was where the other car is now.
+fn compute_relative_gap(
+    car: &LapInfo,
+    reference: &LapInfo,
+    distance: f32,
+    time: u32,
+) -> Option<f32> {
+    let (front, position) = if distance >= 0.0 {
+        (car, car.total_distance - distance)
+    } else {
+        (reference, reference.total_distance + distance)
+    };
+
+    let gap = (time as f32 - front.distance_history.time_at(position)?) / 1000.0;
+
+    Some(if distance >= 0.0 { gap } else { -gap })
+}
+
+fn compute_lap_delta(
+    car: &LapInfo,
+    player: &LapInfo,
+    time: u32,
+    track_length: f32,
+) -> Option<LapDelta> {
+    let gap = car.total_distance - player.total_distance;
+    let (leader, trailing) = if gap >= 0.0 {
+        (car, player)
+    } else {
+        (player, car)
+    };
+
+    // The distance history only covers the last couple of minutes, so whole laps between the cars
+    // are counted with the last lap time of the car in front
+    let laps = if track_length > 0.0 {
+        (gap.abs() / track_length).floor()
+    } else {
+        0.0
+    };
+
+    // The time gap is how long ago the car in front was where the car behind is now
+    let position = trailing.total_distance + laps * track_length;
+    let delta = match leader.distance_history.time_at(position) {
+        Some(t) if laps == 0.0 || leader.last_lap_time > 0 => {
+            (time as f32 - t + laps * leader.last_lap_time as f32) / 1000.0
+        }
+        _ if laps > 0.0 => {
+            return Some(if gap > 0.0 {
+                LapDelta::LapAhead
+            } else {
+                LapDelta::LapBehind
+            })
+        }
+        _ => return None,
+    };
+
+    Some(LapDelta::Time(if gap >= 0.0 { delta } else { -delta }))
 }
 
 #[derive(Default)]
@@ -595,6 +1172,150 @@ pub struct SessionInfo {
     pub track_temperature: i8,
     pub air_temperature: i8,
     pub is_online: bool,
+    /// Whether the game is paused, only sent in network games
+    pub game_paused: bool,
+    pub track_length: u16,
+    pub pit_stop_window: Option<PitStopWindow>,
+    pub weather_forecast: Vec<WeatherForecastSample>,
+    pub forecast_accuracy: ForecastAccuracy,
+    pub marshal_zones: Vec<MarshalZone>,
+}
+
+/// Pit strategy of the player's car, as predicted by the game (F1 2021+).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PitStopWindow {
+    pub ideal_lap: u8,
+    pub latest_lap: u8,
+    pub rejoin_position: u8,
+}
+
+fn parse_pit_stop_window(session: &PacketSessionData) -> Option<PitStopWindow> {
+    let player_index = session.header.player_car_index;
+
+    // The pit window is computed for the player's car, there is none when spectating
+    if session.is_spectating || player_index == 255 {
+        return None;
+    }
+
+    Some(PitStopWindow {
+        ideal_lap: session.pit_stop_window_ideal_lap?,
+        latest_lap: session.pit_stop_window_latest_lap?,
+        rejoin_position: session.pit_stop_rejoin_position?,
+    })
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct TelemetrySample {
+    pub lap_distance: f32,
+    pub speed: u16,
+    pub throttle: f32,
+    pub brake: f32,
+}
+
+const DEFAULT_LAP_HISTORY_SIZE: usize = 5;
+
+/// Telemetry samples of the player's car, for the last `max_laps` laps.
+pub struct LapHistory {
+    laps: BTreeMap<u8, Vec<TelemetrySample>>,
+    max_laps: usize,
+}
+
+impl Default for LapHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_LAP_HISTORY_SIZE)
+    }
+}
+
+impl LapHistory {
+    pub fn new(max_laps: usize) -> Self {
+        Self {
+            laps: BTreeMap::new(),
+            max_laps: max_laps.max(1),
+        }
+    }
+
+    pub fn push(&mut self, lap: u8, sample: TelemetrySample) {
+        let samples = self.laps.entry(lap).or_default();
+
+        // After a flashback, drop the part of the lap that is being driven again
+        let keep = samples.partition_point(|s| s.lap_distance < sample.lap_distance);
+        samples.truncate(keep);
+        samples.push(sample);
+
+        while self.laps.len() > self.max_laps {
+            self.laps.pop_first();
+        }
+    }
+
+    /// Samples of the last lap completed before `current_lap`.
+    pub fn previous_lap(&self, current_lap: u8) -> Option<&[TelemetrySample]> {
+        self.laps
+            .range(..current_lap)
+            .next_back()
+            .map(|(_, samples)| samples.as_slice())
+    }
+
+    /// The last sample taken before reaching `lap_distance`.
+    pub fn sample_at(samples: &[TelemetrySample], lap_distance: f32) -> Option<&TelemetrySample> {
+        let idx = samples.partition_point(|s| s.lap_distance <= lap_distance);
+
+        samples.get(idx.checked_sub(1)?)
+    }
+
+    pub fn clear(&mut self) {
+        self.laps.clear();
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct InputSample {
+    pub throttle: f32,
+    pub brake: f32,
+    pub steer: f32,
+}
+
+const DEFAULT_INPUT_TRACE_SIZE: usize = 40;
+
+/// Inputs of the focused car over the last `capacity` telemetry packets, oldest first.
+pub struct InputTrace {
+    samples: VecDeque<InputSample>,
+    capacity: usize,
+}
+
+impl Default for InputTrace {
+    fn default() -> Self {
+        Self::new(DEFAULT_INPUT_TRACE_SIZE)
+    }
+}
+
+impl InputTrace {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, sample: InputSample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn samples(&self) -> impl DoubleEndedIterator<Item = &InputSample> + ExactSizeIterator {
+        self.samples.iter()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
 }
 
 #[derive(Default)]
@@ -604,13 +1325,67 @@ pub struct TelemetryInfo {
     pub brake: f32,
     pub gear: i8,
     pub engine_rpm: u16,
+    /// DRS open
     pub drs: bool,
+    /// DRS permitted, from the car status packets
+    pub drs_allowed: bool,
+    /// Distance to the next DRS activation zone in metres, 0 if DRS is not available, from the
+    /// car status packets of F1 2021 and later
+    pub drs_activation_distance: Option<u16>,
     pub rev_lights_percent: u8,
     pub engine_temperature: u16,
     pub tyre_inner_temperature: WheelData<u16>,
     pub tyre_surface_temperature: WheelData<u16>,
 }
 
+/// DRS as shown on the onboards
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DrsState {
+    NotAllowed,
+    Allowed,
+    Open,
+}
+
+impl TelemetryInfo {
+    pub fn drs_state(&self) -> DrsState {
+        if self.drs {
+            DrsState::Open
+        } else if self.drs_allowed {
+            DrsState::Allowed
+        } else {
+            DrsState::NotAllowed
+        }
+    }
+}
+
+impl From<&CarTelemetryData> for TelemetryInfo {
+    fn from(td: &CarTelemetryData) -> Self {
+        TelemetryInfo {
+            speed: td.speed,
+            throttle: td.throttle,
+            brake: td.brake,
+            gear: td.gear,
+            engine_rpm: td.engine_rpm,
+            drs: td.drs,
+            drs_allowed: false,
+            drs_activation_distance: None,
+            rev_lights_percent: td.rev_lights_percent,
+            engine_temperature: td.engine_temperature,
+            tyre_inner_temperature: td.tyres_inner_temperature,
+            tyre_surface_temperature: td.tyres_surface_temperature,
+        }
+    }
+}
+
+/// Tyre data of the player car. Values are `None` until the corresponding packet is received.
+#[derive(Default)]
+pub struct TyreInfo {
+    pub surface_temperature: Option<WheelData<u16>>,
+    pub inner_temperature: Option<WheelData<u16>>,
+    pub brakes_temperature: Option<WheelData<u16>>,
+    pub wear: Option<WheelData<f32>>,
+}
+
 #[derive(Default)]
 pub struct MotionInfo {
     pub suspension_position: WheelData<f32>,
@@ -627,6 +1402,42 @@ pub struct MotionInfo {
     pub roll: f32,
 }
 
+const TRACK_MAP_OUTLINE_POINTS: usize = 200;
+
+/// Top-down map of the track, from the world positions of the cars on the X/Z plane
+#[derive(Default)]
+pub struct TrackMap {
+    /// Positions of the focused car, one per section of the lap, tracing the track outline
+    outline: Vec<Option<(f32, f32)>>,
+    /// Latest position of every car, by car index
+    pub positions: Vec<(f32, f32)>,
+}
+
+impl TrackMap {
+    /// Points of the track outline, once the focused car has covered a whole lap.
+    pub fn outline(&self) -> Option<Vec<(f32, f32)>> {
+        if self.outline.is_empty() {
+            return None;
+        }
+
+        self.outline.iter().copied().collect()
+    }
+
+    fn record(&mut self, lap_distance: f32, track_length: f32, position: (f32, f32)) {
+        // The lap distance is negative before crossing the line for the first time
+        if track_length <= 0.0 || lap_distance < 0.0 || lap_distance >= track_length {
+            return;
+        }
+
+        if self.outline.is_empty() {
+            self.outline = vec![None; TRACK_MAP_OUTLINE_POINTS];
+        }
+
+        let idx = (lap_distance / track_length * TRACK_MAP_OUTLINE_POINTS as f32) as usize;
+        self.outline[idx.min(TRACK_MAP_OUTLINE_POINTS - 1)] = Some(position);
+    }
+}
+
 #[derive(Default)]
 pub struct CarStatus {
     pub tyres_damage: WheelData<u8>,
@@ -636,10 +1447,25 @@ pub struct CarStatus {
     pub engine_damage: u8,
     pub gearbox_damage: u8,
     pub fuel_in_tank: f32,
+    pub fuel_capacity: f32,
     pub fuel_remaining_laps: f32,
+    pub fuel_mix: FuelMix,
+    /// Front brake bias, in percent
+    pub front_brake_bias: u8,
+    pub traction_control: TractionControl,
+    pub anti_lock_brakes: bool,
+    /// Differential adjustment on and off throttle, in percent, from the car setup
+    pub differential: Option<(u8, u8)>,
+    pub ers_store_energy: f32,
+    pub ers_deploy_mode: ERSDeployMode,
+    pub ers_harvested_this_lap_mguk: f32,
+    pub ers_harvested_this_lap_mguh: f32,
+    pub ers_deployed_this_lap: f32,
     pub tyre_compound: TyreCompoundVisual,
     pub tyre_age_laps: u8,
     pub drs: bool,
+    /// FIA flag shown to the car
+    pub fia_flag: Flag,
 }
 
 #[derive(Default)]
@@ -656,10 +1482,7 @@ pub struct TimedWheelData {
 
 impl TimedWheelData {
     pub(crate) fn sum(&self) -> u16 {
-        (self.tyre_damage.front_left
-            + self.tyre_damage.front_right
-            + self.tyre_damage.rear_left
-            + self.tyre_damage.rear_right) as u16
+        self.tyre_damage.iter().map(u16::from).sum()
     }
 }
 #[derive(Default, Clone, Copy)]
@@ -691,3 +1514,967 @@ pub struct FinalClassificationInfo {
     pub delta_time: u32,
     pub delta_laps: u8,
 }
+
+#[cfg(test)]
+mod test_best_sectors {
+    use f1_telemetry::test_util::{LapDataBuilder, PacketLapDataBuilder};
+
+    use super::*;
+
+    fn lap_data(
+        lap_num: u8,
+        sectors: (u16, u16),
+        last_lap_time: u32,
+        invalid: bool,
+    ) -> PacketLapData {
+        let car = LapDataBuilder::default()
+            .with_current_lap_num(lap_num)
+            .with_last_lap_time(last_lap_time)
+            .with_current_lap_invalid(invalid)
+            .with_sector_1_time(sectors.0)
+            .with_sector_2_time(sectors.1)
+            .build();
+
+        PacketLapDataBuilder::default().with_car(car).build()
+    }
+
+    fn game_state() -> GameState {
+        GameState {
+            lap_infos: vec![LapInfo::default()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_best_sectors_are_set_on_lap_completion() {
+        let mut gs = game_state();
+        gs.parse_lap_data_times(&lap_data(1, (30000, 0), 0, false));
+        gs.parse_lap_data_times(&lap_data(1, (30000, 31000), 0, false));
+
+        assert_eq!(0, gs.lap_infos[0].theoretical_best_lap());
+
+        gs.parse_lap_data_times(&lap_data(2, (0, 0), 92000, false));
+
+        let li = &gs.lap_infos[0];
+        assert_eq!(
+            (30000, 31000, 31000),
+            (li.best_sector_1, li.best_sector_2, li.best_sector_3)
+        );
+        assert_eq!(92000, li.theoretical_best_lap());
+        assert_eq!(31000, gs.session_best_times.sector_3);
+    }
+
+    #[test]
+    fn test_invalid_lap_sectors_are_ignored() {
+        let mut gs = game_state();
+        gs.parse_lap_data_times(&lap_data(1, (30000, 31000), 0, false));
+        gs.parse_lap_data_times(&lap_data(2, (0, 0), 92000, false));
+        gs.parse_lap_data_times(&lap_data(2, (29000, 30000), 92000, true));
+        gs.parse_lap_data_times(&lap_data(3, (0, 0), 89000, false));
+
+        let li = &gs.lap_infos[0];
+        assert_eq!(30000, li.sector_3);
+        assert_eq!(92000, li.theoretical_best_lap());
+    }
+
+    #[test]
+    fn test_best_sectors_can_come_from_different_laps() {
+        let mut gs = game_state();
+        gs.parse_lap_data_times(&lap_data(1, (30000, 31000), 0, false));
+        gs.parse_lap_data_times(&lap_data(2, (0, 0), 92000, false));
+        gs.parse_lap_data_times(&lap_data(2, (29000, 32000), 92000, false));
+        gs.parse_lap_data_times(&lap_data(3, (0, 0), 93000, false));
+
+        assert_eq!(91000, gs.lap_infos[0].theoretical_best_lap());
+    }
+}
+
+#[cfg(test)]
+mod test_lap_invalid_reason {
+    use f1_telemetry::packet::event::{InfringementType, Penalty, PenaltyType};
+    use f1_telemetry::test_util::{LapDataBuilder, PacketHeaderBuilder, PacketLapDataBuilder};
+
+    use super::*;
+
+    fn lap_data(lap_num: u8, invalid: bool) -> PacketLapData {
+        PacketLapDataBuilder::default()
+            .with_car(
+                LapDataBuilder::default()
+                    .with_current_lap_num(lap_num)
+                    .with_current_lap_invalid(invalid)
+                    .build(),
+            )
+            .build()
+    }
+
+    fn penalty(penalty_type: PenaltyType) -> PacketEventData {
+        PacketEventData {
+            header: PacketHeaderBuilder::default().build(),
+            event: Event::Penalty(Penalty {
+                vehicle_idx: 0,
+                penalty_type,
+                infringement_type: InfringementType::LapInvalidatedCornerCutting,
+                other_vehicle_idx: 255,
+                time: 255,
+                lap_num: 1,
+                places_gained: 255,
+            }),
+        }
+    }
+
+    fn game_state() -> GameState {
+        GameState {
+            lap_infos: vec![LapInfo::default()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reason_is_kept_until_the_next_lap() {
+        let mut gs = game_state();
+        gs.parse_lap_data_times(&lap_data(1, false));
+        gs.parse_event_data(&penalty(PenaltyType::ThisLapInvalidated));
+        gs.parse_lap_data_times(&lap_data(1, true));
+
+        assert_eq!(
+            Some(InvalidationReason::CornerCutting),
+            gs.lap_infos[0].lap_invalid_reason
+        );
+
+        gs.parse_lap_data_times(&lap_data(2, false));
+
+        assert_eq!(None, gs.lap_infos[0].lap_invalid_reason);
+    }
+
+    #[test]
+    fn test_reason_is_kept_when_the_next_lap_is_invalidated() {
+        let mut gs = game_state();
+        gs.parse_lap_data_times(&lap_data(1, false));
+        gs.parse_event_data(&penalty(PenaltyType::ThisAndNextLapInvalidated));
+        gs.parse_lap_data_times(&lap_data(2, true));
+
+        assert_eq!(
+            Some(InvalidationReason::CornerCutting),
+            gs.lap_infos[0].lap_invalid_reason
+        );
+    }
+
+    #[test]
+    fn test_penalty_without_reason_is_ignored() {
+        let mut gs = game_state();
+        gs.parse_lap_data_times(&lap_data(1, false));
+        gs.parse_event_data(&penalty(PenaltyType::ThisLapInvalidatedWithoutReason));
+
+        assert_eq!(None, gs.lap_infos[0].lap_invalid_reason);
+    }
+}
+
+#[cfg(test)]
+mod test_spectating {
+    use f1_telemetry::test_util::{PacketHeaderBuilder, PacketSessionDataBuilder};
+
+    use super::*;
+
+    fn session(is_spectating: bool, spectator_car_index: u8) -> PacketSessionData {
+        PacketSessionDataBuilder::default()
+            .with_header(
+                PacketHeaderBuilder::default()
+                    .with_player_car_index(0)
+                    .build(),
+            )
+            .with_is_spectating(is_spectating)
+            .with_spectator_car_index(spectator_car_index)
+            .build()
+    }
+
+    #[test]
+    fn test_spectated_car_is_followed() {
+        let mut gs = GameState::default();
+
+        let spectating = session(true, 3);
+        gs.parse_session_data(&spectating);
+        assert_eq!(3, gs.player_index);
+        assert_eq!(3, gs.focused_car_index(&spectating.header));
+
+        let driving = session(false, 3);
+        gs.parse_session_data(&driving);
+        assert_eq!(0, gs.player_index);
+        assert_eq!(0, gs.focused_car_index(&driving.header));
+    }
+
+    #[test]
+    fn test_unknown_spectated_car_falls_back_to_player() {
+        let mut gs = GameState::default();
+        gs.parse_session_data(&session(true, 255));
+
+        assert_eq!(None, gs.spectator_index);
+        assert_eq!(0, gs.player_index);
+    }
+}
+
+#[cfg(test)]
+mod test_lap_delta {
+    use super::*;
+
+    fn lap_info(samples: &[(u32, f32)]) -> LapInfo {
+        let mut li = LapInfo::default();
+
+        for (time, distance) in samples {
+            li.distance_history.push(*time, *distance);
+            li.total_distance = *distance;
+        }
+
+        li
+    }
+
+    #[test]
+    fn test_car_ahead_has_positive_delta() {
+        let car = lap_info(&[(1000, 100.0), (2000, 200.0), (3000, 300.0)]);
+        let player = lap_info(&[(1000, 50.0), (2000, 100.0), (3000, 150.0)]);
+
+        let delta = compute_lap_delta(&car, &player, 3000, 5000.0);
+        assert_eq!(Some(LapDelta::Time(1.5)), delta);
+
+        let delta = compute_lap_delta(&player, &car, 3000, 5000.0);
+        assert_eq!(Some(LapDelta::Time(-1.5)), delta);
+    }
+
+    #[test]
+    fn test_lapped_car_delta_adds_leader_lap_time() {
+        let mut car = lap_info(&[(1000, 5100.0), (2000, 5200.0), (3000, 5300.0)]);
+        car.last_lap_time = 90000;
+        let player = lap_info(&[(1000, 100.0), (2000, 150.0), (3000, 200.0)]);
+
+        let delta = compute_lap_delta(&car, &player, 3000, 5000.0);
+        assert_eq!(Some(LapDelta::Time(91.0)), delta);
+
+        let delta = compute_lap_delta(&player, &car, 3000, 5000.0);
+        assert_eq!(Some(LapDelta::Time(-91.0)), delta);
+    }
+
+    #[test]
+    fn test_time_gap_to_car_ahead() {
+        let mut game_state = GameState::default();
+        game_state.session_info.track_length = 5000;
+
+        for (position, samples) in [
+            (2, [(1000, 50.0), (2000, 100.0), (3000, 150.0)]),
+            (1, [(1000, 100.0), (2000, 200.0), (3000, 300.0)]),
+        ] {
+            let mut li = lap_info(&samples);
+            li.position = position;
+            li.status = ResultStatus::Active;
+            game_state.lap_infos.push(li);
+        }
+
+        assert_eq!(Some(1), game_state.car_ahead(0));
+        assert_eq!(None, game_state.car_ahead(1));
+        assert_eq!(Some(LapDelta::Time(1.5)), game_state.time_gap(1, 0));
+    }
+
+    #[test]
+    fn test_car_a_full_lap_ahead() {
+        let car = lap_info(&[(1000, 6000.0)]);
+        let player = lap_info(&[(1000, 500.0)]);
+
+        assert_eq!(
+            Some(LapDelta::LapAhead),
+            compute_lap_delta(&car, &player, 1000, 5000.0)
+        );
+        assert_eq!(
+            Some(LapDelta::LapBehind),
+            compute_lap_delta(&player, &car, 1000, 5000.0)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_relative_cars {
+    use super::*;
+
+    fn game_state(cars: &[(ResultStatus, [(u32, f32); 3])]) -> GameState {
+        let mut game_state = GameState::default();
+        game_state.session_info.track_length = 5000;
+
+        for (status, samples) in cars {
+            let mut li = LapInfo {
+                status: *status,
+                ..Default::default()
+            };
+            for (time, distance) in samples {
+                li.distance_history.push(*time, *distance);
+                li.total_distance = *distance;
+            }
+            game_state.lap_infos.push(li);
+        }
+
+        game_state
+    }
+
+    #[test]
+    fn test_closest_cars_on_track_whatever_their_lap() {
+        let gs = game_state(&[
+            (
+                ResultStatus::Active,
+                [(1000, 10100.0), (2000, 10200.0), (3000, 10300.0)],
+            ),
+            // A lap down, 100 m ahead on track
+            (
+                ResultStatus::Active,
+                [(1000, 5200.0), (2000, 5300.0), (3000, 5400.0)],
+            ),
+            // A lap up, 50 m behind on track
+            (
+                ResultStatus::Active,
+                [(1000, 15050.0), (2000, 15150.0), (3000, 15250.0)],
+            ),
+            // Half a lap behind
+            (
+                ResultStatus::Active,
+                [(1000, 12600.0), (2000, 12700.0), (3000, 12800.0)],
+            ),
+            (
+                ResultStatus::Retired,
+                [(1000, 10200.0), (2000, 10250.0), (3000, 10310.0)],
+            ),
+        ]);
+
+        let cars = gs.relative_cars(0, 3);
+
+        assert_eq!(
+            vec![
+                RelativeCar {
+                    index: 1,
+                    distance: 100.0,
+                    gap: Some(1.0),
+                    laps: -1,
+                },
+                RelativeCar {
+                    index: 0,
+                    distance: 0.0,
+                    gap: Some(0.0),
+                    laps: 0,
+                },
+                RelativeCar {
+                    index: 2,
+                    distance: -50.0,
+                    gap: Some(-0.5),
+                    laps: 1,
+                },
+                RelativeCar {
+                    index: 3,
+                    distance: -2500.0,
+                    gap: None,
+                    laps: 1,
+                },
+            ],
+            cars
+        );
+
+        let indexes: Vec<usize> = gs.relative_cars(0, 1).iter().map(|rc| rc.index).collect();
+        assert_eq!(vec![1, 0, 2], indexes);
+    }
+
+    #[test]
+    fn test_no_relative_cars_without_track_length() {
+        let mut gs = game_state(&[(
+            ResultStatus::Active,
+            [(1000, 100.0), (2000, 200.0), (3000, 300.0)],
+        )]);
+        gs.session_info.track_length = 0;
+
+        assert!(gs.relative_cars(0, 3).is_empty());
+        assert!(gs.relative_cars(5, 3).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_team_standings {
+    use super::*;
+
+    fn game_state(cars: &[(Team, u8, ResultStatus)]) -> GameState {
+        let mut gs = GameState::default();
+
+        for (team, position, status) in cars {
+            gs.participants.push(Participant {
+                name: String::new(),
+                driver: Driver::Player,
+                team: *team,
+            });
+            gs.lap_infos.push(LapInfo {
+                position: *position,
+                status: *status,
+                ..Default::default()
+            });
+        }
+
+        gs
+    }
+
+    #[test]
+    fn test_teams_are_ranked_by_points() {
+        let gs = game_state(&[
+            (Team::Ferrari, 1, ResultStatus::Active),
+            (Team::Mercedes, 2, ResultStatus::Active),
+            (Team::Mercedes, 3, ResultStatus::Active),
+            (Team::Ferrari, 12, ResultStatus::Active),
+            (Team::Haas, 11, ResultStatus::Active),
+        ]);
+
+        let standings = gs.team_standings();
+
+        assert_eq!(
+            vec![
+                TeamStanding {
+                    team: Team::Mercedes,
+                    points: 33,
+                    positions: vec![2, 3],
+                },
+                TeamStanding {
+                    team: Team::Ferrari,
+                    points: 25,
+                    positions: vec![1, 12],
+                },
+                TeamStanding {
+                    team: Team::Haas,
+                    points: 0,
+                    positions: vec![11],
+                },
+            ],
+            standings
+        );
+    }
+
+    #[test]
+    fn test_teams_without_points_are_ranked_by_best_car() {
+        let gs = game_state(&[
+            (Team::Haas, 14, ResultStatus::Active),
+            (Team::Williams, 11, ResultStatus::Active),
+            (Team::Haas, 20, ResultStatus::Active),
+        ]);
+
+        let teams: Vec<Team> = gs.team_standings().iter().map(|s| s.team).collect();
+
+        assert_eq!(vec![Team::Williams, Team::Haas], teams);
+    }
+
+    #[test]
+    fn test_retired_cars_are_left_out() {
+        let gs = game_state(&[
+            (Team::Alpine, 1, ResultStatus::Retired),
+            (Team::Alpine, 4, ResultStatus::Active),
+        ]);
+
+        let standings = gs.team_standings();
+
+        assert_eq!(1, standings.len());
+        assert_eq!(
+            (12, vec![4]),
+            (standings[0].points, standings[0].positions.clone())
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_telemetry_car {
+    use super::*;
+
+    fn game_state(cars: &[(u8, ResultStatus)]) -> GameState {
+        let mut gs = GameState::default();
+
+        for (position, status) in cars {
+            gs.lap_infos.push(LapInfo {
+                position: *position,
+                status: *status,
+                ..Default::default()
+            });
+        }
+
+        gs
+    }
+
+    #[test]
+    fn test_cycles_by_position() {
+        let gs = game_state(&[
+            (2, ResultStatus::Active),
+            (3, ResultStatus::Active),
+            (1, ResultStatus::Active),
+        ]);
+
+        assert_eq!(Some(0), gs.next_telemetry_car(2));
+        assert_eq!(Some(1), gs.next_telemetry_car(0));
+        assert_eq!(Some(2), gs.next_telemetry_car(1));
+    }
+
+    #[test]
+    fn test_skips_inactive_cars() {
+        let gs = game_state(&[
+            (1, ResultStatus::Active),
+            (2, ResultStatus::Inactive),
+            (3, ResultStatus::Retired),
+            (0, ResultStatus::Invalid),
+        ]);
+
+        assert_eq!(Some(2), gs.next_telemetry_car(0));
+        assert_eq!(Some(0), gs.next_telemetry_car(2));
+        assert_eq!(Some(0), gs.next_telemetry_car(1));
+    }
+
+    #[test]
+    fn test_no_cars() {
+        assert_eq!(None, GameState::default().next_telemetry_car(0));
+    }
+}
+
+#[cfg(test)]
+mod test_track_map {
+    use super::*;
+
+    #[test]
+    fn test_outline_is_known_after_a_lap() {
+        let mut track_map = TrackMap::default();
+        let track_length = 5000.0;
+        let step = track_length / TRACK_MAP_OUTLINE_POINTS as f32;
+
+        assert_eq!(None, track_map.outline());
+
+        for i in 0..TRACK_MAP_OUTLINE_POINTS - 1 {
+            track_map.record((i as f32 + 0.5) * step, track_length, (i as f32, 0.0));
+        }
+        assert_eq!(None, track_map.outline());
+
+        track_map.record(track_length - 1.0, track_length, (-1.0, 0.0));
+        let outline = track_map.outline().unwrap();
+        assert_eq!(TRACK_MAP_OUTLINE_POINTS, outline.len());
+        assert_eq!(Some(&(-1.0, 0.0)), outline.last());
+    }
+
+    #[test]
+    fn test_positions_before_the_line_are_ignored() {
+        let mut track_map = TrackMap::default();
+
+        track_map.record(-120.0, 5000.0, (1.0, 1.0));
+        track_map.record(100.0, 0.0, (1.0, 1.0));
+
+        assert!(track_map.outline.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_lap_history {
+    use super::*;
+
+    fn sample(lap_distance: f32, throttle: f32) -> TelemetrySample {
+        TelemetrySample {
+            lap_distance,
+            throttle,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_previous_lap_returns_last_completed_lap() {
+        let mut history = LapHistory::default();
+        history.push(1, sample(10.0, 0.1));
+        history.push(2, sample(10.0, 0.2));
+        history.push(3, sample(10.0, 0.3));
+
+        let previous = history.previous_lap(3).unwrap();
+        assert_eq!(previous, &[sample(10.0, 0.2)]);
+        assert!(history.previous_lap(1).is_none());
+    }
+
+    #[test]
+    fn test_history_keeps_last_laps_only() {
+        let mut history = LapHistory::new(2);
+
+        for lap in 1..=4 {
+            history.push(lap, sample(10.0, 0.0));
+        }
+
+        assert!(history.previous_lap(3).is_none());
+        assert!(history.previous_lap(4).is_some());
+    }
+
+    #[test]
+    fn test_sample_at_returns_closest_sample_before_distance() {
+        let samples = [sample(0.0, 0.0), sample(100.0, 0.5), sample(200.0, 1.0)];
+
+        assert_eq!(
+            LapHistory::sample_at(&samples, 150.0),
+            Some(&sample(100.0, 0.5))
+        );
+        assert_eq!(
+            LapHistory::sample_at(&samples, 250.0),
+            Some(&sample(200.0, 1.0))
+        );
+        assert_eq!(LapHistory::sample_at(&samples, -10.0), None);
+    }
+
+    #[test]
+    fn test_flashback_drops_samples_ahead() {
+        let mut history = LapHistory::default();
+        history.push(1, sample(100.0, 0.1));
+        history.push(1, sample(200.0, 0.2));
+        history.push(1, sample(150.0, 0.3));
+        history.push(2, sample(0.0, 0.0));
+
+        let previous = history.previous_lap(2).unwrap();
+        assert_eq!(previous, &[sample(100.0, 0.1), sample(150.0, 0.3)]);
+    }
+}
+
+#[cfg(test)]
+mod test_input_trace {
+    use f1_telemetry::test_util::{PacketCarTelemetryDataBuilder, PacketHeaderBuilder};
+
+    use super::*;
+
+    fn sample(throttle: f32) -> InputSample {
+        InputSample {
+            throttle,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_trace_keeps_last_samples_only() {
+        let mut trace = InputTrace::new(2);
+
+        for throttle in [0.1, 0.2, 0.3] {
+            trace.push(sample(throttle));
+        }
+
+        assert_eq!(
+            trace.samples().copied().collect::<Vec<_>>(),
+            vec![sample(0.2), sample(0.3)]
+        );
+    }
+
+    #[test]
+    fn test_trace_is_cleared_on_new_session() {
+        let mut state = GameState::default();
+
+        let telemetry = |session_uid| {
+            Packet::CarTelemetry(
+                PacketCarTelemetryDataBuilder::default()
+                    .with_header(
+                        PacketHeaderBuilder::default()
+                            .with_session_uid(session_uid)
+                            .build(),
+                    )
+                    .with_car(CarTelemetryData::default())
+                    .build(),
+            )
+        };
+
+        state.update(&telemetry(1));
+        state.update(&telemetry(1));
+        assert_eq!(state.input_trace.samples().len(), 2);
+
+        state.update(&telemetry(2));
+        assert_eq!(state.input_trace.samples().len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod test_drs {
+    use f1_telemetry::packet::car_status::CarStatusData;
+    use f1_telemetry::packet::car_telemetry::{MFDPanel, PacketCarTelemetryData};
+    use f1_telemetry::test_util::{
+        PacketCarStatusDataBuilder, PacketHeaderBuilder, PacketSessionDataBuilder,
+    };
+
+    use super::*;
+
+    fn telemetry(drs: bool) -> Packet {
+        Packet::CarTelemetry(PacketCarTelemetryData {
+            header: PacketHeaderBuilder::default().build(),
+            car_telemetry_data: vec![CarTelemetryData {
+                drs,
+                ..Default::default()
+            }],
+            button_status: None,
+            mfd_panel: MFDPanel::NotSet,
+            secondary_player_mfd_panel: MFDPanel::NotSet,
+            suggested_gear: None,
+        })
+    }
+
+    fn car_status(drs_status: DRS) -> Packet {
+        Packet::CarStatus(
+            PacketCarStatusDataBuilder::default()
+                .with_car(CarStatusData {
+                    drs_status,
+                    ..Default::default()
+                })
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_drs_allowed_is_kept_across_telemetry_packets() {
+        let mut gs = GameState::default();
+
+        gs.update(&telemetry(false));
+        assert_eq!(gs.telemetry_info.drs_state(), DrsState::NotAllowed);
+
+        gs.update(&car_status(DRS::Allowed));
+        gs.update(&telemetry(false));
+        assert_eq!(gs.telemetry_info.drs_state(), DrsState::Allowed);
+        assert_eq!(gs.car_telemetry[0].drs_state(), DrsState::Allowed);
+
+        gs.update(&telemetry(true));
+        assert_eq!(gs.telemetry_info.drs_state(), DrsState::Open);
+
+        gs.update(&car_status(DRS::NotAllowed));
+        gs.update(&telemetry(false));
+        assert_eq!(gs.car_telemetry[0].drs_state(), DrsState::NotAllowed);
+    }
+
+    #[test]
+    fn test_drs_zone_ahead() {
+        let mut gs = GameState::default();
+
+        let session = |flag| {
+            Packet::Session(
+                PacketSessionDataBuilder::default()
+                    .with_track_length(1000)
+                    .with_marshal_zones(vec![MarshalZone {
+                        zone_start: 0.0,
+                        zone_flag: flag,
+                    }])
+                    .build(),
+            )
+        };
+        let approaching = Packet::CarStatus(
+            PacketCarStatusDataBuilder::default()
+                .with_car(CarStatusData {
+                    drs_activation_distance: Some(150),
+                    ..Default::default()
+                })
+                .build(),
+        );
+
+        gs.update(&telemetry(false));
+        gs.lap_infos.push(LapInfo {
+            lap_distance: 300.0,
+            ..Default::default()
+        });
+        gs.update(&approaching);
+        assert!(!gs.drs_zone_ahead(0));
+
+        gs.update(&session(Flag::None));
+        assert!(gs.drs_zone_ahead(0));
+
+        gs.update(&session(Flag::Yellow));
+        assert!(!gs.drs_zone_ahead(0));
+    }
+}
+
+#[cfg(test)]
+mod test_personal_best {
+    use f1_telemetry::test_util::{PacketHeaderBuilder, PacketLapDataBuilder};
+
+    use super::*;
+
+    fn lap_data(session_time: u32, lap: u8, last_lap_time: u32) -> Packet {
+        Packet::LapData(
+            PacketLapDataBuilder::default()
+                .with_header(PacketHeaderBuilder::in_session(1, session_time).build())
+                .with_cars([lap], |car, lap| {
+                    car.with_current_lap_num(lap)
+                        .with_last_lap_time(last_lap_time)
+                })
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_personal_best_is_highlighted_for_a_while() {
+        let mut gs = GameState::default();
+
+        // The first lap was joined midway
+        gs.update(&lap_data(1000, 1, 0));
+        gs.update(&lap_data(90000, 2, 89000));
+        assert!(!gs.is_personal_best_highlighted());
+
+        gs.update(&lap_data(180000, 3, 90000));
+        assert!(gs.is_personal_best_highlighted());
+
+        gs.update(&lap_data(183000, 3, 90000));
+        assert!(!gs.is_personal_best_highlighted());
+
+        gs.update(&lap_data(272000, 4, 92000));
+        assert!(!gs.is_personal_best_highlighted());
+
+        gs.update(&lap_data(357000, 5, 85000));
+        assert!(gs.is_personal_best_highlighted());
+    }
+}
+
+#[cfg(test)]
+mod test_battles {
+    use f1_telemetry::test_util::{PacketHeaderBuilder, PacketLapDataBuilder};
+
+    use super::*;
+
+    fn positions(session_time: u32, positions: &[u8]) -> Packet {
+        Packet::LapData(
+            PacketLapDataBuilder::default()
+                .with_header(PacketHeaderBuilder::in_session(1, session_time).build())
+                .with_cars(positions, |car, &position| car.with_car_position(position))
+                .build(),
+        )
+    }
+
+    fn game_state(behind: &[(u32, f32)], ahead: &[(u32, f32)]) -> GameState {
+        let mut game_state = GameState::default();
+        game_state.session_info.track_length = 5000;
+
+        for (position, samples) in [(2, behind), (1, ahead)] {
+            let mut li = LapInfo {
+                position,
+                status: ResultStatus::Active,
+                ..Default::default()
+            };
+            for (time, distance) in samples {
+                li.distance_history.push(*time, *distance);
+                li.total_distance = *distance;
+            }
+            game_state.lap_infos.push(li);
+        }
+
+        game_state
+    }
+
+    #[test]
+    fn test_close_cars_that_swapped_are_battling() {
+        let mut gs = game_state(
+            &[(1000, 80.0), (2000, 180.0), (3000, 280.0)],
+            &[(1000, 100.0), (2000, 200.0), (3000, 300.0)],
+        );
+
+        assert!(gs.battles().is_empty());
+
+        gs.position_changes.observe(&positions(1000, &[1, 2]));
+        gs.position_changes.observe(&positions(2000, &[2, 1]));
+
+        let battles = gs.battles();
+        assert_eq!(1, battles.len());
+        assert_eq!(
+            (1, 0),
+            (battles[0].car_ahead_index, battles[0].car_behind_index)
+        );
+        assert!((battles[0].gap - 0.2).abs() < 1e-6);
+
+        // The swap is too old
+        gs.position_changes.observe(&positions(70000, &[2, 1]));
+        assert!(gs.battles().is_empty());
+    }
+
+    #[test]
+    fn test_distant_cars_are_not_battling() {
+        let mut gs = game_state(
+            &[(1000, 50.0), (2000, 100.0), (3000, 150.0)],
+            &[(1000, 100.0), (2000, 200.0), (3000, 300.0)],
+        );
+
+        gs.position_changes.observe(&positions(1000, &[1, 2]));
+        gs.position_changes.observe(&positions(2000, &[2, 1]));
+
+        assert!(gs.battles().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_car_setup {
+    use f1_telemetry::packet::car_setup::CarSetupData;
+    use f1_telemetry::test_util::PacketHeaderBuilder;
+
+    use super::*;
+
+    fn car_setups(on_throttle: u8, off_throttle: u8) -> Packet {
+        Packet::CarSetups(PacketCarSetupData {
+            header: PacketHeaderBuilder::default().build(),
+            car_setups: vec![CarSetupData {
+                on_throttle,
+                off_throttle,
+                ..Default::default()
+            }],
+        })
+    }
+
+    #[test]
+    fn test_differential_from_setup() {
+        let mut gs = GameState::default();
+        assert_eq!(gs.car_status.differential, None);
+
+        gs.update(&car_setups(75, 55));
+        assert_eq!(gs.car_status.differential, Some((75, 55)));
+
+        // Blank setup of another player
+        gs.update(&car_setups(0, 0));
+        assert_eq!(gs.car_status.differential, None);
+    }
+}
+
+#[cfg(test)]
+mod test_game_paused {
+    use f1_telemetry::test_util::{
+        PacketHeaderBuilder, PacketLapDataBuilder, PacketSessionDataBuilder,
+    };
+
+    use super::*;
+
+    fn session(game_paused: bool, session_time: u32) -> Packet {
+        Packet::Session(
+            PacketSessionDataBuilder::default()
+                .with_header(PacketHeaderBuilder::in_session(1, session_time).build())
+                .with_session_time_left(3000)
+                .with_session_duration(3600)
+                .with_game_paused(game_paused)
+                .build(),
+        )
+    }
+
+    fn lap_data(session_time: u32, position: u8) -> Packet {
+        Packet::LapData(
+            PacketLapDataBuilder::default()
+                .with_header(PacketHeaderBuilder::in_session(1, session_time).build())
+                .with_cars([position], |car, position| car.with_car_position(position))
+                .build(),
+        )
+    }
+
+    #[test]
+    fn test_clock_is_frozen_while_paused() {
+        let mut gs = GameState::default();
+
+        gs.update(&session(false, 0));
+        gs.update(&lap_data(1000, 1));
+        assert_eq!(gs.session_time, 1000);
+        assert_eq!(gs.session_info.elapsed_time, 600);
+
+        gs.update(&session(true, 1000));
+        assert!(gs.session_info.game_paused);
+        gs.update(&lap_data(2000, 1));
+        assert_eq!(gs.session_time, 1000);
+
+        gs.update(&session(false, 1000));
+        gs.update(&lap_data(3000, 1));
+        assert_eq!(gs.session_time, 3000);
+    }
+
+    #[test]
+    fn test_packets_are_applied_while_paused() {
+        let mut gs = GameState::default();
+
+        gs.update(&session(true, 0));
+        gs.lap_infos.push(LapInfo::default());
+        gs.update(&lap_data(1000, 2));
+
+        assert_eq!(gs.lap_infos[0].position, 2);
+        assert_eq!(gs.lap_infos[0].distance_history.last_time(), None);
+    }
+}
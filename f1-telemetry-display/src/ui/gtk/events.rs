@@ -4,6 +4,7 @@ use gtk::{InfoBar, Label, MessageType, ResponseType, Widget};
 use f1_telemetry::packet::event::Event;
 
 use crate::fmt;
+use crate::fmt::UnitSystem;
 use crate::models::GameState;
 
 pub(super) struct EventsView {
@@ -44,8 +45,10 @@ impl EventsView {
         self.message_label.hide();
         self.info_bar.hide();
 
-        self.message_label
-            .set_label(&fmt::format_event_info(&game_state.event_info));
+        self.message_label.set_label(&fmt::format_event_info(
+            &game_state.event_info,
+            UnitSystem::default(),
+        ));
         self.info_bar
             .set_message_type(get_message_type(&game_state.event_info.event));
 
@@ -60,7 +63,7 @@ impl EventsView {
 
 fn get_message_type(event: &Event) -> MessageType {
     match event {
-        Event::Penalty(_) => MessageType::Error,
+        Event::Penalty(_) | Event::RedFlag => MessageType::Error,
         Event::DRSDisabled | Event::Retirement(_) => MessageType::Warning,
         _ => MessageType::Info,
     }
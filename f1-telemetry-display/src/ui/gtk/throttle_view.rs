@@ -2,6 +2,7 @@ use gtk::prelude::*;
 use gtk::{Align, Orientation, Widget};
 
 use crate::fmt;
+use crate::fmt::UnitSystem;
 use crate::models::GameState;
 
 pub(super) struct ThrottleView {
@@ -53,7 +54,7 @@ impl ThrottleView {
         // Dummy data
         throttle_bar.set_value(0.75);
         brake_bar.set_value(0.25);
-        speed_lbl.set_text(&fmt::format_speed(420));
+        speed_lbl.set_text(&fmt::format_speed(420, UnitSystem::default()));
         gear_lbl.set_text(&fmt::format_gear(8));
 
         Self {
@@ -72,8 +73,10 @@ impl ThrottleView {
         let brake = games_state.telemetry_info.brake;
         self.brake_bar.set_value(brake as f64);
 
-        self.speed_lbl
-            .set_text(&fmt::format_speed(games_state.telemetry_info.speed));
+        self.speed_lbl.set_text(&fmt::format_speed(
+            games_state.telemetry_info.speed,
+            UnitSystem::default(),
+        ));
         self.gear_lbl
             .set_text(&fmt::format_gear(games_state.telemetry_info.gear));
     }
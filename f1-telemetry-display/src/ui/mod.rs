@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use clap::ValueEnum;
 
 use crate::ui::{gtk::GtkUi, nc::NcursesUi};
 
@@ -14,10 +15,28 @@ pub trait Ui {
     fn destroy(&self);
 }
 
-pub fn get_ui(ui: &str) -> Box<dyn Ui> {
+/// Color palette of the ncurses UI
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, ValueEnum)]
+pub enum Theme {
+    #[default]
+    Default,
+    /// Blue and orange instead of green and red, for red-green color blindness
+    Deuteranopia,
+    /// Only bright colors
+    HighContrast,
+}
+
+/// `fps` limits the frame rate, `theme` sets the colors and `input_history` the length of the
+/// input traces of the ncurses UI, the GTK UI redraws on every packet and keeps its own style.
+pub fn get_ui(ui: &str, fps: u32, theme: Theme, input_history: usize) -> Box<dyn Ui> {
     match ui {
         "gtk" => Box::new(GtkUi::new()),
-        "ncurses" => Box::new(NcursesUi::new()),
+        "ncurses" => Box::new(
+            NcursesUi::new()
+                .with_fps(fps)
+                .with_theme(theme)
+                .with_input_history(input_history),
+        ),
         _ => panic!("Invalid ui: {}", ui),
     }
 }
@@ -1,3 +1,4 @@
+use std::mem;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -5,20 +6,29 @@ use ncurses::*;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::SendError;
 use tokio::sync::mpsc::UnboundedSender;
-use tokio::time::sleep;
+use tokio::time::{interval, sleep, Interval, MissedTickBehavior};
 
+use f1_telemetry::analysis::{weather_change, DEFAULT_RAIN_THRESHOLD};
 use f1_telemetry::packet::generic::{ResultStatus, SessionType, TyreCompoundVisual};
 use f1_telemetry::packet::session::SafetyCar;
 use f1_telemetry::packet::Packet;
+use f1_telemetry_common::signal::shutdown_signal;
 
 use crate::fmt as cfmt;
+use crate::fmt::UnitSystem;
 use crate::models::*;
-use crate::ui::Ui;
+use crate::ui::{Theme, Ui};
 
 mod car;
+mod ers;
 mod fmt;
+mod gforce;
+mod inputs;
+mod map;
 mod suspension;
+mod tyres;
 mod weather;
+mod wheel;
 
 const WIDTH: i32 = 132;
 const HEIGHT: i32 = 35;
@@ -26,12 +36,24 @@ const SESSION_Y_OFFSET: i32 = 1;
 const WINDOW_Y_OFFSET: i32 = 5;
 const LEFT_BORDER_X_OFFSET: i32 = 2;
 const CURRENT_CAR_DATA_Y_OFFSET: i32 = 24;
+const CAR_X_OFFSET: i32 = 40;
+const REV_LIGHTS_X_OFFSET: i32 = 36;
+const GAP_AHEAD_X_OFFSET: i32 = 24;
+const INPUT_TRACES_X_OFFSET: i32 = 69;
+const MARSHAL_ZONES_HEIGHT: i32 = 3;
+/// Cars shown ahead and behind the focused car in the relative view
+const RELATIVE_CARS: usize = 3;
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum View {
     Dashboard,
     TrackOverview,
     LapDetail,
+    Tyres,
+    Energy,
+    GForce,
+    TimeTrial,
+    Relative,
 }
 
 struct DashboardView {
@@ -44,6 +66,7 @@ struct DashboardView {
 
 struct TrackView {
     win: WINDOW,
+    map_swnd: WINDOW,
 }
 
 struct LapDetailView {
@@ -53,19 +76,65 @@ struct LapDetailView {
     handling_swnd: WINDOW,
 }
 
+struct TyresView {
+    win: WINDOW,
+}
+
+struct EnergyView {
+    win: WINDOW,
+}
+
+struct GForceView {
+    win: WINDOW,
+    meter_swnd: WINDOW,
+}
+
+struct TimeTrialView {
+    win: WINDOW,
+    laps_swnd: WINDOW,
+}
+
+struct RelativeView {
+    win: WINDOW,
+    cars_swnd: WINDOW,
+}
+
 pub struct NcursesUi {
     main_window: WINDOW,
     active_view: View,
     dashboard_view: DashboardView,
     track_view: TrackView,
     lap_detail_view: LapDetailView,
+    tyres_view: TyresView,
+    energy_view: EnergyView,
+    g_force_view: GForceView,
+    time_trial_view: TimeTrialView,
+    relative_view: RelativeView,
     session_rotation: bool,
+    units: UnitSystem,
+    too_small: bool,
+    /// Car shown in the telemetry of the dashboard and whose lap invalidation is explained in the
+    /// lap details, `None` for the focused car
+    telemetry_car: Option<usize>,
+    /// Whether the dashboard shows the gaps to the leader instead of the deltas to the focused car
+    leader_gaps: bool,
+    /// Whether the dashboard groups the cars by team instead of listing them
+    team_standings: bool,
+    /// Minimum time between two frames, `None` to redraw on every packet
+    frame_interval: Option<Duration>,
+    /// Number of telemetry packets shown in the input traces
+    input_history: usize,
 }
 
 enum Event {
     UpdateGame(Box<Packet>),
     SwitchView(View),
     EnableRotation,
+    ToggleUnits,
+    ToggleLeaderGaps,
+    ToggleTeamStandings,
+    CycleTelemetryCar,
+    Resize,
     Quit,
 }
 
@@ -75,70 +144,29 @@ impl Ui for NcursesUi {
         setlocale(ncurses::LcCategory::all, "");
 
         let mwnd = initscr();
-
-        let w = getmaxx(mwnd);
-        let h = getmaxy(mwnd);
-
-        if w < WIDTH || h < HEIGHT {
-            endwin();
-
-            panic!(
-                "Terminal must be at least {}x{}. Current size: {}x{}",
-                WIDTH, HEIGHT, w, h
-            );
-        }
+        restore_terminal_on_panic();
 
         curs_set(CURSOR_VISIBILITY::CURSOR_INVISIBLE);
         cbreak();
         noecho();
         keypad(mwnd, true);
         timeout(-1);
-        fmt::init_colors();
-
-        wresize(mwnd, h, w);
-
-        refresh();
-
-        let win_w = w - 2;
-        let win_h = h - WINDOW_Y_OFFSET - 2;
-
-        let dashboard_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Dashboard"));
-        let tyres_swnd = derwin(dashboard_wnd, 23, 2, 1, 2);
-        let lap_times_swnd = derwin(dashboard_wnd, 23, 80, 1, 4);
-        let car_swnd = derwin(dashboard_wnd, 24, 39, 1, win_w - 40);
-        // let motion_swnd = derwin(dashboard_wnd, 15, 30, 3, win_w-100);
-        let rel_pos_swnd = derwin(
-            dashboard_wnd,
-            13,
-            getmaxx(dashboard_wnd) / 2,
-            getmaxy(dashboard_wnd) - 15,
-            2,
-        );
-        let handling_swnd = derwin(dashboard_wnd, 23, 58, win_h - 24, win_w - 60);
+        fmt::init_colors(Theme::default());
 
-        let dashboard_view = DashboardView {
-            win: dashboard_wnd,
-            tyres_swnd,
-            lap_times_swnd,
-            car_swnd,
-            rel_pos_swnd,
-        };
+        wait_for_min_size(mwnd);
 
-        let track_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Track Status"));
-        let track_view = TrackView { win: track_wnd };
-
-        let laps_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Lap Details"));
-        let lap_detail_swnd = derwin(laps_wnd, 23, 123, 1, 4);
-        let best_sectors_swnd = derwin(laps_wnd, 2, 80, 24, 3);
-
-        let lap_detail_view = LapDetailView {
-            win: laps_wnd,
-            lap_detail_swnd,
-            best_sectors_swnd,
-            handling_swnd,
-        };
+        let (
+            dashboard_view,
+            track_view,
+            lap_detail_view,
+            tyres_view,
+            energy_view,
+            g_force_view,
+            time_trial_view,
+            relative_view,
+        ) = Self::create_views(mwnd);
 
-        wrefresh(dashboard_wnd);
+        wrefresh(dashboard_view.win);
 
         Self {
             main_window: mwnd,
@@ -146,7 +174,19 @@ impl Ui for NcursesUi {
             dashboard_view,
             track_view,
             lap_detail_view,
+            tyres_view,
+            energy_view,
+            g_force_view,
+            time_trial_view,
+            relative_view,
             session_rotation: false,
+            units: UnitSystem::default(),
+            too_small: false,
+            telemetry_car: None,
+            leader_gaps: false,
+            team_standings: false,
+            frame_interval: None,
+            input_history: InputTrace::default().capacity(),
         }
     }
 
@@ -165,15 +205,50 @@ impl Ui for NcursesUi {
             let _ = process_input(sender.clone()).await;
         });
 
-        let mut game_state = GameState::default();
+        // Ctrl-C and SIGTERM go through the same path as `q`, so that the terminal is restored on
+        // exit
+        let sender = tx.clone();
+        let signal_thread = tokio::spawn(async move {
+            if shutdown_signal().await.is_ok() {
+                let _ = sender.send(Event::Quit);
+            }
+        });
+
+        let mut game_state = GameState::default().with_input_history(self.input_history);
+
+        // Packets update the game state as they arrive, but are only drawn at the frame rate
+        let mut frame = self.frame_interval.map(|period| {
+            let mut frame = interval(period);
+            frame.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            frame
+        });
+        let mut pending = PendingPackets::default();
+
+        loop {
+            let evt = tokio::select! {
+                evt = rx.recv() => match evt {
+                    Some(evt) => evt,
+                    None => break,
+                },
+                _ = next_frame(&mut frame), if !pending.is_empty() => {
+                    for p in pending.take() {
+                        self.render(&game_state, &p).await;
+                    }
+                    continue;
+                }
+            };
 
-        while let Some(evt) = rx.recv().await {
             match evt {
                 Event::UpdateGame(p) => {
                     debug!("Packet");
                     trace!("Packet: {:?}", p);
                     game_state.update(&p);
-                    self.render(&game_state, &p).await;
+
+                    if frame.is_some() {
+                        pending.push(*p);
+                    } else {
+                        self.render(&game_state, &p).await;
+                    }
                 }
                 Event::SwitchView(v) => {
                     debug!("Switch View: {:?}", v);
@@ -184,6 +259,36 @@ impl Ui for NcursesUi {
                     debug!("Enable Rotation");
                     self.enable_rotation();
                 }
+                Event::ToggleUnits => {
+                    self.units = self.units.toggle();
+                    debug!("Units: {:?}", self.units);
+                }
+                Event::ToggleLeaderGaps => {
+                    self.leader_gaps = !self.leader_gaps;
+                    debug!("Leader gaps: {}", self.leader_gaps);
+                }
+                Event::ToggleTeamStandings => {
+                    self.team_standings = !self.team_standings;
+                    debug!("Team standings: {}", self.team_standings);
+                }
+                Event::CycleTelemetryCar => {
+                    let focused = game_state.player_index as usize;
+                    let current = self.telemetry_car.unwrap_or(focused);
+
+                    // Cycling back to the focused car follows it again, e.g. when spectating
+                    self.telemetry_car = game_state
+                        .next_telemetry_car(current)
+                        .filter(|idx| *idx != focused);
+                    debug!("Telemetry car: {:?}", self.telemetry_car);
+                }
+                Event::Resize => {
+                    debug!(
+                        "Resize: {}x{}",
+                        getmaxx(self.main_window),
+                        getmaxy(self.main_window)
+                    );
+                    self.resize();
+                }
                 Event::Quit => {
                     debug!("Quit");
                     break;
@@ -199,6 +304,8 @@ impl Ui for NcursesUi {
         stream_thread.abort();
         debug!("Done");
 
+        signal_thread.abort();
+
         rx.close();
     }
 
@@ -220,7 +327,224 @@ impl Ui for NcursesUi {
 
 unsafe impl Send for NcursesUi {}
 
+/// Packets received since the last frame. Only the latest packet of each type is kept, as the
+/// game state already holds the data of the earlier ones.
+#[derive(Default)]
+struct PendingPackets(Vec<Packet>);
+
+impl PendingPackets {
+    fn push(&mut self, packet: Packet) {
+        let kind = mem::discriminant(&packet);
+
+        match self.0.iter_mut().find(|p| mem::discriminant(*p) == kind) {
+            Some(p) => *p = packet,
+            None => self.0.push(packet),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn take(&mut self) -> Vec<Packet> {
+        mem::take(&mut self.0)
+    }
+}
+
+/// Waits for the next frame, forever when the frame rate is not limited.
+async fn next_frame(frame: &mut Option<Interval>) {
+    match frame {
+        Some(frame) => {
+            frame.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
 impl NcursesUi {
+    /// Limits the frame rate to `fps` frames per second, 0 to redraw on every packet.
+    pub(crate) fn with_fps(mut self, fps: u32) -> Self {
+        self.frame_interval = (fps > 0).then(|| Duration::from_secs(1) / fps);
+        self
+    }
+
+    /// Shows the inputs of the last `samples` telemetry packets in the input traces.
+    pub(crate) fn with_input_history(mut self, samples: usize) -> Self {
+        self.input_history = samples;
+        self
+    }
+
+    /// Colors the throttle and brake bars, flags and damage levels with the palette of `theme`.
+    pub(crate) fn with_theme(self, theme: Theme) -> Self {
+        fmt::init_colors(theme);
+        self
+    }
+
+    fn create_views(
+        mwnd: WINDOW,
+    ) -> (
+        DashboardView,
+        TrackView,
+        LapDetailView,
+        TyresView,
+        EnergyView,
+        GForceView,
+        TimeTrialView,
+        RelativeView,
+    ) {
+        let w = getmaxx(mwnd);
+        let h = getmaxy(mwnd);
+
+        wresize(mwnd, h, w);
+
+        refresh();
+
+        let win_w = w - 2;
+        let win_h = h - WINDOW_Y_OFFSET - 2;
+
+        let dashboard_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Dashboard"));
+        let tyres_swnd = derwin(dashboard_wnd, 23, 2, 1, 2);
+        let lap_times_swnd = derwin(dashboard_wnd, 23, 86, 1, 4);
+        let car_swnd = derwin(dashboard_wnd, 24, 39, 1, win_w - CAR_X_OFFSET);
+        // let motion_swnd = derwin(dashboard_wnd, 15, 30, 3, win_w-100);
+        let rel_pos_swnd = derwin(
+            dashboard_wnd,
+            13,
+            getmaxx(dashboard_wnd) / 2,
+            getmaxy(dashboard_wnd) - 15,
+            2,
+        );
+        let handling_swnd = derwin(dashboard_wnd, 23, 58, win_h - 24, win_w - 60);
+
+        let dashboard_view = DashboardView {
+            win: dashboard_wnd,
+            tyres_swnd,
+            lap_times_swnd,
+            car_swnd,
+            rel_pos_swnd,
+        };
+
+        let track_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Track Status"));
+        let map_swnd = derwin(track_wnd, win_h - 2 - MARSHAL_ZONES_HEIGHT, 86, 1, 2);
+        let track_view = TrackView {
+            win: track_wnd,
+            map_swnd,
+        };
+
+        let laps_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Lap Details"));
+        let lap_detail_swnd = derwin(laps_wnd, 23, 123, 1, 4);
+        let best_sectors_swnd = derwin(laps_wnd, 3, 120, 24, 3);
+
+        let lap_detail_view = LapDetailView {
+            win: laps_wnd,
+            lap_detail_swnd,
+            best_sectors_swnd,
+            handling_swnd,
+        };
+
+        let tyres_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Tyres"));
+        let tyres_view = TyresView { win: tyres_wnd };
+
+        let energy_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("ERS & Fuel"));
+        let energy_view = EnergyView { win: energy_wnd };
+
+        let g_force_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("G-Force"));
+        let meter_swnd = derwin(g_force_wnd, win_h - 2, win_w - 2, 1, 1);
+        let g_force_view = GForceView {
+            win: g_force_wnd,
+            meter_swnd,
+        };
+
+        let time_trial_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Time Trial"));
+        let laps_swnd = derwin(time_trial_wnd, 5, 80, 1, 4);
+        let time_trial_view = TimeTrialView {
+            win: time_trial_wnd,
+            laps_swnd,
+        };
+
+        let relative_wnd = Self::create_win(win_h, win_w, WINDOW_Y_OFFSET, 1, Some("Relative"));
+        let cars_swnd = derwin(relative_wnd, 2 * RELATIVE_CARS as i32 + 2, 80, 1, 4);
+        let relative_view = RelativeView {
+            win: relative_wnd,
+            cars_swnd,
+        };
+
+        (
+            dashboard_view,
+            track_view,
+            lap_detail_view,
+            tyres_view,
+            energy_view,
+            g_force_view,
+            time_trial_view,
+            relative_view,
+        )
+    }
+
+    fn delete_views(&self) {
+        // Sub-windows must be deleted before their parent
+        for w in [
+            self.dashboard_view.tyres_swnd,
+            self.dashboard_view.lap_times_swnd,
+            self.dashboard_view.car_swnd,
+            self.dashboard_view.rel_pos_swnd,
+            self.lap_detail_view.handling_swnd,
+            self.lap_detail_view.lap_detail_swnd,
+            self.lap_detail_view.best_sectors_swnd,
+            self.g_force_view.meter_swnd,
+            self.time_trial_view.laps_swnd,
+            self.relative_view.cars_swnd,
+            self.track_view.map_swnd,
+            self.dashboard_view.win,
+            self.track_view.win,
+            self.lap_detail_view.win,
+            self.tyres_view.win,
+            self.energy_view.win,
+            self.g_force_view.win,
+            self.time_trial_view.win,
+            self.relative_view.win,
+        ] {
+            delwin(w);
+        }
+    }
+
+    fn resize(&mut self) {
+        if !is_large_enough(self.main_window) {
+            self.too_small = true;
+            render_too_small(self.main_window);
+            return;
+        }
+
+        self.too_small = false;
+
+        self.delete_views();
+        clear();
+
+        let (
+            dashboard_view,
+            track_view,
+            lap_detail_view,
+            tyres_view,
+            energy_view,
+            g_force_view,
+            time_trial_view,
+            relative_view,
+        ) = Self::create_views(self.main_window);
+
+        self.dashboard_view = dashboard_view;
+        self.track_view = track_view;
+        self.lap_detail_view = lap_detail_view;
+        self.tyres_view = tyres_view;
+        self.energy_view = energy_view;
+        self.g_force_view = g_force_view;
+        self.time_trial_view = time_trial_view;
+        self.relative_view = relative_view;
+
+        let w = self.active_window();
+        redrawwin(w);
+        self.commit(w);
+    }
+
     fn create_win(h: i32, w: i32, y: i32, x: i32, title: Option<&str>) -> WINDOW {
         let wnd = newwin(h, w, y, x);
         box_(wnd, 0, 0);
@@ -233,6 +557,10 @@ impl NcursesUi {
     }
 
     async fn render(&mut self, game_state: &GameState, packet: &Packet) {
+        if self.too_small {
+            return;
+        }
+
         self.render_main_view(game_state, packet);
 
         if self.session_rotation {
@@ -243,6 +571,11 @@ impl NcursesUi {
             View::Dashboard => self.render_dashboard_view(game_state, packet),
             View::TrackOverview => self.render_track_view(game_state, packet),
             View::LapDetail => self.render_lap_view(game_state, packet),
+            View::Tyres => self.render_tyres_view(game_state, packet),
+            View::Energy => self.render_energy_view(game_state, packet),
+            View::GForce => self.render_g_force_view(game_state, packet),
+            View::TimeTrial => self.render_time_trial_view(game_state, packet),
+            View::Relative => self.render_relative_view(game_state, packet),
         };
     }
 
@@ -257,6 +590,7 @@ impl NcursesUi {
     fn rotate_view(&mut self, game_state_session: SessionType) {
         match game_state_session {
             SessionType::Race => self.switch_view(View::Dashboard),
+            SessionType::TimeTrial => self.switch_view(View::TimeTrial),
             _ => self.switch_view(View::LapDetail),
         }
     }
@@ -266,18 +600,30 @@ impl NcursesUi {
             return;
         }
 
-        let neww = match &view {
-            View::Dashboard => self.dashboard_view.win,
-            View::TrackOverview => self.track_view.win,
-            View::LapDetail => self.lap_detail_view.win,
-        };
-
         self.active_view = view;
 
+        if self.too_small {
+            return;
+        }
+
+        let neww = self.active_window();
         redrawwin(neww);
         self.commit(neww);
     }
 
+    fn active_window(&self) -> WINDOW {
+        match self.active_view {
+            View::Dashboard => self.dashboard_view.win,
+            View::TrackOverview => self.track_view.win,
+            View::LapDetail => self.lap_detail_view.win,
+            View::Tyres => self.tyres_view.win,
+            View::Energy => self.energy_view.win,
+            View::GForce => self.g_force_view.win,
+            View::TimeTrial => self.time_trial_view.win,
+            View::Relative => self.relative_view.win,
+        }
+    }
+
     fn commit(&self, w: WINDOW) {
         fmt::wreset(w);
         wrefresh(w);
@@ -295,6 +641,9 @@ impl NcursesUi {
             Packet::LapData(_) => {
                 self.print_session_info(game_state);
             }
+            Packet::CarStatus(_) => {
+                self.print_fia_flag(game_state);
+            }
             _ => {}
         }
     }
@@ -317,6 +666,8 @@ impl NcursesUi {
                 // self.print_motion_info(&game_state);
                 self.print_handling_info(game_state);
             }
+            Packet::Session(_) => self.print_pit_strategy(game_state),
+            Packet::CarSetups(_) => self.print_car_status(game_state),
             _ => {}
         }
     }
@@ -342,12 +693,50 @@ impl NcursesUi {
         match packet {
             Packet::LapData(_) => {
                 self.print_track_status_lap_info(game_state);
+                self.print_marshal_zones(game_state);
             }
-            Packet::Session(_) => self.print_weather_info(game_state),
+            Packet::Session(_) => {
+                self.print_weather_info(game_state);
+                self.print_marshal_zones(game_state);
+            }
+            Packet::Motion(_) => self.print_track_map(game_state),
             _ => {}
         }
     }
 
+    fn render_tyres_view(&self, game_state: &GameState, packet: &Packet) {
+        match packet {
+            Packet::CarTelemetry(_) | Packet::CarStatus(_) | Packet::CarDamage(_) => {
+                self.print_tyres_info(game_state)
+            }
+            _ => {}
+        }
+    }
+
+    fn render_energy_view(&self, game_state: &GameState, packet: &Packet) {
+        if let Packet::CarStatus(_) = packet {
+            self.print_energy_info(game_state)
+        }
+    }
+
+    fn render_g_force_view(&self, game_state: &GameState, packet: &Packet) {
+        if let Packet::Motion(_) = packet {
+            self.print_g_force_info(game_state)
+        }
+    }
+
+    fn render_time_trial_view(&self, game_state: &GameState, packet: &Packet) {
+        if let Packet::LapData(_) | Packet::SessionHistory(_) = packet {
+            self.print_time_trial_info(game_state)
+        }
+    }
+
+    fn render_relative_view(&self, game_state: &GameState, packet: &Packet) {
+        if let Packet::LapData(_) = packet {
+            self.print_relative_info(game_state)
+        }
+    }
+
     fn print_session_info(&self, game_state: &GameState) {
         let sinfo = &game_state.session_info;
 
@@ -359,17 +748,57 @@ impl NcursesUi {
         addstr_center(self.main_window, SESSION_Y_OFFSET + 1, &lap_info);
         addstr_center(self.main_window, SESSION_Y_OFFSET + 2, &session_time);
 
-        if sinfo.safety_car == SafetyCar::Virtual || sinfo.safety_car == SafetyCar::Full {
-            fmt::blink_colour(COLOR_WHITE, COLOR_YELLOW);
-            addstr_center(
-                self.main_window,
-                SESSION_Y_OFFSET + 3,
-                sinfo.safety_car.name(),
-            );
+        let dropped = game_state.dropped_packets();
+        if dropped > 0 {
+            let packet_loss = format!("Packet loss: {}", dropped);
+            let x = getmaxx(self.main_window) - packet_loss.len() as i32 - LEFT_BORDER_X_OFFSET;
+
+            fmt::set_color(None, COLOR_YELLOW);
+            mvaddstr(SESSION_Y_OFFSET, x, &packet_loss);
             fmt::reset();
-        } else {
-            wmove(self.main_window, SESSION_Y_OFFSET + 3, 0);
-            clrtoeol();
+        }
+
+        let banner = match sinfo.safety_car {
+            _ if sinfo.game_paused => Some("PAUSED"),
+            SafetyCar::Full => Some("SAFETY CAR"),
+            SafetyCar::Virtual => Some("VSC"),
+            SafetyCar::None => None,
+        };
+
+        match banner {
+            Some(banner) => {
+                if sinfo.game_paused {
+                    fmt::set_color(None, COLOR_WHITE);
+                } else {
+                    fmt::blink_colour(COLOR_WHITE, COLOR_YELLOW);
+                }
+                attron(A_REVERSE() | A_BOLD());
+                addstr_banner(self.main_window, SESSION_Y_OFFSET + 3, banner);
+                fmt::reset();
+            }
+            None => {
+                wmove(self.main_window, SESSION_Y_OFFSET + 3, 0);
+                clrtoeol();
+            }
+        }
+    }
+
+    fn print_fia_flag(&self, game_state: &GameState) {
+        let flag = game_state.car_status.fia_flag;
+
+        match fmt::flag_color(flag) {
+            Some(color) => {
+                let banner = format!("{} FLAG", format!("{:?}", flag).to_uppercase());
+
+                fmt::set_color(None, color);
+                attron(A_REVERSE() | A_BOLD());
+                addstr_banner(self.main_window, 0, &banner);
+                fmt::reset();
+            }
+            None => {
+                wmove(self.main_window, 0, 0);
+                clrtoeol();
+            }
         }
     }
 
@@ -380,10 +809,18 @@ impl NcursesUi {
         fmt::wset_bold(wnd);
 
         let header =
-            "  P. NAME            | CURRENT   | LAST      | BEST      | SECTOR 1  | SECTOR 2  | SECTOR 3  | STATUS ";
+            "  P. NAME            | CURRENT   | LAST      | BEST      | SECTOR 1  | SECTOR 2  | SECTOR 3  | THEO BEST | STATUS ";
 
         mvwaddstr(wnd, 0, 0, header);
 
+        let best_theoretical_lap = game_state
+            .lap_infos
+            .iter()
+            .map(LapInfo::theoretical_best_lap)
+            .filter(|t| *t > 0)
+            .min()
+            .unwrap_or(0);
+
         for (idx, li) in game_state.lap_infos.iter().enumerate() {
             if let ResultStatus::Invalid = li.status {
                 continue;
@@ -407,7 +844,7 @@ impl NcursesUi {
             };
 
             let s = format!(
-                "                     | {} |           |           |           |           |           | {}{}{} ",
+                "                     | {} |           |           |           |           |           |           | {}{}{} ",
                 cfmt::milliseconds_to_msf(li.current_lap_time),
                 if li.in_pit { "P" } else { " " },
                 if li.lap_invalid { "!" } else { " " },
@@ -469,6 +906,16 @@ impl NcursesUi {
             );
             mvwaddstr(wnd, row, 83, &s);
 
+            let theoretical_lap = li.theoretical_best_lap();
+            let s = cfmt::milliseconds_to_msf(theoretical_lap);
+            let color = if theoretical_lap > 0 && theoretical_lap == best_theoretical_lap {
+                fmt::Color::Magenta
+            } else {
+                fmt::Color::White
+            };
+            fmt::set_color(Some(wnd), color as i16);
+            mvwaddstr(wnd, row, 95, &s);
+
             fmt::reset_color(Some(wnd));
         }
 
@@ -498,6 +945,82 @@ impl NcursesUi {
         );
         mvwaddstr(wnd, 1, 2, s.as_str());
 
+        let car_index = self
+            .telemetry_car
+            .unwrap_or(game_state.player_index as usize);
+
+        if let Some((li, participant)) = game_state
+            .lap_infos
+            .get(car_index)
+            .zip(game_state.participants.get(car_index))
+        {
+            if let Some(reason) = li.lap_invalid_reason.filter(|_| li.lap_invalid) {
+                let s = format!(
+                    "{} lap invalidated: {}",
+                    cfmt::format_driver_name(participant, game_state.session_info.is_online),
+                    reason
+                );
+
+                fmt::set_color(Some(wnd), COLOR_RED);
+                mvwaddstr(wnd, 2, 2, &s);
+                fmt::reset_color(Some(wnd));
+            }
+        }
+
+        self.commit(wnd);
+    }
+
+    fn print_time_trial_info(&self, game_state: &GameState) {
+        let wnd = self.time_trial_view.laps_swnd;
+
+        werase(wnd);
+        fmt::wset_bold(wnd);
+
+        let Some(info) = game_state.time_trial_info() else {
+            mvwaddstr(wnd, 0, 0, "Not in a time trial session");
+            self.commit(wnd);
+            return;
+        };
+
+        let header = "               | LAP       | SECTOR 1  | SECTOR 2  | SECTOR 3  | DELTA";
+        mvwaddstr(wnd, 0, 0, header);
+
+        let laps = [
+            ("PLAYER BEST", info.player_best),
+            ("PERSONAL BEST", info.personal_best),
+            ("RIVAL", info.rival),
+        ];
+
+        for (row, (name, lap)) in laps.iter().enumerate() {
+            let y = row as i32 + 1;
+
+            fmt::set_color(Some(wnd), COLOR_WHITE);
+            mvwaddstr(wnd, y, 0, &format!("{:14} | ", name));
+
+            let Some(lap) = lap else {
+                waddstr(wnd, "-");
+                continue;
+            };
+
+            waddstr(
+                wnd,
+                &format!("{} ", cfmt::milliseconds_to_msf(lap.lap_time)),
+            );
+
+            for (sector, time) in lap.sector_times.iter().enumerate() {
+                let player_time = info.player_best.map(|p| p.sector_times[sector]);
+                fmt::set_color(Some(wnd), time_trial_color(player_time, *time));
+                waddstr(wnd, &format!("| {} ", cfmt::milliseconds_to_msf(*time)));
+            }
+
+            let player_time = info.player_best.map(|p| p.lap_time);
+            fmt::set_color(Some(wnd), time_trial_color(player_time, lap.lap_time));
+            waddstr(
+                wnd,
+                &format!("| {}", format_time_trial_delta(player_time, lap.lap_time)),
+            );
+        }
+
         self.commit(wnd);
     }
 
@@ -633,15 +1156,24 @@ impl NcursesUi {
     // }
 
     fn print_dashboard_lap_info(&self, game_state: &GameState) {
+        if self.team_standings {
+            return self.print_dashboard_team_standings(game_state);
+        }
+
         let wnd = self.dashboard_view.lap_times_swnd;
 
         werase(wnd);
 
         fmt::wset_bold(wnd);
 
-        let header = "  P. NAME                 | CURRENT LAP | LAST LAP    | BEST LAP    | STATUS";
+        let header =
+            "  P. NAME                 | CURRENT LAP | LAST LAP    | BEST LAP    | STATUS | ";
 
         mvwaddstr(wnd, 0, 0, header);
+        waddstr(wnd, if self.leader_gaps { "GAP" } else { "DELTA" });
+
+        let focused = game_state.player_index as usize;
+        let battles = game_state.battles();
 
         for (idx, li) in game_state.lap_infos.iter().enumerate() {
             if let ResultStatus::Invalid = li.status {
@@ -670,8 +1202,69 @@ impl NcursesUi {
                 penalties,
             );
 
-            fmt::set_team_color(wnd, participant.team);
+            if idx == focused && game_state.is_personal_best_highlighted() {
+                fmt::set_color(Some(wnd), COLOR_MAGENTA);
+                wattron(wnd, A_REVERSE());
+            } else {
+                fmt::set_team_color(wnd, participant.team);
+            }
             mvwaddstr(wnd, li.position as i32, 0, s.as_str());
+            wattroff(wnd, A_REVERSE());
+            waddstr(wnd, "| ");
+
+            if self.leader_gaps {
+                fmt::set_color(Some(wnd), COLOR_WHITE);
+                waddstr(wnd, &cfmt::format_leader_gap(game_state.leader_gap(idx)));
+            } else {
+                let delta_color = match li.delta {
+                    Some(LapDelta::Time(t)) if t < 0.0 => COLOR_GREEN,
+                    Some(LapDelta::LapBehind) => COLOR_GREEN,
+                    Some(_) => COLOR_RED,
+                    None => COLOR_WHITE,
+                };
+                fmt::set_color(Some(wnd), delta_color);
+                waddstr(wnd, &cfmt::format_lap_delta(li.delta));
+            }
+
+            if battles
+                .iter()
+                .any(|b| b.car_ahead_index as usize == idx || b.car_behind_index as usize == idx)
+            {
+                fmt::set_color(Some(wnd), COLOR_RED);
+                waddstr(wnd, " BATTLE");
+            }
+        }
+
+        self.commit(wnd);
+    }
+
+    fn print_dashboard_team_standings(&self, game_state: &GameState) {
+        let wnd = self.dashboard_view.lap_times_swnd;
+
+        werase(wnd);
+
+        fmt::wset_bold(wnd);
+
+        mvwaddstr(wnd, 0, 0, "  P. TEAM                 | POINTS | CARS");
+
+        for (idx, standing) in game_state.team_standings().iter().enumerate() {
+            let positions = standing
+                .positions
+                .iter()
+                .map(|p| format!("P{}", p))
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            let s = format!(
+                "{:3}. {:20.20} | {:6} | {}",
+                idx + 1,
+                cfmt::format_team_name(standing.team),
+                standing.points,
+                positions
+            );
+
+            fmt::set_team_color(wnd, standing.team);
+            mvwaddstr(wnd, idx as i32 + 1, 0, &s);
         }
 
         self.commit(wnd);
@@ -694,6 +1287,17 @@ impl NcursesUi {
         mvwaddstr(wnd, 0, 0, "Relative Positions");
         mvwaddstr(wnd, 1, 0, &header);
 
+        let player_index = game_state.player_index as usize;
+        let gap_ahead = game_state
+            .car_ahead(player_index)
+            .and_then(|idx| game_state.time_gap(idx, player_index));
+        mvwaddstr(
+            wnd,
+            0,
+            GAP_AHEAD_X_OFFSET,
+            &format!("{:<24}", cfmt::format_gap_ahead(gap_ahead)),
+        );
+
         let scale = relative_positions.max - relative_positions.min;
         let slice = scale / (w - 1) as f32;
 
@@ -712,12 +1316,72 @@ impl NcursesUi {
         self.commit(wnd);
     }
 
+    fn print_relative_info(&self, game_state: &GameState) {
+        let wnd = self.relative_view.cars_swnd;
+
+        werase(wnd);
+
+        fmt::wset_bold(wnd);
+
+        mvwaddstr(
+            wnd,
+            0,
+            0,
+            "  P. NAME                 | TYRE            | GAP",
+        );
+
+        let focused = game_state.player_index as usize;
+
+        for (idx, rc) in game_state
+            .relative_cars(focused, RELATIVE_CARS)
+            .iter()
+            .enumerate()
+        {
+            let li = &game_state.lap_infos[rc.index];
+            let participant = &game_state.participants[rc.index];
+            let y = idx as i32 + 1;
+
+            let s = format!(
+                "{}. {:20} | ",
+                cfmt::format_position(li.position, &li.status),
+                cfmt::format_driver_name(participant, game_state.session_info.is_online),
+            );
+
+            // Cars a lap up are shown in red and cars a lap down in blue, as in the game
+            let color = match rc.laps {
+                l if l > 0 => COLOR_RED,
+                l if l < 0 => COLOR_BLUE,
+                _ => COLOR_WHITE,
+            };
+
+            fmt::set_color(Some(wnd), color);
+            if rc.index == focused {
+                wattron(wnd, A_REVERSE());
+            }
+            mvwaddstr(wnd, y, 0, &s);
+            wattroff(wnd, A_REVERSE());
+
+            let tyre = match li.tyre_compound {
+                TyreCompoundVisual::Invalid => "",
+                compound => compound.name(),
+            };
+            fmt::set_tyre_color(wnd, li.tyre_compound);
+            waddstr(wnd, &format!("{:15}", tyre));
+
+            fmt::set_color(Some(wnd), color);
+            waddstr(wnd, " | ");
+            waddstr(wnd, &cfmt::format_lap_delta(rc.gap.map(LapDelta::Time)));
+        }
+
+        self.commit(wnd);
+    }
+
     fn print_event_info(&self, game_state: &GameState) {
         fmt::set_bold();
 
         let event_info = &game_state.event_info;
 
-        let msg = cfmt::format_event_info(event_info);
+        let msg = cfmt::format_event_info(event_info, self.units);
 
         mvaddstr(getmaxy(self.main_window) - 1, LEFT_BORDER_X_OFFSET, &msg);
         clrtoeol();
@@ -728,14 +1392,20 @@ impl NcursesUi {
     fn print_telemetry_info(&self, game_state: &GameState) {
         let wnd = self.dashboard_view.win;
 
-        let telemetry_info = &game_state.telemetry_info;
+        let focused = game_state.player_index as usize;
+        let car_index = self.telemetry_car.unwrap_or(focused);
+        let is_focused = car_index == focused;
+        let telemetry_info = game_state
+            .car_telemetry
+            .get(car_index)
+            .unwrap_or(&game_state.telemetry_info);
 
         fmt::set_bold();
 
         let gear_msg = format!(
             "Gear     : {}    Speed : {}",
             cfmt::format_gear(telemetry_info.gear),
-            cfmt::format_speed(telemetry_info.speed)
+            cfmt::format_speed(telemetry_info.speed, self.units)
         );
 
         mvwaddstr(
@@ -745,6 +1415,25 @@ impl NcursesUi {
             &gear_msg,
         );
 
+        wheel::render_rev_lights(
+            wnd,
+            telemetry_info.rev_lights_percent,
+            CURRENT_CAR_DATA_Y_OFFSET,
+            REV_LIGHTS_X_OFFSET,
+        );
+        wheel::render_drs(
+            wnd,
+            telemetry_info.drs_state(),
+            CURRENT_CAR_DATA_Y_OFFSET,
+            getcurx(wnd) + 2,
+        );
+        wheel::render_drs_zone(
+            wnd,
+            game_state.drs_zone_ahead(car_index),
+            CURRENT_CAR_DATA_Y_OFFSET,
+            getcurx(wnd) + 1,
+        );
+
         mvwaddstr(
             wnd,
             CURRENT_CAR_DATA_Y_OFFSET + 1,
@@ -761,16 +1450,137 @@ impl NcursesUi {
         let offset = getcurx(wnd);
 
         let throttle_bar = fmt::format_perc_bar(telemetry_info.throttle);
-        fmt::set_color(Some(wnd), COLOR_GREEN);
+        fmt::set_color(Some(wnd), fmt::Color::Throttle as i16);
         mvwaddstr(wnd, CURRENT_CAR_DATA_Y_OFFSET + 1, offset, &throttle_bar);
 
         let brake_bar = fmt::format_perc_bar(telemetry_info.brake);
-        fmt::set_color(Some(wnd), COLOR_RED);
+        fmt::set_color(Some(wnd), fmt::Color::Brake as i16);
         mvwaddstr(wnd, CURRENT_CAR_DATA_Y_OFFSET + 2, offset, &brake_bar);
 
+        // Ghost of the previous lap, at the same point on track, only recorded for the focused car
+        let sample = game_state.previous_lap_sample().filter(|_| is_focused);
+        let (ghost_throttle, ghost_brake) = match sample {
+            Some(s) => (
+                fmt::format_perc_bar(s.throttle),
+                fmt::format_perc_bar(s.brake),
+            ),
+            None => (fmt::format_perc_bar(0.0), fmt::format_perc_bar(0.0)),
+        };
+        let ghost_offset = getcurx(wnd) + 1;
+
+        fmt::wreset(wnd);
+        fmt::wset_dim(wnd);
+        fmt::set_color(Some(wnd), fmt::Color::Throttle as i16);
+        mvwaddstr(
+            wnd,
+            CURRENT_CAR_DATA_Y_OFFSET + 1,
+            ghost_offset,
+            &ghost_throttle,
+        );
+        fmt::set_color(Some(wnd), fmt::Color::Brake as i16);
+        mvwaddstr(
+            wnd,
+            CURRENT_CAR_DATA_Y_OFFSET + 2,
+            ghost_offset,
+            &ghost_brake,
+        );
+        fmt::wreset(wnd);
+
+        // The inputs are only recorded for the focused car
+        let samples: Vec<InputSample> = if is_focused {
+            game_state.input_trace.samples().copied().collect()
+        } else {
+            Vec::new()
+        };
+        inputs::render_input_traces(
+            wnd,
+            &samples,
+            game_state.input_trace.capacity(),
+            CURRENT_CAR_DATA_Y_OFFSET,
+            INPUT_TRACES_X_OFFSET,
+            getmaxx(wnd) - CAR_X_OFFSET - INPUT_TRACES_X_OFFSET - 1,
+        );
+
+        let driver = match game_state.participants.get(car_index) {
+            Some(p) => cfmt::format_driver_name(p, game_state.session_info.is_online),
+            None => "-".into(),
+        };
+        let position = game_state
+            .lap_infos
+            .get(car_index)
+            .map_or(0, |li| li.position);
+        let car_msg = format!(
+            "Car      : {:<20} {:<3}  RPM : {:<5}  Engine : {:<6}",
+            driver,
+            format_or_dash(position, "P"),
+            telemetry_info.engine_rpm,
+            cfmt::format_temperature(telemetry_info.engine_temperature as i16, self.units)
+        );
+        mvwaddstr(
+            wnd,
+            CURRENT_CAR_DATA_Y_OFFSET + 3,
+            LEFT_BORDER_X_OFFSET,
+            &car_msg,
+        );
+
         self.commit(wnd)
     }
 
+    fn print_pit_strategy(&self, game_state: &GameState) {
+        let wnd = self.dashboard_view.win;
+
+        let x = getmaxx(wnd) - CAR_X_OFFSET;
+        let y = CURRENT_CAR_DATA_Y_OFFSET;
+
+        let lines = match &game_state.session_info.pit_stop_window {
+            Some(pw) => [
+                "PIT STRATEGY".to_string(),
+                format!(
+                    "Ideal Lap : {:<4}  Latest Lap : {}",
+                    format_or_dash(pw.ideal_lap, ""),
+                    format_or_dash(pw.latest_lap, "")
+                ),
+                format!(
+                    "Rejoin Position : {}",
+                    format_or_dash(pw.rejoin_position, "P")
+                ),
+            ],
+            None => Default::default(),
+        };
+
+        for (i, l) in lines.iter().enumerate() {
+            if i == 0 {
+                fmt::wset_bold(wnd);
+            }
+            mvwaddstr(wnd, y + i as i32, x, &format!("{:38}", l));
+            fmt::wreset(wnd);
+        }
+
+        self.commit(wnd);
+    }
+
+    fn print_track_map(&self, game_state: &GameState) {
+        let wnd = self.track_view.map_swnd;
+
+        map::render_track_map(wnd, game_state);
+
+        self.commit(wnd);
+    }
+
+    fn print_marshal_zones(&self, game_state: &GameState) {
+        let wnd = self.track_view.win;
+
+        map::render_marshal_zones(
+            wnd,
+            game_state,
+            getmaxy(wnd) - 1 - MARSHAL_ZONES_HEIGHT,
+            LEFT_BORDER_X_OFFSET,
+            84,
+        );
+
+        self.commit(wnd);
+    }
+
     fn print_weather_info(&self, game_state: &GameState) {
         let wnd = self.track_view.win;
 
@@ -781,33 +1591,95 @@ impl NcursesUi {
             wnd,
             2 + 10,
             90,
-            &format!("Air Temp   : {}C", session.air_temperature),
+            &format!(
+                "Air Temp   : {:5}",
+                cfmt::format_temperature(session.air_temperature.into(), self.units)
+            ),
         );
         mvwaddstr(
             wnd,
             2 + 11,
             90,
-            &format!("Track Temp : {}C", session.track_temperature),
+            &format!(
+                "Track Temp : {:5}",
+                cfmt::format_temperature(session.track_temperature.into(), self.units)
+            ),
         );
 
+        let lap_time = game_state
+            .lap_infos
+            .get(game_state.player_index as usize)
+            .map_or(0, |li| li.last_lap_time);
+        let hint = weather_change(
+            session.weather,
+            &session.weather_forecast,
+            DEFAULT_RAIN_THRESHOLD,
+        )
+        .map(|change| cfmt::format_weather_change(change, lap_time))
+        .unwrap_or_default();
+
+        fmt::set_color(Some(wnd), COLOR_YELLOW);
+        mvwaddstr(wnd, 2 + 12, 90, &format!("{:38}", hint));
+        fmt::reset_color(Some(wnd));
+
+        weather::render_forecast(wnd, session, 2 + 13, 90);
+
         self.commit(wnd);
     }
 
     fn print_handling_info(&self, game_state: &GameState) {
         let wnd = self.lap_detail_view.handling_swnd;
 
-        suspension::render_suspension(wnd, &game_state.motion_info, &game_state.telemetry_info);
+        suspension::render_suspension(
+            wnd,
+            &game_state.motion_info,
+            &game_state.telemetry_info,
+            self.units,
+        );
         // wclrtoeol(wnd);
 
         self.commit(wnd);
     }
 
+    fn print_tyres_info(&self, game_state: &GameState) {
+        let wnd = self.tyres_view.win;
+
+        tyres::render_tyres(wnd, &game_state.tyre_info, self.units);
+
+        self.commit(wnd);
+    }
+
+    fn print_energy_info(&self, game_state: &GameState) {
+        let wnd = self.energy_view.win;
+
+        ers::render_ers(wnd, &game_state.car_status, 2, LEFT_BORDER_X_OFFSET + 2);
+        ers::render_fuel(wnd, &game_state.car_status, 9, LEFT_BORDER_X_OFFSET + 2);
+        ers::render_fuel_target(
+            wnd,
+            &game_state.car_status,
+            &game_state.session_info,
+            14,
+            LEFT_BORDER_X_OFFSET + 2,
+        );
+
+        self.commit(wnd);
+    }
+
+    fn print_g_force_info(&self, game_state: &GameState) {
+        let wnd = self.g_force_view.meter_swnd;
+
+        gforce::render_g_force(wnd, &game_state.motion_info, 1, LEFT_BORDER_X_OFFSET + 2);
+
+        self.commit(wnd);
+    }
+
     fn print_car_status(&self, game_state: &GameState) {
         let wnd = self.dashboard_view.car_swnd;
 
         let car_status = &game_state.car_status;
 
         car::render_car(wnd, car_status);
+        car::render_settings(wnd, car_status, 0, 30);
 
         mvwaddstr(
             wnd,
@@ -861,6 +1733,84 @@ impl NcursesUi {
     }
 }
 
+fn is_large_enough(w: WINDOW) -> bool {
+    getmaxx(w) >= WIDTH && getmaxy(w) >= HEIGHT
+}
+
+fn render_too_small(w: WINDOW) {
+    let msg = format!(
+        "Terminal must be at least {}x{}. Current size: {}x{}",
+        WIDTH,
+        HEIGHT,
+        getmaxx(w),
+        getmaxy(w)
+    );
+
+    werase(w);
+    mvwaddstr(w, getmaxy(w) / 2, fmt::center(w, &msg).max(0), &msg);
+    wrefresh(w);
+}
+
+/// Show a holding screen until the terminal is large enough to fit the UI. Pressing `q` exits.
+fn wait_for_min_size(w: WINDOW) {
+    while !is_large_enough(w) {
+        render_too_small(w);
+
+        if let Some(WchResult::Char(113)) = get_wch() {
+            endwin();
+            std::process::exit(0);
+        }
+    }
+
+    werase(w);
+}
+
+/// Leave curses mode before printing the panic message, so that the terminal is usable again.
+///
+/// The process exits once the message is printed: a panic in the input or stream task would
+/// otherwise only end that task, and the next render would bring curses mode back.
+fn restore_terminal_on_panic() {
+    let default_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        endwin();
+        default_hook(info);
+        std::process::exit(101);
+    }));
+}
+
+/// The game sends 0 when a lap or position is not known
+fn format_or_dash(value: u8, prefix: &str) -> String {
+    match value {
+        0 => "-".to_string(),
+        v => format!("{}{}", prefix, v),
+    }
+}
+
+/// Green for a time faster than the player's, red for a slower one
+fn time_trial_color(player_time: Option<u32>, time: u32) -> i16 {
+    match player_time {
+        Some(p) if time < p => COLOR_GREEN,
+        Some(p) if time > p => COLOR_RED,
+        _ => COLOR_WHITE,
+    }
+}
+
+/// Difference to the player's best lap, in seconds
+fn format_time_trial_delta(player_time: Option<u32>, time: u32) -> String {
+    match player_time {
+        Some(p) => format!("{:+.3}", (time as i64 - p as i64) as f32 / 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+/// Same as [`addstr_center`], with the current attributes applied to the whole line
+fn addstr_banner(w: WINDOW, y: i32, str_: &str) {
+    let width = getmaxx(w) as usize;
+
+    mvwaddstr(w, y, 0, &format!("{:^width$}", str_, width = width));
+}
+
 fn addstr_center(w: WINDOW, y: i32, str_: &str) {
     mv(y, 0);
     clrtoeol();
@@ -889,10 +1839,49 @@ async fn process_input(tx: UnboundedSender<Event>) -> Result<(), SendError<Event
                     //4
                     tx.send(Event::EnableRotation)?;
                 }
+                ncurses::WchResult::Char(53) => {
+                    // 5
+                    tx.send(Event::SwitchView(View::Tyres))?;
+                }
+                ncurses::WchResult::Char(54) => {
+                    // 6
+                    tx.send(Event::SwitchView(View::Energy))?;
+                }
+                ncurses::WchResult::Char(55) => {
+                    // 7
+                    tx.send(Event::SwitchView(View::GForce))?;
+                }
+                ncurses::WchResult::Char(56) => {
+                    // 8
+                    tx.send(Event::SwitchView(View::TimeTrial))?;
+                }
+                ncurses::WchResult::Char(57) => {
+                    // 9
+                    tx.send(Event::SwitchView(View::Relative))?;
+                }
+                ncurses::WchResult::Char(99) => {
+                    // c
+                    tx.send(Event::CycleTelemetryCar)?;
+                }
+                ncurses::WchResult::Char(117) => {
+                    // u
+                    tx.send(Event::ToggleUnits)?;
+                }
+                ncurses::WchResult::Char(103) => {
+                    // g
+                    tx.send(Event::ToggleLeaderGaps)?;
+                }
+                ncurses::WchResult::Char(116) => {
+                    // t
+                    tx.send(Event::ToggleTeamStandings)?;
+                }
                 ncurses::WchResult::Char(113) => {
                     // q
                     tx.send(Event::Quit)?;
                 }
+                ncurses::WchResult::KeyCode(KEY_RESIZE) => {
+                    tx.send(Event::Resize)?;
+                }
                 _ => {}
             }
         } else {
@@ -1,5 +1,7 @@
 use ncurses::*;
 
+use f1_telemetry::packet::car_status::{FuelMix, TractionControl};
+
 use crate::models::CarStatus;
 
 use super::fmt;
@@ -87,6 +89,48 @@ pub fn render_car(w: WINDOW, car_status: &CarStatus) {
     render_component(w, TYRE, car_status.tyres_damage.rear_right, 13, 22);
 }
 
+/// Driver settings and differential, in a 9 columns wide panel
+pub fn render_settings(w: WINDOW, car_status: &CarStatus, y: i32, x: i32) {
+    let tc = match car_status.traction_control {
+        TractionControl::Off => "Off",
+        TractionControl::Low => "Low",
+        TractionControl::High => "High",
+    };
+    let mix = match car_status.fuel_mix {
+        FuelMix::Lean => "Lean",
+        FuelMix::Standard => "Std",
+        FuelMix::Rich => "Rich",
+        FuelMix::Max => "Max",
+    };
+    let abs = if car_status.anti_lock_brakes {
+        "On"
+    } else {
+        "Off"
+    };
+
+    mvwaddstr(
+        w,
+        y,
+        x,
+        &format!("BIAS {:>3}%", car_status.front_brake_bias),
+    );
+    mvwaddstr(w, y + 1, x, &format!("TC {:>6}", tc));
+    mvwaddstr(w, y + 2, x, &format!("ABS {:>5}", abs));
+    mvwaddstr(w, y + 3, x, &format!("MIX {:>5}", mix));
+
+    mvwaddstr(w, y + 5, x, "DIFF     ");
+    match car_status.differential {
+        Some((on_throttle, off_throttle)) => {
+            mvwaddstr(w, y + 6, x, &format!("ON  {:>4}%", on_throttle));
+            mvwaddstr(w, y + 7, x, &format!("OFF {:>4}%", off_throttle));
+        }
+        None => {
+            mvwaddstr(w, y + 6, x, "ON      -");
+            mvwaddstr(w, y + 7, x, "OFF     -");
+        }
+    }
+}
+
 pub(crate) fn render_component(w: WINDOW, component: &str, damage: u8, y: i32, x: i32) {
     let (ok, caution, warning): (u8, u8, u8) = match component {
         ENGINE => (50, 70, 90),
@@ -0,0 +1,132 @@
+use ncurses::*;
+
+use crate::fmt as cfmt;
+use crate::models::{CarStatus, SessionInfo};
+
+use super::fmt;
+
+const MAX_ERS_STORE_ENERGY: f32 = 4_000_000.0; // 4 MJ
+const JOULES_PER_MEGAJOULE: f32 = 1_000_000.0;
+
+pub(crate) fn render_ers(w: WINDOW, car_status: &CarStatus, y: i32, x: i32) {
+    let store_pct = (car_status.ers_store_energy / MAX_ERS_STORE_ENERGY).clamp(0.0, 1.0);
+
+    fmt::wset_bold(w);
+    mvwaddstr(w, y, x, "ERS");
+    fmt::wreset(w);
+
+    mvwaddstr(w, y + 1, x, "Store       : ");
+    fmt::set_damage_color(Some(w), 100 - (store_pct * 100.0) as u8, 30, 60, 80);
+    waddstr(w, &fmt::format_perc_bar(store_pct));
+    fmt::wreset(w);
+    waddstr(
+        w,
+        &format!(
+            " {:4.2} MJ ({:3.0}%)",
+            car_status.ers_store_energy / JOULES_PER_MEGAJOULE,
+            store_pct * 100.0
+        ),
+    );
+
+    mvwaddstr(
+        w,
+        y + 2,
+        x,
+        &format!("Deploy Mode : {:<10?}", car_status.ers_deploy_mode),
+    );
+    mvwaddstr(
+        w,
+        y + 3,
+        x,
+        &format!(
+            "Harvested   : MGU-K {:4.2} MJ   MGU-H {:4.2} MJ",
+            car_status.ers_harvested_this_lap_mguk / JOULES_PER_MEGAJOULE,
+            car_status.ers_harvested_this_lap_mguh / JOULES_PER_MEGAJOULE
+        ),
+    );
+    mvwaddstr(
+        w,
+        y + 4,
+        x,
+        &format!(
+            "Deployed    : {:4.2} MJ",
+            car_status.ers_deployed_this_lap / JOULES_PER_MEGAJOULE
+        ),
+    );
+}
+
+pub(crate) fn render_fuel(w: WINDOW, car_status: &CarStatus, y: i32, x: i32) {
+    let fuel_pct = if car_status.fuel_capacity > 0.0 {
+        (car_status.fuel_in_tank / car_status.fuel_capacity).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    fmt::wset_bold(w);
+    mvwaddstr(w, y, x, "FUEL");
+    fmt::wreset(w);
+
+    mvwaddstr(w, y + 1, x, "In Tank     : ");
+    waddstr(w, &fmt::format_perc_bar(fuel_pct));
+    waddstr(
+        w,
+        &format!(
+            " {:5.2} / {:5.2} kg",
+            car_status.fuel_in_tank, car_status.fuel_capacity
+        ),
+    );
+
+    mvwaddstr(
+        w,
+        y + 2,
+        x,
+        &format!("Mix         : {:<10?}", car_status.fuel_mix),
+    );
+
+    mvwaddstr(w, y + 3, x, "Remaining   : ");
+    let c = if car_status.fuel_remaining_laps < 0.0 {
+        COLOR_RED
+    } else {
+        COLOR_GREEN
+    };
+    fmt::set_color(Some(w), c);
+    waddstr(w, &format!("{:+5.2} laps", car_status.fuel_remaining_laps));
+    fmt::wreset(w);
+}
+
+/// Whether the fuel in the tank lasts until the end of the race, based on the MFD fuel value
+pub(crate) fn render_fuel_target(
+    w: WINDOW,
+    car_status: &CarStatus,
+    session_info: &SessionInfo,
+    y: i32,
+    x: i32,
+) {
+    fmt::wset_bold(w);
+    mvwaddstr(w, y, x, "FUEL TARGET");
+    fmt::wreset(w);
+
+    mvwaddstr(w, y + 1, x, "Target      : ");
+
+    let total_laps = session_info.number_of_laps;
+    if total_laps > 0 {
+        let c = if car_status.fuel_remaining_laps < 0.0 {
+            COLOR_RED
+        } else {
+            COLOR_GREEN
+        };
+        fmt::set_color(Some(w), c);
+    }
+    waddstr(
+        w,
+        &cfmt::format_fuel_target(car_status.fuel_remaining_laps, total_laps),
+    );
+    fmt::wreset(w);
+    wclrtoeol(w);
+
+    let laps_to_go = match total_laps {
+        0 => "N/A".to_string(),
+        _ => (total_laps.saturating_sub(session_info.current_lap) + 1).to_string(),
+    };
+    mvwaddstr(w, y + 2, x, &format!("Laps To Go  : {:<4}", laps_to_go));
+}
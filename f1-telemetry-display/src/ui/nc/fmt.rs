@@ -2,11 +2,14 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use ncurses::*;
 
-use f1_telemetry::packet::generic::{Team, TyreCompoundVisual};
+use f1_telemetry::packet::generic::{Flag, Team, TyreCompoundVisual};
+
+use crate::ui::Theme;
 
 const PERCENTAGE_BAR_SLICES: i8 = 20;
 
 #[allow(dead_code)]
+#[derive(Copy, Clone)]
 pub enum Color {
     Black = COLOR_BLACK as isize,
     Red = COLOR_RED as isize,
@@ -22,6 +25,15 @@ pub enum Color {
     StatusWarning,
     StatusDanger,
 
+    Throttle,
+    Brake,
+    Steer,
+    FlagGreen,
+    FlagBlue,
+    FlagYellow,
+    FlagRed,
+    Orange,
+
     TyreSoft,
     TyreMedium,
     TyreHard,
@@ -43,8 +55,40 @@ pub enum Color {
     Alpine,
     AstonMartin,
     MyTeam,
+
+    Lotus,
+    Benetton,
+    Jordan,
+    Brawn,
+
+    ArtGp,
+    Campos,
+    Carlin,
+    Charouz,
+    Dams,
+    MpMotorsport,
+    Prema,
+    Trident,
+    Virtuosi,
+    Arden,
+    Hitech,
+    VanAmersfoort,
+    Konnersport,
+
+    TeamOther1,
+    TeamOther2,
+    TeamOther3,
+    TeamOther4,
 }
 
+/// Teams without a livery of their own (generic cars, unknown teams) are spread over these
+const TEAM_FALLBACK_COLORS: [Color; 4] = [
+    Color::TeamOther1,
+    Color::TeamOther2,
+    Color::TeamOther3,
+    Color::TeamOther4,
+];
+
 trait ToColor {
     fn get_color(&self) -> Color;
 }
@@ -52,21 +96,133 @@ trait ToColor {
 impl ToColor for Team {
     fn get_color(&self) -> Color {
         match self {
-            Team::Mercedes => Color::Mercedes,
-            Team::Ferrari => Color::Ferrari,
-            Team::RedBullRacing => Color::RedBullRacing,
-            Team::Williams => Color::Williams,
-            Team::RacingPoint => Color::RacingPoint,
-            Team::Renault => Color::Renault,
+            // Classic cars and supercars use the color of their constructor
+            Team::Mercedes
+            | Team::Mercedes2020
+            | Team::Mercedes2022
+            | Team::MercedesAMGGTBlackSeriesSafetyCar
+            | Team::MercedesAMGGTRPro
+            | Team::MercedesAMGGTBlackSeries => Color::Mercedes,
+            Team::Ferrari
+            | Team::Ferrari1976
+            | Team::Ferrari1979
+            | Team::Ferrari1990
+            | Team::Ferrari1995
+            | Team::Ferrari2000
+            | Team::Ferrari2002
+            | Team::Ferrari2004
+            | Team::Ferrari2007
+            | Team::Ferrari2010
+            | Team::Ferrari2020
+            | Team::Ferrari2022
+            | Team::FerrariF8Tributo
+            | Team::FerrariRoma => Color::Ferrari,
+            Team::RedBullRacing
+            | Team::RedBull2010
+            | Team::RedBull2020
+            | Team::RedBullRacing2022 => Color::RedBullRacing,
+            Team::Williams
+            | Team::Williams1992
+            | Team::Williams1996
+            | Team::Williams2003
+            | Team::Williams2020
+            | Team::Williams2022 => Color::Williams,
+            Team::RacingPoint | Team::RacingPoint2020 => Color::RacingPoint,
+            Team::Renault | Team::Renault2006 | Team::Renault2020 => Color::Renault,
             Team::ToroRosso => Color::ToroRosso,
-            Team::Haas => Color::Haas,
-            Team::McLaren => Color::McLaren,
-            Team::AlfaRomeo => Color::AlfaRomeo,
-            Team::AlphaTauri => Color::AlphaTauri,
-            Team::Alpine => Color::Alpine,
-            Team::AstonMartin => Color::AstonMartin,
+            Team::Haas | Team::Haas2020 | Team::Haas2022 => Color::Haas,
+            Team::McLaren
+            | Team::McLaren1976
+            | Team::McLaren1982
+            | Team::McLaren1988
+            | Team::McLaren1990
+            | Team::McLaren1991
+            | Team::McLaren1998
+            | Team::McLaren2008
+            | Team::McLaren2010
+            | Team::McLaren2020
+            | Team::McLaren2022
+            | Team::McLaren720S
+            | Team::McLarenArtura => Color::McLaren,
+            Team::AlfaRomeo | Team::AlfaRomeo2020 | Team::AlfaRomeo2022 => Color::AlfaRomeo,
+            Team::AlphaTauri | Team::AlphaTauri2020 | Team::AlphaTauri2022 => Color::AlphaTauri,
+            Team::Alpine | Team::Alpine2022 => Color::Alpine,
+            Team::AstonMartin
+            | Team::AstonMartin2022
+            | Team::AstonMartinDB11V12
+            | Team::AstonMartinVantageF1Edition
+            | Team::AstonMartinVantageSafetyCar => Color::AstonMartin,
             Team::MyTeam => Color::MyTeam,
-            _ => Color::Black,
+
+            Team::Lotus1972 | Team::Lotus1978 => Color::Lotus,
+            Team::Benetton1994 | Team::Benetton1995 => Color::Benetton,
+            Team::Jordan1991 => Color::Jordan,
+            Team::Brawn2009 => Color::Brawn,
+
+            Team::ARTGrandPrix
+            | Team::ArtGP2019
+            | Team::ArtGP2020
+            | Team::ArtGP2021
+            | Team::ArtGP2022
+            | Team::ArtGP2023 => Color::ArtGp,
+            Team::CamposVexatecRacing
+            | Team::Campos2019
+            | Team::Campos2020
+            | Team::Campos2021
+            | Team::Campos2022
+            | Team::Campos2023 => Color::Campos,
+            Team::Carlin
+            | Team::Carlin2019
+            | Team::Carlin2020
+            | Team::Carlin2021
+            | Team::Carlin2022
+            | Team::Carlin2023 => Color::Carlin,
+            Team::CharouzRacingSystem
+            | Team::SauberJuniorCharouz2019
+            | Team::Charouz2020
+            | Team::Charouz2021
+            | Team::Charouz2022
+            | Team::Phm2023 => Color::Charouz,
+            Team::DAMS
+            | Team::Dams2019
+            | Team::Dams2020
+            | Team::Dams2021
+            | Team::Dams2022
+            | Team::Dams2023 => Color::Dams,
+            Team::MPMotorsport
+            | Team::MPMotorsport2019
+            | Team::MPMotorsport2020
+            | Team::MPMotorsport2021
+            | Team::MPMotorsport2022
+            | Team::MPMotorsport2023 => Color::MpMotorsport,
+            Team::Pertamina
+            | Team::Prema2019
+            | Team::Prema2020
+            | Team::Prema2021
+            | Team::Prema2022
+            | Team::Prema2023 => Color::Prema,
+            Team::Trident
+            | Team::Trident2019
+            | Team::Trident2020
+            | Team::Trident2021
+            | Team::Trident2022
+            | Team::Trident2023 => Color::Trident,
+            Team::RussianTime
+            | Team::UniVirtuosi2019
+            | Team::UniVirtuosi2020
+            | Team::UniVirtuosi2021
+            | Team::Virtuosi2022
+            | Team::Virtuosi2023 => Color::Virtuosi,
+            Team::BWTArden | Team::Arden2019 | Team::BWT2020 | Team::BWT2021 => Color::Arden,
+            Team::Hitech2020 | Team::Hitech2021 | Team::Hitech2022 | Team::Hitech2023 => {
+                Color::Hitech
+            }
+            Team::VanAmersfoortRacing2022 | Team::VanAmersfoortRacing2023 => Color::VanAmersfoort,
+            Team::Konnersport | Team::Konnersport2022 => Color::Konnersport,
+
+            Team::F1GenericCar | Team::F1CustomTeam | Team::F1WorldCar | Team::Unknown => {
+                TEAM_FALLBACK_COLORS[*self as usize % TEAM_FALLBACK_COLORS.len()]
+            }
         }
     }
 }
@@ -84,12 +240,12 @@ impl ToColor for TyreCompoundVisual {
     }
 }
 
-pub fn init_colors() {
+pub fn init_colors(theme: Theme) {
     start_color();
 
     init_base_color_pairs();
     init_team_colors();
-    init_status_colors();
+    init_theme_colors(theme);
     init_tyre_colors();
 }
 
@@ -120,6 +276,27 @@ fn init_team_colors() {
         (Color::Alpine, (0, 144, 255)),
         (Color::AstonMartin, (0, 111, 98)),
         (Color::MyTeam, (118, 0, 218)),
+        (Color::Lotus, (180, 140, 40)),
+        (Color::Benetton, (0, 150, 60)),
+        (Color::Jordan, (0, 140, 110)),
+        (Color::Brawn, (210, 230, 60)),
+        (Color::ArtGp, (190, 190, 190)),
+        (Color::Campos, (0, 60, 200)),
+        (Color::Carlin, (20, 40, 120)),
+        (Color::Charouz, (200, 20, 90)),
+        (Color::Dams, (0, 170, 255)),
+        (Color::MpMotorsport, (255, 100, 0)),
+        (Color::Prema, (230, 0, 30)),
+        (Color::Trident, (100, 140, 180)),
+        (Color::Virtuosi, (110, 0, 90)),
+        (Color::Arden, (240, 60, 160)),
+        (Color::Hitech, (170, 170, 60)),
+        (Color::VanAmersfoort, (230, 180, 0)),
+        (Color::Konnersport, (150, 200, 0)),
+        (Color::TeamOther1, (0, 150, 150)),
+        (Color::TeamOther2, (150, 100, 200)),
+        (Color::TeamOther3, (200, 150, 50)),
+        (Color::TeamOther4, (100, 160, 80)),
     ] {
         let idx = t as i16;
         let (r, g, b) = rgb_to_curses(c);
@@ -137,16 +314,57 @@ fn rgb_to_curses(c: (i16, i16, i16)) -> (i16, i16, i16) {
     (r, g, b)
 }
 
-fn init_status_colors() {
+/// Pairs of the throttle, brake and steering inputs, flags and damage levels, which depend on the theme
+fn init_theme_colors(theme: Theme) {
     init_color(Color::StatusWarning as i16, 1000, 812, 686);
+    init_color(Color::Orange as i16, 1000, 550, 0);
+
+    let palette = match theme {
+        Theme::Default => [
+            (Color::Throttle, Color::Green),
+            (Color::Brake, Color::Red),
+            (Color::Steer, Color::Blue),
+            (Color::FlagGreen, Color::Green),
+            (Color::FlagBlue, Color::Blue),
+            (Color::FlagYellow, Color::Yellow),
+            (Color::FlagRed, Color::Red),
+            (Color::StatusOk, Color::Green),
+            (Color::StatusCaution, Color::Yellow),
+            (Color::StatusWarning, Color::StatusWarning),
+            (Color::StatusDanger, Color::Red),
+        ],
+        // Blue against orange, as red and green look alike
+        Theme::Deuteranopia => [
+            (Color::Throttle, Color::Cyan),
+            (Color::Brake, Color::Orange),
+            (Color::Steer, Color::Blue),
+            (Color::FlagGreen, Color::Cyan),
+            (Color::FlagBlue, Color::Blue),
+            (Color::FlagYellow, Color::Yellow),
+            (Color::FlagRed, Color::Magenta),
+            (Color::StatusOk, Color::Cyan),
+            (Color::StatusCaution, Color::Yellow),
+            (Color::StatusWarning, Color::Orange),
+            (Color::StatusDanger, Color::Magenta),
+        ],
+        // No dark foreground colors
+        Theme::HighContrast => [
+            (Color::Throttle, Color::White),
+            (Color::Brake, Color::Yellow),
+            (Color::Steer, Color::Cyan),
+            (Color::FlagGreen, Color::Green),
+            (Color::FlagBlue, Color::Cyan),
+            (Color::FlagYellow, Color::Yellow),
+            (Color::FlagRed, Color::Red),
+            (Color::StatusOk, Color::White),
+            (Color::StatusCaution, Color::Yellow),
+            (Color::StatusWarning, Color::Magenta),
+            (Color::StatusDanger, Color::Red),
+        ],
+    };
 
-    for (status, c) in [
-        (Color::StatusOk, Color::Green),
-        (Color::StatusCaution, Color::Yellow),
-        (Color::StatusWarning, Color::StatusWarning),
-        (Color::StatusDanger, Color::Red),
-    ] {
-        init_pair(status as i16, c as i16, COLOR_BLACK);
+    for (pair, c) in palette {
+        init_pair(pair as i16, c as i16, COLOR_BLACK);
     }
 }
 
@@ -173,6 +391,10 @@ pub fn wset_bold(w: WINDOW) {
     wattron(w, A_BOLD());
 }
 
+pub fn wset_dim(w: WINDOW) {
+    wattron(w, A_DIM());
+}
+
 pub fn set_team_color(w: WINDOW, team: Team) {
     wcolor_set(w, team.get_color() as i16);
 }
@@ -195,6 +417,17 @@ pub fn reset_color(w: Option<WINDOW>) {
     };
 }
 
+/// Color of a waved flag, `None` when no flag is shown
+pub fn flag_color(flag: Flag) -> Option<i16> {
+    match flag {
+        Flag::Green => Some(Color::FlagGreen as i16),
+        Flag::Blue => Some(Color::FlagBlue as i16),
+        Flag::Yellow => Some(Color::FlagYellow as i16),
+        Flag::Red => Some(Color::FlagRed as i16),
+        Flag::None | Flag::Invalid => None,
+    }
+}
+
 pub fn set_damage_color(w: Option<WINDOW>, damage_pct: u8, ok: u8, caution: u8, warning: u8) {
     let c = match damage_pct {
         d if d <= ok => Color::StatusOk,
@@ -0,0 +1,90 @@
+use std::f32::consts::PI;
+
+use ncurses::*;
+
+use crate::models::MotionInfo;
+
+use super::fmt;
+
+/// G-force at the edge of the meter, around the grip limit of a modern F1 car
+const MAX_G_FORCE: f32 = 5.0;
+/// Radius of the meter in rows, columns are twice as many to keep it round on screen
+const RADIUS: i32 = 10;
+
+pub(crate) fn render_g_force(w: WINDOW, motion_info: &MotionInfo, y: i32, x: i32) {
+    let cy = y + RADIUS;
+    let cx = x + RADIUS * 2;
+
+    werase(w);
+
+    fmt::wset_dim(w);
+    render_circle(w, cy, cx, RADIUS as f32);
+    render_circle(w, cy, cx, RADIUS as f32 / 2.0);
+    mvwaddstr(w, cy, cx, "+");
+    fmt::wreset(w);
+
+    // Lateral force moves the dot sideways, longitudinal force up and down. The dot stays centered
+    // until motion data is received.
+    let (dy, dx) = dot_offset(
+        motion_info.g_force_lateral,
+        motion_info.g_force_longitudinal,
+    );
+
+    fmt::wset_bold(w);
+    fmt::set_color(Some(w), COLOR_RED);
+    mvwaddstr(w, cy + dy, cx + dx, "●");
+    fmt::wreset(w);
+
+    let x = cx + RADIUS * 2 + 6;
+
+    fmt::wset_bold(w);
+    mvwaddstr(w, cy - 2, x, "G-FORCE");
+    fmt::wreset(w);
+
+    mvwaddstr(
+        w,
+        cy,
+        x,
+        &format!("Lateral      : {:+5.2} g", motion_info.g_force_lateral),
+    );
+    mvwaddstr(
+        w,
+        cy + 1,
+        x,
+        &format!("Longitudinal : {:+5.2} g", motion_info.g_force_longitudinal),
+    );
+    mvwaddstr(
+        w,
+        cy + 2,
+        x,
+        &format!("Vertical     : {:+5.2} g", motion_info.g_force_vertical),
+    );
+}
+
+fn render_circle(w: WINDOW, cy: i32, cx: i32, radius: f32) {
+    let steps = (radius * 8.0) as i32;
+
+    for i in 0..steps {
+        let angle = 2.0 * PI * i as f32 / steps as f32;
+        let y = cy + (angle.sin() * radius).round() as i32;
+        let x = cx + (angle.cos() * radius * 2.0).round() as i32;
+
+        mvwaddstr(w, y, x, "·");
+    }
+}
+
+/// Position of the dot relative to the center of the meter, in rows and columns. Forces beyond
+/// `MAX_G_FORCE` are kept on the edge of the meter.
+fn dot_offset(lateral: f32, longitudinal: f32) -> (i32, i32) {
+    let magnitude = (lateral * lateral + longitudinal * longitudinal).sqrt();
+    let scale = if magnitude > MAX_G_FORCE {
+        MAX_G_FORCE / magnitude
+    } else {
+        1.0
+    };
+
+    let dy = longitudinal * scale / MAX_G_FORCE * RADIUS as f32;
+    let dx = lateral * scale / MAX_G_FORCE * RADIUS as f32 * 2.0;
+
+    (dy.round() as i32, dx.round() as i32)
+}
@@ -0,0 +1,84 @@
+use ncurses::*;
+
+use crate::models::InputSample;
+
+use super::fmt;
+
+const LABEL_WIDTH: i32 = 4;
+
+/// Braille dots of the left and right columns of a cell, from the bottom up
+const LEFT_DOTS: [u32; 4] = [0x40, 0x04, 0x02, 0x01];
+const RIGHT_DOTS: [u32; 4] = [0x80, 0x20, 0x10, 0x08];
+
+/// Label, color and normalized value of an input trace
+type Trace = (&'static str, fmt::Color, fn(&InputSample) -> f32);
+
+/// Draws the throttle, brake and steering traces on three lines from `y`, as sparklines of two
+/// samples per cell. The traces are sized for `history` samples, at most `width` cells including
+/// the labels, and the latest samples are on the right.
+pub(crate) fn render_input_traces(
+    w: WINDOW,
+    samples: &[InputSample],
+    history: usize,
+    y: i32,
+    x: i32,
+    width: i32,
+) {
+    let cells = history
+        .div_ceil(2)
+        .min((width - LABEL_WIDTH).max(0) as usize);
+    let samples = &samples[samples.len().saturating_sub(cells * 2)..];
+
+    let traces: [Trace; 3] = [
+        ("THR", fmt::Color::Throttle, |s| s.throttle),
+        ("BRK", fmt::Color::Brake, |s| s.brake),
+        // Straight ahead in the middle
+        ("STR", fmt::Color::Steer, |s| (s.steer + 1.0) / 2.0),
+    ];
+
+    for (i, (label, color, value)) in traces.into_iter().enumerate() {
+        let y = y + i as i32;
+
+        fmt::wset_bold(w);
+        mvwaddstr(w, y, x, label);
+        fmt::wreset(w);
+
+        fmt::set_color(Some(w), color as i16);
+        mvwaddstr(
+            w,
+            y,
+            x + LABEL_WIDTH,
+            &sparkline(samples.iter().map(value), cells),
+        );
+        fmt::wreset(w);
+    }
+}
+
+/// Braille sparkline of values from 0.0 to 1.0, right-aligned in `cells` characters.
+fn sparkline(values: impl ExactSizeIterator<Item = f32>, cells: usize) -> String {
+    let mut dots = vec![0; cells];
+    let start = (cells * 2).saturating_sub(values.len());
+
+    for (pos, value) in (start..cells * 2).zip(values) {
+        let column = [&LEFT_DOTS, &RIGHT_DOTS][pos % 2];
+        let level = (value.clamp(0.0, 1.0) * column.len() as f32).round() as usize;
+
+        dots[pos / 2] |= column[..level].iter().fold(0, |acc, d| acc | d);
+    }
+
+    dots.into_iter()
+        .map(|d| char::from_u32(0x2800 + d).unwrap_or(' '))
+        .collect()
+}
+
+#[cfg(test)]
+mod test_sparkline {
+    use super::sparkline;
+
+    #[test]
+    fn test_sparkline_is_right_aligned() {
+        let values = [1.0, 0.25, 0.5];
+
+        assert_eq!(sparkline(values.into_iter(), 3), "\u{2800}\u{28b8}\u{28e0}");
+    }
+}
@@ -0,0 +1,148 @@
+use f1_telemetry::packet::session::MarshalZone;
+use ncurses::*;
+
+use crate::models::GameState;
+
+use super::fmt;
+
+/// Screen position of a world position, fitting the map in the window. Rows are about twice as
+/// high as columns are wide, so distances are doubled horizontally to keep the track shape.
+struct Projection {
+    min_x: f32,
+    max_z: f32,
+    scale: f32,
+}
+
+impl Projection {
+    fn fit<'a>(points: impl Iterator<Item = &'a (f32, f32)>, h: i32, w: i32) -> Option<Self> {
+        let (mut min_x, mut max_x, mut min_z, mut max_z) = (f32::MAX, f32::MIN, f32::MAX, f32::MIN);
+
+        for &(x, z) in points {
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_z = min_z.min(z);
+            max_z = max_z.max(z);
+        }
+
+        if min_x > max_x {
+            return None;
+        }
+
+        let rows = (h - 1) as f32 / (max_z - min_z).max(1.0);
+        let cols = (w - 1) as f32 / 2.0 / (max_x - min_x).max(1.0);
+
+        Some(Projection {
+            min_x,
+            max_z,
+            scale: rows.min(cols),
+        })
+    }
+
+    fn project(&self, (x, z): (f32, f32)) -> (i32, i32) {
+        let y = (self.max_z - z) * self.scale;
+        let x = (x - self.min_x) * self.scale * 2.0;
+
+        (y.round() as i32, x.round() as i32)
+    }
+}
+
+/// Plots the cars on a top-down map of the track, in their team colors. The track outline is only
+/// drawn once the focused car has covered a whole lap.
+pub(crate) fn render_track_map(w: WINDOW, game_state: &GameState) {
+    let track_map = &game_state.track_map;
+    let outline = track_map.outline().unwrap_or_default();
+
+    let cars: Vec<(usize, (f32, f32))> = track_map
+        .positions
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(idx, _)| {
+            game_state
+                .lap_infos
+                .get(*idx)
+                .is_some_and(|li| li.status.is_valid())
+        })
+        .collect();
+
+    werase(w);
+
+    let projection = match Projection::fit(
+        outline.iter().chain(cars.iter().map(|(_, p)| p)),
+        getmaxy(w),
+        getmaxx(w),
+    ) {
+        Some(projection) => projection,
+        None => return,
+    };
+
+    fmt::wset_dim(w);
+    for point in outline {
+        let (y, x) = projection.project(point);
+        mvwaddstr(w, y, x, "·");
+    }
+    fmt::wreset(w);
+
+    let focused_idx = game_state.player_index as usize;
+
+    for (idx, point) in cars {
+        let team = match game_state.participants.get(idx) {
+            Some(participant) => participant.team,
+            None => continue,
+        };
+
+        let (y, x) = projection.project(point);
+
+        fmt::set_team_color(w, team);
+        if idx == focused_idx {
+            fmt::wset_bold(w);
+            mvwaddstr(w, y, x, "◆");
+        } else {
+            mvwaddstr(w, y, x, "●");
+        }
+        fmt::wreset(w);
+    }
+}
+
+/// Draws the lap as a bar split into marshal zones, each colored by the flag waved in it, with a
+/// marker under the position of the focused car.
+pub(crate) fn render_marshal_zones(w: WINDOW, game_state: &GameState, y: i32, x: i32, width: i32) {
+    let session_info = &game_state.session_info;
+    let track_length = session_info.track_length;
+
+    fmt::wset_bold(w);
+    mvwaddstr(w, y, x, "MARSHAL ZONES");
+    fmt::wreset(w);
+
+    wmove(w, y + 1, x);
+    for col in 0..width {
+        let lap_distance = (col as f32 + 0.5) / width as f32 * track_length as f32;
+        let zone = MarshalZone::at(&session_info.marshal_zones, track_length, lap_distance);
+
+        match zone.and_then(|z| fmt::flag_color(z.zone_flag)) {
+            Some(color) => fmt::set_color(Some(w), color),
+            None => fmt::wset_dim(w),
+        }
+        waddstr(w, "━");
+        fmt::wreset(w);
+    }
+
+    mvwaddstr(w, y + 2, x, &" ".repeat(width as usize));
+
+    let track_length = track_length as f32;
+    let lap_distance = game_state
+        .lap_infos
+        .get(game_state.player_index as usize)
+        .map(|li| li.lap_distance);
+
+    if let Some(lap_distance) = lap_distance {
+        // The lap distance is negative before crossing the line for the first time
+        if track_length > 0.0 && lap_distance >= 0.0 {
+            let col = ((lap_distance / track_length) * width as f32) as i32;
+
+            fmt::wset_bold(w);
+            mvwaddstr(w, y + 2, x + col.min(width - 1), "▲");
+            fmt::wreset(w);
+        }
+    }
+}
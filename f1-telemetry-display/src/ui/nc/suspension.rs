@@ -1,5 +1,6 @@
 use ncurses::{mvwaddstr, werase, WINDOW};
 
+use crate::fmt::{format_temperature, UnitSystem};
 use crate::models::{MotionInfo, TelemetryInfo};
 
 use super::car::render_component;
@@ -93,6 +94,7 @@ pub(crate) fn render_suspension(
     w: WINDOW,
     motion_info: &MotionInfo,
     telemetry_info: &TelemetryInfo,
+    units: UnitSystem,
 ) {
     werase(w);
     let left_front_susp = get_left_component(motion_info.suspension_position.front_left);
@@ -109,7 +111,7 @@ pub(crate) fn render_suspension(
 
     render_component(w, TEMPTYRE, front_left_temp as u8, 3, 1);
 
-    let front_left_str = format!("T: {:01.1}", front_left_temp);
+    let front_left_str = format!("T: {}", format_temperature(front_left_temp as i16, units));
     mvwaddstr(w, 3 + temp_offsety, 1 + temp_offsetx, &front_left_str);
 
     render_component(w, &left_front_susp, 0, 1, 10);
@@ -117,13 +119,13 @@ pub(crate) fn render_suspension(
 
     render_component(w, TEMPTYRE, front_right_temp as u8, 3, 49);
 
-    let front_right_str = format!("T: {:01.1}", front_right_temp);
+    let front_right_str = format!("T: {}", format_temperature(front_right_temp as i16, units));
     mvwaddstr(w, 3 + temp_offsety, 50 + temp_offsetx, &front_right_str);
 
     let offset = 12;
     render_component(w, TEMPTYRE, rear_left_temp as u8, 3 + offset, 1);
 
-    let rear_left_str = format!("T: {:01.1}", rear_left_temp);
+    let rear_left_str = format!("T: {}", format_temperature(rear_left_temp as i16, units));
     mvwaddstr(
         w,
         3 + offset + temp_offsety,
@@ -136,7 +138,7 @@ pub(crate) fn render_suspension(
 
     render_component(w, TEMPTYRE, rear_right_temp as u8, 3 + offset, 49);
 
-    let rear_right_str = format!("T: {:01.1}", rear_right_temp);
+    let rear_right_str = format!("T: {}", format_temperature(rear_right_temp as i16, units));
     mvwaddstr(
         w,
         3 + offset + temp_offsety,
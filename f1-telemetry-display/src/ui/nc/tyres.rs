@@ -0,0 +1,98 @@
+use ncurses::*;
+
+use f1_telemetry::packet::generic::WheelData;
+
+use crate::fmt::{format_temperature, UnitSystem};
+use crate::models::TyreInfo;
+
+use super::car::{render_component, TYRE};
+use super::fmt;
+
+const CORNER_HEIGHT: i32 = 9;
+const CORNER_WIDTH: i32 = 50;
+const LABEL_X_OFFSET: i32 = 10;
+
+const CORNERS: [&str; 4] = ["FRONT LEFT", "FRONT RIGHT", "REAR LEFT", "REAR RIGHT"];
+
+pub(crate) fn render_tyres(w: WINDOW, tyre_info: &TyreInfo, units: UnitSystem) {
+    for i in 0..CORNERS.len() {
+        let y = 2 + (i as i32 / 2) * CORNER_HEIGHT;
+        let x = 4 + (i as i32 % 2) * CORNER_WIDTH;
+
+        render_corner(w, y, x, tyre_info, i, units);
+    }
+}
+
+fn corner<T: Clone + Copy>(data: Option<WheelData<T>>, idx: usize) -> Option<T> {
+    data.map(|d| match idx {
+        0 => d.front_left,
+        1 => d.front_right,
+        2 => d.rear_left,
+        _ => d.rear_right,
+    })
+}
+
+fn render_corner(w: WINDOW, y: i32, x: i32, tyre_info: &TyreInfo, idx: usize, units: UnitSystem) {
+    let surface_temperature = corner(tyre_info.surface_temperature, idx);
+    let inner_temperature = corner(tyre_info.inner_temperature, idx);
+    let brakes_temperature = corner(tyre_info.brakes_temperature, idx);
+    let wear = corner(tyre_info.wear, idx);
+
+    let wear_pct = wear.map(|w| w.round() as u8).unwrap_or_default();
+    render_component(w, TYRE, wear_pct, y, x);
+
+    let x = x + LABEL_X_OFFSET;
+
+    fmt::wset_bold(w);
+    mvwaddstr(w, y, x, CORNERS[idx]);
+    fmt::wreset(w);
+
+    mvwaddstr(w, y + 1, x, "Surface : ");
+    render_temperature(w, surface_temperature, units);
+
+    mvwaddstr(w, y + 2, x, "Inner   : ");
+    render_temperature(w, inner_temperature, units);
+
+    mvwaddstr(w, y + 3, x, "Brakes  : ");
+    render_brakes_temperature(w, brakes_temperature, units);
+
+    mvwaddstr(w, y + 4, x, "Wear    : ");
+    match wear {
+        Some(wear) => {
+            fmt::set_damage_color(Some(w), wear_pct, 30, 60, 80);
+            waddstr(w, &fmt::format_perc_bar(wear / 100.0));
+            waddstr(w, &format!(" {:5.1}%", wear));
+            fmt::wreset(w);
+        }
+        None => {
+            waddstr(w, &format!("{:27}", "--"));
+        }
+    }
+}
+
+fn render_temperature(w: WINDOW, temperature: Option<u16>, units: UnitSystem) {
+    match temperature {
+        Some(t) => {
+            fmt::set_damage_color(Some(w), t.min(u8::MAX as u16) as u8, 103, 106, 110);
+            waddstr(w, &format!("{:>4}", format_temperature(t as i16, units)));
+            fmt::wreset(w);
+        }
+        None => {
+            waddstr(w, " -- ");
+        }
+    }
+}
+
+/// Brakes run much hotter than tyres: they are colored in steps of 10°C, with caution above 800°C.
+fn render_brakes_temperature(w: WINDOW, temperature: Option<u16>, units: UnitSystem) {
+    match temperature {
+        Some(t) => {
+            fmt::set_damage_color(Some(w), (t / 10).min(u8::MAX as u16) as u8, 80, 95, 110);
+            waddstr(w, &format!("{:>6}", format_temperature(t as i16, units)));
+            fmt::wreset(w);
+        }
+        None => {
+            waddstr(w, "   -- ");
+        }
+    }
+}
@@ -1,6 +1,8 @@
-use ncurses::{mvwaddstr, WINDOW};
+use ncurses::*;
 
-use f1_telemetry::packet::session::Weather;
+use f1_telemetry::packet::session::{
+    ForecastAccuracy, TemperatureChange, Weather, WeatherForecastSample,
+};
 
 use crate::models::SessionInfo;
 
@@ -114,3 +116,62 @@ pub fn render_weather(w: WINDOW, session_info: &SessionInfo, y: i32, x: i32) {
         mvwaddstr(w, y + i as i32, x, l);
     }
 }
+
+pub fn render_forecast(w: WINDOW, session_info: &SessionInfo, y: i32, x: i32) {
+    fmt::wset_bold(w);
+    let header = match session_info.forecast_accuracy {
+        ForecastAccuracy::Approximate => "WEATHER FORECAST (approximate)",
+        _ => "WEATHER FORECAST",
+    };
+    mvwaddstr(w, y, x, &format!("{:30}", header));
+    fmt::wreset(w);
+
+    let max_rows = (getmaxy(w) - y - 2).max(0) as usize;
+
+    for (i, sample) in session_info
+        .weather_forecast
+        .iter()
+        .take(max_rows)
+        .enumerate()
+    {
+        let color = match forecast_trend(sample) {
+            TemperatureChange::Up => COLOR_RED,
+            TemperatureChange::Down => COLOR_BLUE,
+            TemperatureChange::NoChange => COLOR_WHITE,
+        };
+
+        fmt::set_color(Some(w), color);
+        mvwaddstr(
+            w,
+            y + 1 + i as i32,
+            x,
+            &format!(
+                "+{:3} min  {} {:11}  Rain {:3}%",
+                sample.time_offset,
+                forecast_glyph(sample.weather),
+                sample.weather.name(),
+                sample.rain_percentage
+            ),
+        );
+        fmt::wreset(w);
+    }
+}
+
+/// Track temperature trend, falling back to the air temperature one when the track is stable
+fn forecast_trend(sample: &WeatherForecastSample) -> TemperatureChange {
+    match sample.track_temperature_change {
+        TemperatureChange::NoChange => sample.air_temperature_change,
+        change => change,
+    }
+}
+
+fn forecast_glyph(weather: Weather) -> &'static str {
+    match weather {
+        Weather::Clear => "☀",
+        Weather::LightCloud => "◓",
+        Weather::Overcast => "☁",
+        Weather::LightRain => "☂",
+        Weather::HeavyRain => "⛆",
+        Weather::Storm => "☇",
+    }
+}
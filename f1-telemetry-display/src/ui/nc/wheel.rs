@@ -0,0 +1,57 @@
+use ncurses::*;
+
+use crate::models::DrsState;
+
+use super::fmt;
+
+const REV_LIGHTS: i32 = 15;
+/// Above this, the lights flash to signal the shift point
+const SHIFT_LIGHTS_PERCENT: u8 = 95;
+
+pub(crate) fn render_rev_lights(w: WINDOW, rev_lights_percent: u8, y: i32, x: i32) {
+    let lit = (rev_lights_percent.min(100) as i32 * REV_LIGHTS + 50) / 100;
+    let flashing = rev_lights_percent >= SHIFT_LIGHTS_PERCENT && fmt::blink();
+
+    wmove(w, y, x);
+
+    for i in 0..REV_LIGHTS {
+        if i < lit && !flashing {
+            fmt::set_color(Some(w), rev_light_color(i));
+            waddstr(w, "●");
+        } else {
+            fmt::wset_dim(w);
+            waddstr(w, "○");
+        }
+        fmt::wreset(w);
+    }
+}
+
+/// The strip goes from green to red, a third of the lights each
+fn rev_light_color(idx: i32) -> i16 {
+    match idx * 3 / REV_LIGHTS {
+        0 => COLOR_GREEN,
+        1 => COLOR_YELLOW,
+        _ => COLOR_RED,
+    }
+}
+
+pub(crate) fn render_drs(w: WINDOW, drs: DrsState, y: i32, x: i32) {
+    match drs {
+        DrsState::Open => {
+            fmt::set_color(Some(w), COLOR_GREEN);
+            wattron(w, A_REVERSE());
+        }
+        DrsState::Allowed => fmt::set_color(Some(w), COLOR_YELLOW),
+        DrsState::NotAllowed => fmt::wset_dim(w),
+    }
+
+    mvwaddstr(w, y, x, " DRS ");
+    fmt::wreset(w);
+}
+
+/// Dim hint that DRS is plausible in the activation zone ahead
+pub(crate) fn render_drs_zone(w: WINDOW, zone_ahead: bool, y: i32, x: i32) {
+    fmt::wset_dim(w);
+    mvwaddstr(w, y, x, if zone_ahead { "DRS ZONE" } else { "        " });
+    fmt::wreset(w);
+}
@@ -0,0 +1,106 @@
+use std::io::{self, Write};
+
+use clap::Parser;
+use log::{error, info, LevelFilter};
+use simplelog::{ColorChoice, TerminalMode};
+
+use f1_telemetry::capture::FileSource;
+use f1_telemetry::packet::{Packet, UnpackError};
+use f1_telemetry::Stream;
+use f1_telemetry_common::logging::LogBuilder;
+use f1_telemetry_common::signal::until_shutdown;
+
+#[derive(Parser)]
+#[command(author, version, about, long_about = None, propagate_version = true)]
+struct AppArgs {
+    /// Address to bind on for the UDP packet listener
+    #[clap(long, default_value = "0.0.0.0:20777")]
+    bind: String,
+
+    /// Read a capture file from stdin instead of listening for packets, exiting at its end
+    #[clap(long)]
+    stdin: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = AppArgs::parse();
+
+    // Logs go to stderr, stdout is reserved for the exported packets
+    LogBuilder::new()
+        .with_term_logger(LevelFilter::Info, TerminalMode::Stderr, ColorChoice::Auto)
+        .build()
+        .expect("Error initializing loggger.");
+
+    let mut stdout = io::stdout();
+
+    if args.stdin {
+        export_capture(FileSource::stdin(), &mut stdout).await;
+    } else {
+        export_stream(&args.bind, &mut stdout).await;
+    }
+
+    if let Err(e) = stdout.flush() {
+        error!("Error flushing output: {}", e);
+    }
+}
+
+async fn export_stream<W: Write>(bind: &str, writer: &mut W) {
+    let stream = Stream::new(bind)
+        .await
+        .expect("Unable to bind packet socket");
+    info!("Listening for telemetry packets on: {}", bind);
+
+    let export = async {
+        loop {
+            match stream.next().await {
+                Ok(p) => {
+                    if let Err(e) = write_packet(writer, &p) {
+                        error!("Error writing packet: {}", e);
+                        break;
+                    }
+                }
+                Err(UnpackError::NotF1Packet) => {}
+                // The socket is unusable, e.g. the network interface went down
+                Err(e) if e.is_fatal() => {
+                    error!("{}, exiting", e);
+                    break;
+                }
+                Err(e) => error!("{}", e),
+            }
+        }
+    };
+
+    if until_shutdown(export).await.is_none() {
+        info!("Interrupted, exiting");
+    }
+}
+
+async fn export_capture<R, W>(mut source: FileSource<R>, writer: &mut W)
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: Write,
+{
+    loop {
+        match source.next().await {
+            Ok(Some(p)) => {
+                if let Err(e) = write_packet(writer, &p) {
+                    error!("Error writing packet: {}", e);
+                    break;
+                }
+            }
+            Ok(None) => break,
+            Err(UnpackError::Io(e)) => {
+                error!("Error reading capture: {}", e);
+                break;
+            }
+            // The frame was read, the next one can still be parsed
+            Err(e) => error!("{}", e),
+        }
+    }
+}
+
+fn write_packet<W: Write>(writer: &mut W, packet: &Packet) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, packet)?;
+    writeln!(writer)
+}
@@ -0,0 +1,1267 @@
+//! Helpers to derive higher level information from a sequence of parsed packets.
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use crate::packet::car_status::CarStatusData;
+use crate::packet::car_telemetry::CarTelemetryData;
+use crate::packet::final_classification::{FinalClassification, PacketFinalClassificationData};
+use crate::packet::generic::{Flag, Nationality, ResultStatus, SessionType, Team, TyreCompound};
+use crate::packet::lap::LapData;
+use crate::packet::participants::{PacketParticipantsData, ParticipantData};
+use crate::packet::session::{
+    MarshalZone, PacketSessionData, SafetyCar, Track, Weather, WeatherForecastSample,
+};
+use crate::packet::{Packet, PacketType};
+
+/// A new session starting, e.g. when moving on from qualifying to the race
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct SessionBoundary {
+    /// UID of the session that ended, `None` for the first session seen
+    pub previous: Option<u64>,
+    pub session_uid: u64,
+}
+
+/// Session a packet belongs to, as seen by a [`SessionTracker`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub enum SessionStatus {
+    /// The packet belongs to the current session
+    Current,
+    /// The packet is the first one of a new session
+    Started(SessionBoundary),
+    /// The packet belongs to the previous session, and was received after the new one started
+    Stale,
+}
+
+/// Tracks the `session_uid` of consecutive packets to detect session changes, so that state can
+/// be reset between sessions:
+///
+/// ```no_run
+/// # async fn run(stream: f1_telemetry::Stream) {
+/// use f1_telemetry::analysis::{SessionStatus, SessionTracker};
+///
+/// let mut tracker = SessionTracker::default();
+///
+/// while let Ok(packet) = stream.next().await {
+///     match tracker.observe(&packet) {
+///         SessionStatus::Started(boundary) => println!("New session {}", boundary.session_uid),
+///         SessionStatus::Current => {}
+///         SessionStatus::Stale => continue,
+///     }
+/// }
+/// # }
+/// ```
+///
+/// Packets of the old and new sessions may briefly interleave when a session ends. Those of the
+/// previous session are reported as [`SessionStatus::Stale`] rather than as another change.
+///
+/// The other trackers of this module, e.g. [`PersonalBests`] or [`LeaderGaps`], follow sessions
+/// with [`SessionTracker::observe_and_reset`]: they start over when a new session is detected, and
+/// ignore the packets of the previous session received afterwards.
+#[derive(Debug, Default)]
+pub struct SessionTracker {
+    current: Option<u64>,
+    previous: Option<u64>,
+}
+
+impl SessionTracker {
+    pub fn observe(&mut self, packet: &Packet) -> SessionStatus {
+        let session_uid = packet.header().session_uid;
+
+        if self.current == Some(session_uid) {
+            return SessionStatus::Current;
+        }
+
+        if self.previous == Some(session_uid) {
+            return SessionStatus::Stale;
+        }
+
+        self.previous = self.current;
+        self.current = Some(session_uid);
+
+        SessionStatus::Started(SessionBoundary {
+            previous: self.previous,
+            session_uid,
+        })
+    }
+
+    /// Observes `packet`, calling `reset` if it is the first packet of a new session. Returns
+    /// `false` if the packet belongs to the previous session, and should be ignored.
+    pub fn observe_and_reset(&mut self, packet: &Packet, reset: impl FnOnce()) -> bool {
+        match self.observe(packet) {
+            SessionStatus::Current => true,
+            SessionStatus::Started(_) => {
+                reset();
+                true
+            }
+            SessionStatus::Stale => false,
+        }
+    }
+
+    /// UID of the current session, if any packet was observed
+    pub fn session_uid(&self) -> Option<u64> {
+        self.current
+    }
+}
+
+/// Identity of a driver, independent of the index of their car
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize)]
+pub enum ParticipantId {
+    /// Human player in an online session
+    Network(u8),
+    /// AI driver, or player in an offline session
+    Driver {
+        name: String,
+        team: Team,
+        nationality: Nationality,
+    },
+}
+
+impl ParticipantId {
+    pub fn of(participant: &ParticipantData) -> ParticipantId {
+        // AI drivers are sent with a network id of 255
+        match participant.network_id {
+            Some(id) if !participant.ai_controlled && id != 255 => ParticipantId::Network(id),
+            _ => ParticipantId::Driver {
+                name: participant.name.clone(),
+                team: participant.team,
+                nationality: participant.nationality,
+            },
+        }
+    }
+}
+
+/// Changes in the participants of a session, see [`ParticipantsTracker::update`]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ParticipantsChange {
+    /// New index of the car at each previous index, `None` for the drivers that left
+    pub old_to_new: Vec<Option<usize>>,
+    /// Indices of the drivers that joined
+    pub joined: Vec<usize>,
+}
+
+/// Follows the drivers of a session across participants packets, so that data keyed by car index
+/// can be remapped when the participants change, e.g. when a player joins or leaves an online
+/// session.
+///
+/// A tracker covers a single session, see [`SessionTracker`] to detect new sessions.
+#[derive(Debug, Default)]
+pub struct ParticipantsTracker {
+    ids: Vec<ParticipantId>,
+}
+
+impl ParticipantsTracker {
+    /// Returns how the car indices changed since the previous packet, or `None` if the
+    /// participants are the same.
+    pub fn update(&mut self, packet: &PacketParticipantsData) -> Option<ParticipantsChange> {
+        let ids: Vec<ParticipantId> = packet
+            .participants
+            .iter()
+            .take(packet.num_active_cars as usize)
+            .map(ParticipantId::of)
+            .collect();
+
+        if ids == self.ids {
+            return None;
+        }
+
+        let old_to_new = self
+            .ids
+            .iter()
+            .map(|id| ids.iter().position(|i| i == id))
+            .collect();
+        let joined = (0..ids.len())
+            .filter(|idx| !self.ids.contains(&ids[*idx]))
+            .collect();
+
+        self.ids = ids;
+
+        Some(ParticipantsChange { old_to_new, joined })
+    }
+
+    /// Driver of the car at `car_index`
+    pub fn id(&self, car_index: usize) -> Option<&ParticipantId> {
+        self.ids.get(car_index)
+    }
+
+    /// Current index of the car of a driver
+    pub fn car_index(&self, id: &ParticipantId) -> Option<usize> {
+        self.ids.iter().position(|i| i == id)
+    }
+}
+
+/// Follows the car of a driver by name, e.g. for an overlay set to show a given driver, and
+/// reads their data from the packets.
+///
+/// The car index is resolved from participants packets, and again whenever the participants
+/// change, as indices can shift in online sessions. Names are matched case insensitively, the
+/// game sending them in capitals. Until the driver is found, e.g. when a new session starts,
+/// the accessors return `None`.
+///
+/// Once found, the driver is followed by [`ParticipantId`] rather than by name, so that another
+/// driver with the same name joining the session does not take their place. If several drivers
+/// share the name when the driver is looked up, the first one is followed.
+#[derive(Debug)]
+pub struct FollowedDriver {
+    name: String,
+    session: SessionTracker,
+    car_index: Option<usize>,
+    id: Option<ParticipantId>,
+}
+
+impl FollowedDriver {
+    pub fn new(name: impl Into<String>) -> FollowedDriver {
+        FollowedDriver {
+            name: name.into().to_lowercase(),
+            session: SessionTracker::default(),
+            car_index: None,
+            id: None,
+        }
+    }
+
+    pub fn observe(&mut self, packet: &Packet) {
+        if !self.session.observe_and_reset(packet, || {
+            self.car_index = None;
+            self.id = None;
+        }) {
+            return;
+        }
+
+        if let Packet::Participants(p) = packet {
+            self.resolve(p);
+        }
+    }
+
+    /// Looks for the driver in a participants packet.
+    pub fn resolve(&mut self, participants: &PacketParticipantsData) -> Option<usize> {
+        let active = &participants.participants[..participants
+            .participants
+            .len()
+            .min(participants.num_active_cars as usize)];
+
+        if let Some(id) = &self.id {
+            if let Some(idx) = active.iter().position(|p| ParticipantId::of(p) == *id) {
+                self.car_index = Some(idx);
+                return self.car_index;
+            }
+        }
+
+        let mut matching = active
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.name.to_lowercase() == self.name);
+
+        self.car_index = matching.next().map(|(idx, _)| idx);
+        self.id = self.car_index.map(|idx| ParticipantId::of(&active[idx]));
+
+        if let (Some(idx), Some(_)) = (self.car_index, matching.next()) {
+            log::warn!(
+                "Several drivers are named {}, following car {}",
+                active[idx].name,
+                idx
+            );
+        }
+
+        self.car_index
+    }
+
+    /// Index of the driver's car, if found in the last participants packet
+    pub fn car_index(&self) -> Option<usize> {
+        self.car_index
+    }
+
+    pub fn lap_data<'a>(&self, packet: &'a Packet) -> Option<&'a LapData> {
+        match packet {
+            Packet::LapData(p) => p.lap_data.get(self.car_index?),
+            _ => None,
+        }
+    }
+
+    pub fn telemetry<'a>(&self, packet: &'a Packet) -> Option<&'a CarTelemetryData> {
+        match packet {
+            Packet::CarTelemetry(p) => p.car_telemetry_data.get(self.car_index?),
+            _ => None,
+        }
+    }
+
+    pub fn status<'a>(&self, packet: &'a Packet) -> Option<&'a CarStatusData> {
+        match packet {
+            Packet::CarStatus(p) => p.car_status_data.get(self.car_index?),
+            _ => None,
+        }
+    }
+}
+
+/// Brake pressure above which the brakes count as applied
+const BRAKE_APPLIED: f32 = 0.1;
+
+/// Telemetry of the player's car over a lap, see [`LapTelemetry`]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+pub struct LapTelemetrySummary {
+    pub lap: u8,
+    /// Number of telemetry packets received during the lap
+    pub samples: u32,
+    /// Maximum speed in km/h
+    pub max_speed: u16,
+    /// Average throttle application, from 0.0 to 1.0
+    pub avg_throttle: f32,
+    /// Number of times the brakes were applied
+    pub brake_applications: u32,
+    pub max_rpm: u16,
+}
+
+/// Aggregates the telemetry of the player's car lap by lap.
+///
+/// Laps are read from lap data packets and telemetry from car telemetry packets, other packets
+/// are ignored. Telemetry received before the first lap data packet of a session is dropped, and
+/// the lap in progress is discarded when a new session starts.
+///
+/// Only laps observed from start to finish are reported: the lap in progress when the tracking
+/// starts is skipped, as is a lap resumed after a flashback.
+#[derive(Debug, Default)]
+pub struct LapTelemetry {
+    session: SessionTracker,
+    current: Option<LapTelemetrySummary>,
+    /// Whether the start of the current lap was missed
+    partial: bool,
+    throttle_sum: f32,
+    braking: bool,
+}
+
+impl LapTelemetry {
+    /// Returns the summary of the previous lap when a new lap starts.
+    pub fn observe(&mut self, packet: &Packet) -> Option<LapTelemetrySummary> {
+        if !self.session.observe_and_reset(packet, || {
+            self.current = None;
+            self.braking = false;
+        }) {
+            return None;
+        }
+
+        match packet {
+            Packet::LapData(p) => {
+                let lap = p.player_data()?.current_lap_num;
+
+                if self.current.is_some_and(|c| c.lap == lap) {
+                    return None;
+                }
+
+                let previous = self.current.take();
+                let next_lap = previous.and_then(|c| c.lap.checked_add(1)) == Some(lap);
+                let completed = previous.filter(|c| next_lap && !self.partial && c.samples > 0);
+
+                self.start_lap(lap);
+                self.partial = !next_lap;
+
+                completed
+            }
+            Packet::CarTelemetry(p) => {
+                if let (Some(current), Some(td)) = (&mut self.current, p.player_data()) {
+                    current.samples += 1;
+                    current.max_speed = current.max_speed.max(td.speed);
+                    current.max_rpm = current.max_rpm.max(td.engine_rpm);
+
+                    self.throttle_sum += td.throttle;
+                    current.avg_throttle = self.throttle_sum / current.samples as f32;
+
+                    let braking = td.brake > BRAKE_APPLIED;
+                    if braking && !self.braking {
+                        current.brake_applications += 1;
+                    }
+                    self.braking = braking;
+                }
+
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Summary of the lap in progress, if any
+    pub fn current(&self) -> Option<&LapTelemetrySummary> {
+        self.current.as_ref()
+    }
+
+    fn start_lap(&mut self, lap: u8) {
+        self.current = Some(LapTelemetrySummary {
+            lap,
+            samples: 0,
+            max_speed: 0,
+            avg_throttle: 0.0,
+            brake_applications: 0,
+            max_rpm: 0,
+        });
+        self.throttle_sum = 0.0;
+    }
+}
+
+/// A car completing a valid lap faster than its previous ones, see [`PersonalBests`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct PersonalBest {
+    pub car_index: u8,
+    /// Number of the completed lap
+    pub lap: u8,
+    /// Lap time in milliseconds
+    pub lap_time: u32,
+    /// Previous best lap time in milliseconds, `None` for the first valid lap of the session
+    pub previous: Option<u32>,
+}
+
+/// Detects personal bests from lap data packets, for every car.
+///
+/// Best laps are tracked from the last lap times, as the lap data of F1 2021 and later games has
+/// no best lap time. Invalidated laps are ignored, as are the laps that were in progress when the
+/// tracking started.
+#[derive(Debug, Default)]
+pub struct PersonalBests {
+    session: SessionTracker,
+    cars: Vec<CarLaps>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct CarLaps {
+    /// Lap in progress, 0 until lap data is received for the car
+    lap: u8,
+    lap_invalid: bool,
+    best: Option<u32>,
+}
+
+impl PersonalBests {
+    pub fn observe(&mut self, packet: &Packet) -> Vec<PersonalBest> {
+        if !self.session.observe_and_reset(packet, || self.cars.clear()) {
+            return Vec::new();
+        }
+
+        let lap_data = match packet {
+            Packet::LapData(p) => &p.lap_data,
+            _ => return Vec::new(),
+        };
+
+        if self.cars.len() < lap_data.len() {
+            self.cars.resize(lap_data.len(), CarLaps::default());
+        }
+
+        let mut personal_bests = Vec::new();
+
+        for (car_index, (car, ld)) in self.cars.iter_mut().zip(lap_data).enumerate() {
+            if ld.current_lap_num == car.lap {
+                car.lap_invalid |= ld.current_lap_invalid;
+                continue;
+            }
+
+            let completed = car.lap > 0 && car.lap.checked_add(1) == Some(ld.current_lap_num);
+            let lap_time = ld.last_lap_time;
+
+            if completed
+                && !car.lap_invalid
+                && lap_time > 0
+                && car.best.is_none_or(|b| lap_time < b)
+            {
+                personal_bests.push(PersonalBest {
+                    car_index: car_index as u8,
+                    lap: car.lap,
+                    lap_time,
+                    previous: car.best,
+                });
+                car.best = Some(lap_time);
+            }
+
+            // The time of a lap joined midway is of no use
+            car.lap_invalid = ld.current_lap_invalid || car.lap == 0;
+            car.lap = ld.current_lap_num;
+        }
+
+        personal_bests
+    }
+
+    /// Best valid lap time of a car in milliseconds, if it completed one
+    pub fn best(&self, car_index: usize) -> Option<u32> {
+        self.cars.get(car_index)?.best
+    }
+}
+
+/// Number of overtakes kept by [`PositionChanges`]
+const OVERTAKES_HISTORY_SIZE: usize = 100;
+
+/// A car moving ahead of another one in race position, see [`PositionChanges`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct Overtake {
+    /// Index of the car that moved ahead
+    pub car_index: u8,
+    /// Index of the car that was passed
+    pub passed_car_index: u8,
+    /// Session time the positions changed at, in milliseconds
+    pub session_time: u32,
+}
+
+/// Maximum gap between two cars fighting for a position, in seconds
+const BATTLE_MAX_GAP: f32 = 1.0;
+/// How long cars are considered fighting after swapping positions, in milliseconds
+const BATTLE_SWAP_WINDOW: u32 = 60_000;
+
+/// Two cars fighting for a position, see [`PositionChanges::battles`]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+pub struct Battle {
+    pub car_ahead_index: u8,
+    pub car_behind_index: u8,
+    /// Gap between the two cars, in seconds
+    pub gap: f32,
+}
+
+/// Tracks the race positions from lap data packets, to detect cars swapping positions.
+///
+/// Only active cars are considered, so that retirements are not seen as overtakes. The last
+/// overtakes of the session are kept.
+#[derive(Debug, Default)]
+pub struct PositionChanges {
+    session: SessionTracker,
+    positions: Vec<Option<u8>>,
+    overtakes: VecDeque<Overtake>,
+    session_time: u32,
+}
+
+impl PositionChanges {
+    /// Returns the overtakes since the previous lap data packet.
+    pub fn observe(&mut self, packet: &Packet) -> Vec<Overtake> {
+        if !self.session.observe_and_reset(packet, || {
+            self.positions.clear();
+            self.overtakes.clear();
+        }) {
+            return Vec::new();
+        }
+
+        let p = match packet {
+            Packet::LapData(p) => p,
+            _ => return Vec::new(),
+        };
+
+        let positions: Vec<Option<u8>> = p
+            .lap_data
+            .iter()
+            .map(|ld| {
+                (ld.result_status == ResultStatus::Active && ld.car_position > 0)
+                    .then_some(ld.car_position)
+            })
+            .collect();
+
+        let mut overtakes = Vec::new();
+
+        for (car, (old, new)) in self.positions.iter().zip(&positions).enumerate() {
+            let (Some(old), Some(new)) = (old, new) else {
+                continue;
+            };
+
+            for (passed, (passed_old, passed_new)) in
+                self.positions.iter().zip(&positions).enumerate()
+            {
+                if let (Some(passed_old), Some(passed_new)) = (passed_old, passed_new) {
+                    if old > passed_old && new < passed_new {
+                        overtakes.push(Overtake {
+                            car_index: car as u8,
+                            passed_car_index: passed as u8,
+                            session_time: p.header.session_time,
+                        });
+                    }
+                }
+            }
+        }
+
+        self.positions = positions;
+        self.session_time = p.header.session_time;
+
+        for overtake in &overtakes {
+            if self.overtakes.len() == OVERTAKES_HISTORY_SIZE {
+                self.overtakes.pop_front();
+            }
+            self.overtakes.push_back(*overtake);
+        }
+
+        overtakes
+    }
+
+    /// Last overtakes of the session, oldest first
+    pub fn overtakes(&self) -> impl Iterator<Item = &Overtake> {
+        self.overtakes.iter()
+    }
+
+    /// Whether two cars swapped positions, either way, at or after `since` (session time in
+    /// milliseconds).
+    pub fn swapped_since(&self, car_a: usize, car_b: usize, since: u32) -> bool {
+        self.overtakes
+            .iter()
+            .rev()
+            .take_while(|o| o.session_time >= since)
+            .any(|o| {
+                let (car, passed) = (o.car_index as usize, o.passed_car_index as usize);
+                (car, passed) == (car_a, car_b) || (car, passed) == (car_b, car_a)
+            })
+    }
+
+    /// Cars less than a second behind the car one position ahead of them, which they swapped
+    /// positions with in the last minute, ordered by position.
+    ///
+    /// `gap` returns the gap in seconds between the car ahead and the car behind, given their
+    /// car indices, e.g. from the distance history of the cars.
+    pub fn battles(&self, gap: impl Fn(usize, usize) -> Option<f32>) -> Vec<Battle> {
+        let since = self.session_time.saturating_sub(BATTLE_SWAP_WINDOW);
+
+        let mut cars: Vec<(u8, usize)> = self
+            .positions
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, position)| Some(((*position)?, idx)))
+            .collect();
+        cars.sort_unstable();
+
+        cars.windows(2)
+            .filter_map(|pair| {
+                let ((_, ahead), (_, behind)) = (pair[0], pair[1]);
+
+                if !self.swapped_since(ahead, behind, since) {
+                    return None;
+                }
+
+                let gap = gap(ahead, behind).filter(|g| *g < BATTLE_MAX_GAP)?;
+
+                Some(Battle {
+                    car_ahead_index: ahead as u8,
+                    car_behind_index: behind as u8,
+                    gap,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Minimum time between two samples of a [`DistanceHistory`], in milliseconds
+const DISTANCE_HISTORY_INTERVAL: u32 = 100;
+/// How far back a [`DistanceHistory`] goes, in milliseconds
+const DISTANCE_HISTORY_DURATION: u32 = 180_000;
+
+/// Total distance covered by a car over the last few minutes, to find when the car was at a given
+/// distance, e.g. to compute the time gap between two cars.
+///
+/// A sample is kept every 100 ms, the last sample always being the latest known position. The
+/// history is cleared on flashbacks.
+#[derive(Debug, Clone, Default)]
+pub struct DistanceHistory {
+    samples: VecDeque<(u32, f32)>,
+}
+
+impl DistanceHistory {
+    /// Adds the total distance of the car at session time `time`, in milliseconds.
+    pub fn push(&mut self, time: u32, distance: f32) {
+        let len = self.samples.len();
+
+        if let Some(&(last_time, last_distance)) = self.samples.back() {
+            if time < last_time || distance < last_distance {
+                // Flashback or session restart
+                self.samples.clear();
+            } else if len >= 2 && time - self.samples[len - 2].0 < DISTANCE_HISTORY_INTERVAL {
+                self.samples[len - 1] = (time, distance);
+                return;
+            }
+        }
+
+        while self
+            .samples
+            .front()
+            .is_some_and(|(t, _)| time - t > DISTANCE_HISTORY_DURATION)
+        {
+            self.samples.pop_front();
+        }
+
+        self.samples.push_back((time, distance));
+    }
+
+    /// Session time of the latest sample
+    pub fn last_time(&self) -> Option<u32> {
+        self.samples.back().map(|(time, _)| *time)
+    }
+
+    /// Session time at which the car was at `distance`, interpolated between the closest samples.
+    pub fn time_at(&self, distance: f32) -> Option<f32> {
+        let idx = self.samples.partition_point(|(_, d)| *d < distance);
+
+        let (t1, d1) = *self.samples.get(idx)?;
+        if d1 == distance {
+            return Some(t1 as f32);
+        }
+
+        let (t0, d0) = *self.samples.get(idx.checked_sub(1)?)?;
+        let ratio = (distance - d0) / (d1 - d0);
+
+        Some(t0 as f32 + ratio * (t1 - t0) as f32)
+    }
+}
+
+/// Gap of a car to the race leader, see [`LeaderGaps`]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+pub struct LeaderGap {
+    pub car_index: u8,
+    pub position: u8,
+    /// Time since the leader was at the same distance, in seconds, 0 for the leader. `None` when
+    /// the history of the leader does not go back far enough.
+    pub gap: Option<f32>,
+    /// Number of laps the car is behind the leader
+    pub laps_down: u8,
+}
+
+/// Computes the time gap of every car to the leader from lap data packets.
+///
+/// The total distance of each car is sampled in a [`DistanceHistory`], and the gap of a car is the
+/// time elapsed since the leader covered the distance the car is at.
+#[derive(Debug, Default)]
+pub struct LeaderGaps {
+    session: SessionTracker,
+    distances: Vec<DistanceHistory>,
+    gaps: Vec<LeaderGap>,
+}
+
+impl LeaderGaps {
+    pub fn observe(&mut self, packet: &Packet) {
+        if !self.session.observe_and_reset(packet, || {
+            self.distances.clear();
+            self.gaps.clear();
+        }) {
+            return;
+        }
+
+        let p = match packet {
+            Packet::LapData(p) => p,
+            _ => return,
+        };
+
+        let time = p.header.session_time;
+
+        if self.distances.len() < p.lap_data.len() {
+            self.distances
+                .resize(p.lap_data.len(), DistanceHistory::default());
+        }
+
+        for (history, ld) in self.distances.iter_mut().zip(&p.lap_data) {
+            history.push(time, ld.total_distance);
+        }
+
+        let mut cars: Vec<(usize, &LapData)> = p
+            .lap_data
+            .iter()
+            .enumerate()
+            .filter(|(_, ld)| ld.result_status == ResultStatus::Active && ld.car_position > 0)
+            .collect();
+        cars.sort_by_key(|(_, ld)| ld.car_position);
+
+        let Some(&(leader_index, leader)) = cars.first() else {
+            self.gaps.clear();
+            return;
+        };
+        let leader_distances = &self.distances[leader_index];
+
+        self.gaps = cars
+            .iter()
+            .map(|&(car_index, ld)| {
+                let gap = if car_index == leader_index {
+                    Some(0.0)
+                } else {
+                    leader_distances
+                        .time_at(ld.total_distance)
+                        .map(|t| (time as f32 - t) / 1000.0)
+                };
+
+                let laps = leader.current_lap_num.saturating_sub(ld.current_lap_num);
+                let laps_down = if leader.lap_distance < ld.lap_distance {
+                    laps.saturating_sub(1)
+                } else {
+                    laps
+                };
+
+                LeaderGap {
+                    car_index: car_index as u8,
+                    position: ld.car_position,
+                    gap,
+                    laps_down,
+                }
+            })
+            .collect();
+    }
+
+    /// Gaps of the running cars as of the last lap data packet, by position
+    pub fn gaps(&self) -> &[LeaderGap] {
+        &self.gaps
+    }
+}
+
+/// Best lap of a car in time trial, see [`TimeTrial`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct TimeTrialLap {
+    pub car_index: u8,
+    /// Lap time in milliseconds
+    pub lap_time: u32,
+    /// Sector times in milliseconds
+    pub sector_times: [u32; 3],
+}
+
+/// Laps compared in time trial, see [`TimeTrial`]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
+pub struct TimeTrialInfo {
+    /// Best lap of the player in the session
+    pub player_best: Option<TimeTrialLap>,
+    /// Personal best lap, driven by the personal best ghost car
+    pub personal_best: Option<TimeTrialLap>,
+    /// Lap of the rival ghost car
+    pub rival: Option<TimeTrialLap>,
+}
+
+/// Gathers the player, personal best and rival laps of a time trial session.
+///
+/// The ghost cars are identified from lap data packets, which only name them in time trial and
+/// from F1 22 on, and the best lap of each car is read from session history packets.
+#[derive(Debug, Default)]
+pub struct TimeTrial {
+    session: SessionTracker,
+    player_car_index: u8,
+    personal_best_car_index: Option<u8>,
+    rival_car_index: Option<u8>,
+    best_laps: Vec<Option<TimeTrialLap>>,
+}
+
+impl TimeTrial {
+    pub fn observe(&mut self, packet: &Packet) {
+        if !self.session.observe_and_reset(packet, || {
+            self.player_car_index = 0;
+            self.personal_best_car_index = None;
+            self.rival_car_index = None;
+            self.best_laps.clear();
+        }) {
+            return;
+        }
+
+        match packet {
+            Packet::LapData(p) => {
+                self.player_car_index = p.header.player_car_index;
+                self.personal_best_car_index = p.time_trial_personal_best_car_idx;
+                self.rival_car_index = p.time_trial_rival_car_idx;
+            }
+            Packet::SessionHistory(p) => {
+                let car_index = p.car_index as usize;
+                if self.best_laps.len() <= car_index {
+                    self.best_laps.resize(car_index + 1, None);
+                }
+
+                self.best_laps[car_index] = (p.best_lap_time_lap_number as usize)
+                    .checked_sub(1)
+                    .and_then(|lap| p.lap_history.get(lap))
+                    .filter(|lap| lap.lap_time > 0)
+                    .map(|lap| TimeTrialLap {
+                        car_index: p.car_index,
+                        lap_time: lap.lap_time,
+                        sector_times: [
+                            sector_time(lap.sector_1_time, lap.sector_1_time_minutes),
+                            sector_time(lap.sector_2_time, lap.sector_2_time_minutes),
+                            sector_time(lap.sector_3_time, lap.sector_3_time_minutes),
+                        ],
+                    });
+            }
+            _ => {}
+        }
+    }
+
+    /// Laps to compare, or `None` outside of time trial, when the game names no ghost car.
+    pub fn info(&self) -> Option<TimeTrialInfo> {
+        if self.personal_best_car_index.is_none() && self.rival_car_index.is_none() {
+            return None;
+        }
+
+        let best_lap = |car: u8| self.best_laps.get(car as usize).copied().flatten();
+
+        Some(TimeTrialInfo {
+            player_best: best_lap(self.player_car_index),
+            personal_best: self.personal_best_car_index.and_then(best_lap),
+            rival: self.rival_car_index.and_then(best_lap),
+        })
+    }
+}
+
+fn sector_time(millis: u16, minutes: u8) -> u32 {
+    minutes as u32 * 60_000 + millis as u32
+}
+
+/// Packet types sent at the rate set in the telemetry settings, which frame identifiers are
+/// checked by [`FrameMonitor`]
+const MONITORED_PACKET_TYPES: [PacketType; 5] = [
+    PacketType::Motion,
+    PacketType::LapData,
+    PacketType::CarTelemetry,
+    PacketType::CarStatus,
+    PacketType::MotionEx,
+];
+
+/// Frames of a packet type, see [`FrameMonitor`]
+#[derive(Debug, Copy, Clone)]
+struct PacketFrames {
+    packet_type: PacketType,
+    last: u32,
+    /// Smallest increment seen between two packets, the number of frames between two sends
+    interval: Option<u32>,
+}
+
+/// Counts the packets lost in transit from gaps in the frame identifiers of the headers.
+///
+/// The game sends packets every few frames, depending on the rate set in its telemetry settings,
+/// so the smallest gap between two packets of a type is taken as their interval and larger gaps
+/// as missed packets. Only the packet types sent at that rate are checked. The overall frame
+/// identifier of F1 23 is used when present, as the frame identifier goes back on flashbacks;
+/// frames going back are otherwise ignored.
+#[derive(Debug, Default)]
+pub struct FrameMonitor {
+    session: SessionTracker,
+    frames: Vec<PacketFrames>,
+    dropped: u32,
+}
+
+impl FrameMonitor {
+    /// Returns the number of packets of the same type missed before this one.
+    pub fn observe(&mut self, packet: &Packet) -> u32 {
+        if !self.session.observe_and_reset(packet, || {
+            self.frames.clear();
+            self.dropped = 0;
+        }) {
+            return 0;
+        }
+
+        let header = packet.header();
+        if !MONITORED_PACKET_TYPES.contains(&header.packet_type) {
+            return 0;
+        }
+
+        let frame = header
+            .overall_frame_identifier
+            .unwrap_or(header.frame_identifier);
+
+        let Some(frames) = self
+            .frames
+            .iter_mut()
+            .find(|f| f.packet_type == header.packet_type)
+        else {
+            self.frames.push(PacketFrames {
+                packet_type: header.packet_type,
+                last: frame,
+                interval: None,
+            });
+            return 0;
+        };
+
+        let step = frame.wrapping_sub(frames.last);
+        frames.last = frame;
+
+        // Repeated or older frames, e.g. after a flashback
+        if step == 0 || step > u32::MAX / 2 {
+            return 0;
+        }
+
+        let interval = frames.interval.map_or(step, |i| i.min(step));
+        frames.interval = Some(interval);
+
+        let dropped = step / interval - 1;
+        self.dropped += dropped;
+
+        dropped
+    }
+
+    /// Number of packets missed since the session started
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+}
+
+/// Whether DRS could plausibly be activated `lap_distance` metres into the lap.
+///
+/// This is a heuristic: the packets do not describe the DRS zones, so it only rules DRS out when
+/// the session data shows it is disabled, see [`drs_allowed`]. Returns `None` when the session
+/// packet does not locate `lap_distance`, e.g. before the marshal zones are known or before the
+/// car crosses the line for the first time.
+///
+/// On F1 2021 and later, [`CarStatusData::drs_activation_distance`] tells when the car is about to
+/// enter an activation zone.
+pub fn drs_available_here(lap_distance: f32, session: &PacketSessionData) -> Option<bool> {
+    if lap_distance < 0.0 {
+        return None;
+    }
+
+    let zone = session.marshal_zone_at(lap_distance)?;
+
+    Some(drs_allowed(
+        zone,
+        session.safety_car_status,
+        session.weather,
+    ))
+}
+
+/// Whether DRS is allowed in a marshal zone, i.e. not under the safety car or the virtual safety
+/// car, not in the rain, and not where a yellow or red flag is waved. This is the part of
+/// [`drs_available_here`] that does not need the whole session packet.
+pub fn drs_allowed(zone: &MarshalZone, safety_car: SafetyCar, weather: Weather) -> bool {
+    safety_car == SafetyCar::None
+        && !weather.is_rain()
+        && !matches!(zone.zone_flag, Flag::Yellow | Flag::Red)
+}
+
+/// Rain probability, in percent, from which [`weather_change`] expects a forecast sample to be
+/// wet
+pub const DEFAULT_RAIN_THRESHOLD: u8 = 50;
+
+/// Change between dry and wet conditions expected by the forecast, see [`weather_change`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub enum WeatherChange {
+    /// Rain is expected in the given number of minutes
+    Rain(u8),
+    /// The rain is expected to stop in the given number of minutes
+    Drying(u8),
+}
+
+/// First change between dry and wet conditions in `forecast`, e.g. to time the switch to
+/// intermediates.
+///
+/// A forecast sample is wet if it predicts rain, or if its rain probability is at least
+/// `rain_threshold` percent. The rain probability is only sent since F1 2021, the weather alone is
+/// used with earlier games. The samples should be those of the current session, ordered by time
+/// offset. Returns `None` if no sample ahead changes the conditions of `weather`, including when
+/// there is no forecast.
+pub fn weather_change(
+    weather: Weather,
+    forecast: &[WeatherForecastSample],
+    rain_threshold: u8,
+) -> Option<WeatherChange> {
+    let raining = weather.is_rain();
+
+    forecast
+        .iter()
+        .filter(|s| s.time_offset > 0)
+        .find(|s| (s.weather.is_rain() || s.rain_percentage >= rain_threshold) != raining)
+        .map(|s| {
+            if raining {
+                WeatherChange::Drying(s.time_offset)
+            } else {
+                WeatherChange::Rain(s.time_offset)
+            }
+        })
+}
+
+/// Result of a driver in a [`SessionSummary`]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct ClassifiedDriver {
+    pub car_index: u8,
+    pub name: String,
+    pub team: Team,
+    pub nationality: Nationality,
+    pub race_number: u8,
+    pub result: FinalClassification,
+}
+
+/// Outcome of a session, joining its final classification with the names of the drivers and the
+/// session details, e.g. to store the results of a race.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct SessionSummary {
+    pub session_uid: u64,
+    pub track: Track,
+    pub session_type: SessionType,
+    /// Weather as of the last session packet
+    pub weather: Weather,
+    /// Track temperature in celsius, as of the last session packet
+    pub track_temperature: i8,
+    /// Air temperature in celsius, as of the last session packet
+    pub air_temperature: i8,
+    pub total_laps: u8,
+    /// Drivers by finishing position
+    pub classification: Vec<ClassifiedDriver>,
+}
+
+impl SessionSummary {
+    pub fn new(
+        final_classification: &PacketFinalClassificationData,
+        participants: &PacketParticipantsData,
+        session: &PacketSessionData,
+    ) -> SessionSummary {
+        let mut classification: Vec<ClassifiedDriver> = final_classification
+            .final_classifications
+            .iter()
+            .zip(&participants.participants)
+            .take(final_classification.num_cars as usize)
+            .enumerate()
+            .map(|(car_index, (result, participant))| ClassifiedDriver {
+                car_index: car_index as u8,
+                name: participant.name.clone(),
+                team: participant.team,
+                nationality: participant.nationality,
+                race_number: participant.race_number,
+                result: result.clone(),
+            })
+            .collect();
+        classification.sort_by_key(|d| d.result.position);
+
+        SessionSummary {
+            session_uid: final_classification.header.session_uid,
+            track: session.track,
+            session_type: session.session_type,
+            weather: session.weather,
+            track_temperature: session.track_temperature,
+            air_temperature: session.air_temperature,
+            total_laps: session.total_laps,
+            classification,
+        }
+    }
+}
+
+/// Builds a [`SessionSummary`] when the final classification of a session is received, from the
+/// last participants and session packets of the same session.
+///
+/// Both are sent every few seconds, so they are normally seen long before the end of a session.
+/// A final classification received before them is ignored.
+#[derive(Debug, Default)]
+pub struct SessionSummaryBuilder {
+    session: SessionTracker,
+    participants: Option<PacketParticipantsData>,
+    session_data: Option<PacketSessionData>,
+}
+
+impl SessionSummaryBuilder {
+    /// Returns the summary of the session when its final classification is received.
+    pub fn observe(&mut self, packet: &Packet) -> Option<SessionSummary> {
+        if !self.session.observe_and_reset(packet, || {
+            self.participants = None;
+            self.session_data = None;
+        }) {
+            return None;
+        }
+
+        match packet {
+            Packet::Participants(p) => {
+                self.participants = Some(p.clone());
+                None
+            }
+            Packet::Session(p) => {
+                self.session_data = Some(p.clone());
+                None
+            }
+            Packet::FinalClassification(p) => Some(SessionSummary::new(
+                p,
+                self.participants.as_ref()?,
+                self.session_data.as_ref()?,
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A car starting a new stint, on a different tyre compound than the previous one
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct StintChange {
+    pub car_index: u8,
+    /// Lap on which the new stint started, 0 if no lap data was received for the car yet
+    pub lap: u8,
+    /// Compound of the previous stint, `None` for the first stint seen in the session
+    pub from: Option<TyreCompound>,
+    pub to: TyreCompound,
+}
+
+/// Iterator adaptor emitting a [`StintChange`] every time a car changes tyre compound, see
+/// [`StintChangesExt::stint_changes`].
+///
+/// Compounds are read from car status packets and laps from lap data packets, other packets are
+/// ignored.
+pub struct StintChanges<I> {
+    packets: I,
+    session: SessionTracker,
+    compounds: Vec<Option<TyreCompound>>,
+    laps: Vec<u8>,
+    pending: VecDeque<StintChange>,
+}
+
+impl<I> StintChanges<I> {
+    pub fn new(packets: I) -> StintChanges<I> {
+        StintChanges {
+            packets,
+            session: SessionTracker::default(),
+            compounds: Vec::new(),
+            laps: Vec::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn update(&mut self, packet: &Packet) {
+        if !self.session.observe_and_reset(packet, || {
+            self.compounds.clear();
+            self.laps.clear();
+        }) {
+            return;
+        }
+
+        match packet {
+            Packet::LapData(p) => {
+                self.laps = p.lap_data.iter().map(|l| l.current_lap_num).collect();
+            }
+            Packet::CarStatus(p) => {
+                if self.compounds.len() < p.car_status_data.len() {
+                    self.compounds.resize(p.car_status_data.len(), None);
+                }
+
+                for (car_index, car_status) in p.car_status_data.iter().enumerate() {
+                    let to = car_status.actual_tyre_compound;
+                    let from = self.compounds[car_index];
+
+                    // Cars without data (e.g. empty grid slots) report an invalid compound
+                    if to == TyreCompound::Invalid || from == Some(to) {
+                        continue;
+                    }
+
+                    self.compounds[car_index] = Some(to);
+                    self.pending.push_back(StintChange {
+                        car_index: car_index as u8,
+                        lap: self.laps.get(car_index).copied().unwrap_or(0),
+                        from,
+                        to,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<I> Iterator for StintChanges<I>
+where
+    I: Iterator,
+    I::Item: Borrow<Packet>,
+{
+    type Item = StintChange;
+
+    fn next(&mut self) -> Option<StintChange> {
+        loop {
+            if let Some(change) = self.pending.pop_front() {
+                return Some(change);
+            }
+
+            let packet = self.packets.next()?;
+            self.update(packet.borrow());
+        }
+    }
+}
+
+/// Adds [`stint_changes`](StintChangesExt::stint_changes) to iterators over packets, owned or
+/// borrowed.
+pub trait StintChangesExt: Iterator + Sized
+where
+    Self::Item: Borrow<Packet>,
+{
+    fn stint_changes(self) -> StintChanges<Self> {
+        StintChanges::new(self)
+    }
+}
+
+impl<I> StintChangesExt for I
+where
+    I: Iterator,
+    I::Item: Borrow<Packet>,
+{
+}
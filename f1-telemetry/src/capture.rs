@@ -0,0 +1,197 @@
+//! Recording and replay of telemetry sessions.
+//!
+//! A capture file is a plain sequence of frames, one per received UDP datagram:
+//!
+//! ```text
+//! timestamp:  Time at which the datagram was received, in milliseconds since the UNIX epoch (u64, little endian)
+//! length:     Length of the datagram in bytes (u16, little endian)
+//! data:       Raw datagram, as sent by the game
+//! ```
+//!
+//! Captures can also be piped through the standard input, see [`FileSource::stdin`].
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader, BufWriter, Stdin};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+use crate::packet::{parse_packet_with, Packet, ParseOptions, UnpackError};
+use crate::source::PacketSource;
+
+/// Reads packets from a capture file, or from any other reader of the same format.
+///
+/// By default packets are returned as fast as they can be read. Use [`FileSource::with_speed`]
+/// to replay them at the pace they were originally sent by the game.
+pub struct FileSource<R = File> {
+    reader: BufReader<R>,
+    speed: Option<f32>,
+    last_time: Option<(u64, u32)>,
+    parse_options: ParseOptions,
+}
+
+impl FileSource {
+    pub async fn open<P: AsRef<Path>>(path: P) -> io::Result<FileSource> {
+        let file = File::open(path).await?;
+
+        Ok(FileSource::from_reader(file))
+    }
+}
+
+impl FileSource<Stdin> {
+    /// Reads a capture piped through the standard input, e.g. `cat session.bin | my-tool`.
+    pub fn stdin() -> FileSource<Stdin> {
+        FileSource::from_reader(tokio::io::stdin())
+    }
+}
+
+impl<R: AsyncRead + Unpin> FileSource<R> {
+    pub fn from_reader(reader: R) -> FileSource<R> {
+        FileSource {
+            reader: BufReader::new(reader),
+            speed: None,
+            last_time: None,
+            parse_options: ParseOptions::default(),
+        }
+    }
+
+    /// Honor the original timing between packets, based on the session time of each packet.
+    ///
+    /// A speed of `1.0` replays the session in real time, `2.0` twice as fast, etc.
+    pub fn with_speed(mut self, speed: f32) -> FileSource<R> {
+        self.speed = Some(speed).filter(|s| *s > 0.0);
+        self
+    }
+
+    /// Options used to decode the packets of the capture, strict by default.
+    pub fn with_parse_options(mut self, options: ParseOptions) -> FileSource<R> {
+        self.parse_options = options;
+        self
+    }
+
+    /// Returns the next packet in the capture, or `None` once the end of the input is reached.
+    pub async fn next(&mut self) -> Result<Option<Packet>, UnpackError> {
+        let data = match read_frame(&mut self.reader).await? {
+            Some((_, data)) => data,
+            None => return Ok(None),
+        };
+
+        let packet = parse_packet_with(data.len(), &data, self.parse_options)?;
+        self.wait_for(&packet).await;
+
+        Ok(Some(packet))
+    }
+
+    async fn wait_for(&mut self, packet: &Packet) {
+        let header = packet.header();
+        let current = (header.session_uid, header.session_time);
+
+        if let (Some(speed), Some((session_uid, session_time))) = (self.speed, self.last_time) {
+            if session_uid == current.0 && current.1 > session_time {
+                let delta = (current.1 - session_time) as f32 / speed;
+                tokio::time::sleep(Duration::from_secs_f32(delta / 1000.0)).await;
+            }
+        }
+
+        self.last_time = Some(current);
+    }
+}
+
+impl<R: AsyncRead + Unpin + Send> PacketSource for FileSource<R> {
+    async fn next_packet(&mut self) -> Result<Option<Packet>, UnpackError> {
+        self.next().await
+    }
+}
+
+async fn read_frame<R: AsyncRead + Unpin>(
+    reader: &mut BufReader<R>,
+) -> io::Result<Option<(u64, Vec<u8>)>> {
+    let timestamp = match reader.read_u64_le().await {
+        Ok(t) => t,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let len = reader.read_u16_le().await?;
+    let mut data = vec![0; len as usize];
+    reader.read_exact(&mut data).await?;
+
+    Ok(Some((timestamp, data)))
+}
+
+/// Writes raw datagrams to a capture file from a background task, so that recording never blocks
+/// the receive loop.
+///
+/// The file is flushed every `flush_interval`, and once the recorder is dropped. Recording stops
+/// silently if the file cannot be written to, the error being returned by [`Recorder::finish`].
+pub(crate) struct Recorder {
+    tx: UnboundedSender<(u64, Vec<u8>)>,
+    writer: JoinHandle<io::Result<()>>,
+}
+
+impl Recorder {
+    pub(crate) async fn create<P: AsRef<Path>>(
+        path: P,
+        flush_interval: Duration,
+    ) -> io::Result<Recorder> {
+        if flush_interval.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The flush interval must not be zero",
+            ));
+        }
+
+        let file = File::create(path).await?;
+        let (tx, rx) = unbounded_channel();
+
+        let writer = tokio::spawn(write_frames(BufWriter::new(file), rx, flush_interval));
+
+        Ok(Recorder { tx, writer })
+    }
+
+    pub(crate) fn record(&self, data: &[u8]) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+
+        // An error means the writer task stopped, there is nothing left to do with the data
+        let _ = self.tx.send((timestamp, data.to_vec()));
+    }
+
+    /// Writes the frames recorded so far and flushes the file, returning the first error the
+    /// writer task ran into.
+    pub(crate) async fn finish(self) -> io::Result<()> {
+        drop(self.tx);
+
+        self.writer.await?
+    }
+}
+
+async fn write_frames(
+    mut writer: BufWriter<File>,
+    mut rx: UnboundedReceiver<(u64, Vec<u8>)>,
+    flush_interval: Duration,
+) -> io::Result<()> {
+    let mut flush = tokio::time::interval(flush_interval);
+
+    loop {
+        tokio::select! {
+            frame = rx.recv() => match frame {
+                Some((timestamp, data)) => write_frame(&mut writer, timestamp, &data).await?,
+                None => break,
+            },
+            _ = flush.tick() => writer.flush().await?,
+        }
+    }
+
+    writer.flush().await
+}
+
+async fn write_frame(writer: &mut BufWriter<File>, timestamp: u64, data: &[u8]) -> io::Result<()> {
+    writer.write_u64_le(timestamp).await?;
+    writer.write_u16_le(data.len() as u16).await?;
+    writer.write_all(data).await
+}
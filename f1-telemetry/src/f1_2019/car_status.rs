@@ -3,23 +3,19 @@ use std::io::BufRead;
 use serde::Deserialize;
 
 use crate::packet::car_status::*;
-use crate::packet::generic::{TyreCompound, TyreCompoundVisual, WheelData};
+use crate::packet::generic::{Flag, TyreCompound, TyreCompoundVisual, WheelData};
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize, invalid_value, CollectUnpacked};
 
 use super::consts::*;
-use super::generic::unpack_flag;
 
 fn unpack_traction_control(value: u8) -> Result<TractionControl, UnpackError> {
     match value {
         0 => Ok(TractionControl::Off),
         1 => Ok(TractionControl::Low),
         2 => Ok(TractionControl::High),
-        _ => Err(UnpackError(format!(
-            "Invalid TractionControl value: {}",
-            value
-        ))),
+        _ => invalid_value("TractionControl", value),
     }
 }
 
@@ -29,7 +25,7 @@ fn unpack_fuel_mix(value: u8) -> Result<FuelMix, UnpackError> {
         1 => Ok(FuelMix::Standard),
         2 => Ok(FuelMix::Rich),
         3 => Ok(FuelMix::Max),
-        _ => Err(UnpackError(format!("Invalid FuelMix value: {}", value))),
+        _ => invalid_value("FuelMix", value),
     }
 }
 
@@ -38,7 +34,7 @@ fn unpack_drs(value: i8) -> Result<DRS, UnpackError> {
         0 => Ok(DRS::NotAllowed),
         1 => Ok(DRS::Allowed),
         -1 => Ok(DRS::Unknown),
-        _ => Err(UnpackError(format!("Invalid DRS value: {}", value))),
+        _ => invalid_value("DRS", value),
     }
 }
 
@@ -59,10 +55,7 @@ fn unpack_tyre_compound(value: u8) -> Result<TyreCompound, UnpackError> {
         14 => Ok(TyreCompound::F2Hard),
         15 => Ok(TyreCompound::F2Wet),
         0 | 255 => Ok(TyreCompound::Invalid),
-        _ => Err(UnpackError(format!(
-            "Invalid TyreCompound value: {}",
-            value
-        ))),
+        _ => invalid_value("TyreCompound", value),
     }
 }
 
@@ -81,10 +74,7 @@ fn unpack_tyre_compound_visual(value: u8) -> Result<TyreCompoundVisual, UnpackEr
         14 => Ok(TyreCompoundVisual::F2Hard),
         15 => Ok(TyreCompoundVisual::F2Wet),
         0 => Ok(TyreCompoundVisual::Invalid),
-        _ => Err(UnpackError(format!(
-            "Invalid TyreCompoundVisual value: {}",
-            value
-        ))),
+        _ => invalid_value("TyreCompoundVisual", value),
     }
 }
 
@@ -96,10 +86,7 @@ fn unpack_ers_deploy_mode(value: u8) -> Result<ERSDeployMode, UnpackError> {
         3 => Ok(ERSDeployMode::High),
         4 => Ok(ERSDeployMode::Overtake),
         5 => Ok(ERSDeployMode::Hotlap),
-        _ => Err(UnpackError(format!(
-            "Invalid ERSDeployMode value: {}",
-            value
-        ))),
+        _ => invalid_value("ERSDeployMode", value),
     }
 }
 
@@ -188,7 +175,7 @@ impl TryFrom<&RawCarStatus> for CarStatusData {
         let drs_status = unpack_drs(packet.drs_allowed)?;
         let actual_tyre_compound = unpack_tyre_compound(packet.actual_tyre_compound)?;
         let visual_tyre_compound = unpack_tyre_compound_visual(packet.visual_tyre_compound)?;
-        let vehicle_fia_flag = unpack_flag(packet.vehicle_fia_flags)?;
+        let vehicle_fia_flag = Flag::from(packet.vehicle_fia_flags);
         let ers_deploy_mode = unpack_ers_deploy_mode(packet.ers_deploy_mode)?;
 
         Ok(CarStatusData {
@@ -231,12 +218,12 @@ pub fn parse_car_status_data<T: BufRead>(
 ) -> Result<PacketCarStatusData, UnpackError> {
     assert_packet_size(size, CAR_STATUS_PACKET_SIZE)?;
 
-    let car_status: [RawCarStatus; NUMBER_CARS] = bincode::deserialize_from(reader)?;
+    let car_status: [RawCarStatus; NUMBER_CARS] = deserialize(reader)?;
 
     let car_status_data = car_status
         .iter()
         .map(|cs| cs.try_into())
-        .collect::<Result<Vec<CarStatusData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketCarStatusData {
         header,
@@ -5,7 +5,7 @@ use serde::Deserialize;
 use crate::packet::event::*;
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::{assert_packet_size, seconds_to_millis, unpack_string};
+use crate::utils::{assert_packet_size, deserialize, seconds_to_millis, unpack_string};
 
 use super::consts::*;
 
@@ -50,43 +50,45 @@ pub(crate) fn parse_event_data<T: BufRead>(
 ) -> Result<PacketEventData, UnpackError> {
     assert_packet_size(size, EVENT_PACKET_SIZE)?;
 
-    let event: RawEvent = bincode::deserialize_from(reader)?;
+    let event: RawEvent = deserialize(reader)?;
 
-    let event_code = unpack_string(&event.event_code)?;
+    let event_code = unpack_string(&event.event_code);
 
     let event = match event_code.as_str() {
-        "SSTA" => Ok(Event::SessionStarted),
-        "SEND" => Ok(Event::SessionEnded),
+        "SSTA" => Event::SessionStarted,
+        "SEND" => Event::SessionEnded,
         "FTLP" => {
             let evt_detail = FastestLap {
                 vehicle_idx: event.vehicle_idx,
                 lap_time: seconds_to_millis(event.lap_time as f64),
             };
-            Ok(Event::FastestLap(evt_detail))
+            Event::FastestLap(evt_detail)
         }
         "RTMT" => {
             let evt_detail = Retirement {
                 vehicle_idx: event.vehicle_idx,
             };
-            Ok(Event::Retirement(evt_detail))
+            Event::Retirement(evt_detail)
         }
-        "DRSE" => Ok(Event::DRSEnabled),
-        "DRSD" => Ok(Event::DRSDisabled),
+        "DRSE" => Event::DRSEnabled,
+        "DRSD" => Event::DRSDisabled,
         "TMPT" => {
             let evt_detail = TeamMateInPits {
                 vehicle_idx: event.vehicle_idx,
             };
-            Ok(Event::TeamMateInPits(evt_detail))
+            Event::TeamMateInPits(evt_detail)
         }
-        "CHQF" => Ok(Event::ChequeredFlag),
+        "CHQF" => Event::ChequeredFlag,
         "RCWN" => {
             let evt_detail = RaceWinner {
                 vehicle_idx: event.vehicle_idx,
             };
-            Ok(Event::RaceWinner(evt_detail))
+            Event::RaceWinner(evt_detail)
         }
-        _ => Err(UnpackError(format!("Invalid Event Code: {}", event_code))),
-    }?;
+        _ => Event::Other(UnknownEvent {
+            code: event.event_code,
+        }),
+    };
 
     Ok(PacketEventData { header, event })
 }
@@ -1,17 +1,6 @@
-use crate::packet::generic::{Flag, ResultStatus};
+use crate::packet::generic::ResultStatus;
 use crate::packet::UnpackError;
-
-pub(crate) fn unpack_flag(value: i8) -> Result<Flag, UnpackError> {
-    match value {
-        0 => Ok(Flag::None),
-        1 => Ok(Flag::Green),
-        2 => Ok(Flag::Blue),
-        3 => Ok(Flag::Yellow),
-        4 => Ok(Flag::Red),
-        -1 => Ok(Flag::Invalid),
-        _ => Err(UnpackError(format!("Invalid Flag value: {}", value))),
-    }
-}
+use crate::utils::invalid_value;
 
 pub(crate) fn unpack_result_status(value: u8) -> Result<ResultStatus, UnpackError> {
     match value {
@@ -22,9 +11,6 @@ pub(crate) fn unpack_result_status(value: u8) -> Result<ResultStatus, UnpackErro
         4 => Ok(ResultStatus::Disqualified),
         5 => Ok(ResultStatus::NotClassified),
         6 => Ok(ResultStatus::Retired),
-        _ => Err(UnpackError(format!(
-            "Invalid ResultStatus value: {}",
-            value
-        ))),
+        _ => invalid_value("ResultStatus", value),
     }
 }
@@ -6,7 +6,7 @@ use crate::packet::generic::WheelData;
 use crate::packet::header::PacketHeader;
 use crate::packet::motion::{CarMotionData, PacketMotionData, PlayerCarData};
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize};
 
 use super::consts::*;
 
@@ -156,7 +156,7 @@ pub(crate) fn parse_motion_data<T: BufRead>(
 ) -> Result<PacketMotionData, UnpackError> {
     assert_packet_size(size, MOTION_PACKET_SIZE)?;
 
-    let motion_data: RawMotionData = bincode::deserialize_from(reader)?;
+    let motion_data: RawMotionData = deserialize(reader)?;
 
     let car_motion = motion_data.car_motion.iter().map(|cm| cm.into()).collect();
 
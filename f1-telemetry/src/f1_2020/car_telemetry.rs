@@ -8,7 +8,7 @@ use crate::packet::car_telemetry::{
 use crate::packet::generic::WheelData;
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize, invalid_value, CollectUnpacked};
 
 use super::consts::*;
 
@@ -27,7 +27,7 @@ fn unpack_surface_type(value: u8) -> Result<SurfaceType, UnpackError> {
         10 => Ok(SurfaceType::Metal),
         11 => Ok(SurfaceType::Ridged),
         12 => Ok(SurfaceType::Unknown),
-        _ => Err(UnpackError(format!("Invalid SurfaceType value: {}", value))),
+        _ => invalid_value("SurfaceType", value),
     }
 }
 
@@ -39,7 +39,7 @@ fn unpack_mfd_panel(value: u8) -> Result<MFDPanel, UnpackError> {
         3 => Ok(MFDPanel::Engine),
         4 => Ok(MFDPanel::Temperatures),
         255 => Ok(MFDPanel::Closed),
-        _ => Err(UnpackError(format!("Invalid MFDPanel value: {}", value))),
+        _ => invalid_value("MFDPanel", value),
     }
 }
 
@@ -193,13 +193,13 @@ pub(crate) fn parse_car_telemetry_data<T: BufRead>(
 ) -> Result<PacketCarTelemetryData, UnpackError> {
     assert_packet_size(size, CAR_TELEMETRY_PACKET_SIZE)?;
 
-    let packet: RawCarTelemetryData = bincode::deserialize_from(reader)?;
+    let packet: RawCarTelemetryData = deserialize(reader)?;
 
     let car_telemetry_data = packet
         .car_telemetry
         .iter()
         .map(|ct| ct.try_into())
-        .collect::<Result<Vec<CarTelemetryData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     let mfd_panel = unpack_mfd_panel(packet.mfd_panel_index)?;
     let secondary_player_mfd_panel = unpack_mfd_panel(packet.mfd_panel_index_secondary_player)?;
@@ -4,7 +4,7 @@ use serde::Deserialize;
 
 use crate::packet::header::PacketHeader;
 use crate::packet::{PacketType, UnpackError};
-use crate::utils::{assert_packet_at_least_size, seconds_to_millis};
+use crate::utils::{assert_packet_at_least_size, deserialize, seconds_to_millis};
 
 use super::consts::*;
 
@@ -20,7 +20,10 @@ fn parse_packet_type(value: u8) -> Result<PacketType, UnpackError> {
         7 => Ok(PacketType::CarStatus),
         8 => Ok(PacketType::FinalClassification),
         9 => Ok(PacketType::LobbyInfo),
-        _ => Err(UnpackError(format!("Invalid PacketType: {}", value))),
+        _ => Err(UnpackError::InvalidValue {
+            field: "PacketType",
+            value: value.into(),
+        }),
     }
 }
 
@@ -103,7 +106,53 @@ pub(crate) fn parse_header<T: BufRead>(
 ) -> Result<PacketHeader, UnpackError> {
     assert_packet_at_least_size(size, HEADER_SIZE)?;
 
-    let header: Header = bincode::deserialize_from(reader)?;
+    let header: Header = deserialize(reader)?;
 
     header.try_into()
 }
+
+#[cfg(test)]
+mod test_header {
+    use std::io::Cursor;
+
+    use crate::packet::PacketType;
+
+    use super::{parse_header, HEADER_SIZE};
+
+    /// Little endian and packed: the session UID is not aligned on 8 bytes
+    const HEADER: [u8; 24] = [
+        0xe4, 0x07, // packet_format: 2020
+        1, 18, // game_major_version, game_minor_version
+        1, 2, // packet_version, packet_id: lap data
+        0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01, // session_uid
+        0x00, 0x00, 0xc0, 0x3f, // session_time: 1.5 s
+        0x02, 0x01, 0x00, 0x00, // frame_identifier: 258
+        19,   // player_car_index
+        255,  // secondary_player_car_index: none
+    ];
+
+    #[test]
+    fn test_parse_header() {
+        let mut reader = Cursor::new(&HEADER[..]);
+        let header = parse_header(&mut reader, HEADER.len()).unwrap();
+
+        assert_eq!(header.packet_format, 2020);
+        assert_eq!(header.game_major_version, 1);
+        assert_eq!(header.game_minor_version, 18);
+        assert_eq!(header.packet_version, 1);
+        assert_eq!(header.packet_type, PacketType::LapData);
+        assert_eq!(header.session_uid, 0x0123_4567_89ab_cdef);
+        assert_eq!(header.session_time, 1500);
+        assert_eq!(header.frame_identifier, 258);
+        assert_eq!(header.player_car_index, 19);
+        assert_eq!(header.secondary_player_car_index, None);
+    }
+
+    #[test]
+    fn test_header_size() {
+        let mut reader = Cursor::new(&HEADER[..]);
+        parse_header(&mut reader, HEADER.len()).unwrap();
+
+        assert_eq!(reader.position() as usize, HEADER_SIZE);
+    }
+}
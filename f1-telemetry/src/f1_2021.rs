@@ -1,7 +1,6 @@
 use std::io::Cursor;
 
 use car_damage::parse_car_damage_data;
-use car_setup::parse_car_setup_data;
 use car_status::parse_car_status_data;
 use car_telemetry::parse_car_telemetry_data;
 use event::parse_event_data;
@@ -9,15 +8,16 @@ use final_classification::parse_final_classification_data;
 use header::parse_header;
 use lap::parse_lap_data;
 use lobby_info::parse_lobby_info_data;
-use motion::parse_motion_data;
 use participants::parse_participants_data;
 use session::parse_session_data;
 use session_history::parse_session_history_data;
 
+// Car setups and motion data share the F1 2020 layout.
+use crate::f1_2020::car_setup::parse_car_setup_data;
+use crate::f1_2020::motion::parse_motion_data;
 use crate::packet::{Packet, PacketType, UnpackError};
 
 mod car_damage;
-mod car_setup;
 mod car_status;
 mod car_telemetry;
 mod consts;
@@ -27,7 +27,6 @@ mod generic;
 mod header;
 mod lap;
 mod lobby_info;
-mod motion;
 mod participants;
 mod session;
 mod session_history;
@@ -97,6 +96,24 @@ pub(crate) fn parse_packet(size: usize, packet: &[u8]) -> Result<Packet, UnpackE
 
             Ok(Packet::SessionHistory(packet))
         }
-        p => Err(UnpackError(format!("Unsupported packet type: {:?}", p))),
+        p => Err(UnpackError::UnsupportedPacketType(p)),
+    }
+}
+
+/// Size in bytes of a packet of the given type, header included.
+pub(crate) const fn expected_size(packet_type: PacketType) -> Option<usize> {
+    match packet_type {
+        PacketType::Motion | PacketType::CarSetups => crate::f1_2020::expected_size(packet_type),
+        PacketType::Session => Some(consts::SESSION_PACKET_SIZE),
+        PacketType::LapData => Some(consts::LAP_DATA_PACKET_SIZE),
+        PacketType::Event => Some(consts::EVENT_PACKET_SIZE),
+        PacketType::Participants => Some(consts::PARTICIPANTS_PACKET_SIZE),
+        PacketType::CarTelemetry => Some(consts::CAR_TELEMETRY_PACKET_SIZE),
+        PacketType::CarStatus => Some(consts::CAR_STATUS_PACKET_SIZE),
+        PacketType::FinalClassification => Some(consts::FINAL_CLASSIFICATION_PACKET_SIZE),
+        PacketType::LobbyInfo => Some(consts::LOBBY_INFO_PACKET_SIZE),
+        PacketType::CarDamage => Some(consts::CAR_DAMAGE_PACKET_SIZE),
+        PacketType::SessionHistory => Some(consts::SESSION_HISTORY_PACKET_SIZE),
+        _ => None,
     }
 }
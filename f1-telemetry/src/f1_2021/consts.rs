@@ -1,19 +1,17 @@
 pub const NUMBER_CARS: usize = 22;
 pub const NUMBER_MARSHAL_ZONES: usize = 21;
-// pub const NUMBER_WEATHER_FORECASTS: usize = 56;
+pub const NUMBER_WEATHER_FORECASTS: usize = 56;
 // pub const NUMBER_LAP_HISTORY_DATA: usize = 100;
 
 pub const HEADER_SIZE: usize = 24;
 
 pub const CAR_DAMAGE_PACKET_SIZE: usize = 882;
-pub const CAR_SETUPS_PACKET_SIZE: usize = 1102;
 pub const CAR_STATUS_PACKET_SIZE: usize = 1058;
 pub const CAR_TELEMETRY_PACKET_SIZE: usize = 1347;
 pub const EVENT_PACKET_SIZE: usize = 36;
 pub const FINAL_CLASSIFICATION_PACKET_SIZE: usize = 839;
 pub const LAP_DATA_PACKET_SIZE: usize = 970;
 pub const LOBBY_INFO_PACKET_SIZE: usize = 1191;
-pub const MOTION_PACKET_SIZE: usize = 1464;
 pub const PARTICIPANTS_PACKET_SIZE: usize = 1257;
 pub const SESSION_HISTORY_PACKET_SIZE: usize = 1155;
 pub const SESSION_PACKET_SIZE: usize = 625;
@@ -4,7 +4,7 @@ use serde::Deserialize;
 
 use crate::packet::header::PacketHeader;
 use crate::packet::{PacketType, UnpackError};
-use crate::utils::{assert_packet_at_least_size, seconds_to_millis};
+use crate::utils::{assert_packet_at_least_size, deserialize, seconds_to_millis};
 
 use super::consts::*;
 
@@ -22,7 +22,10 @@ fn parse_packet_type(value: u8) -> Result<PacketType, UnpackError> {
         9 => Ok(PacketType::LobbyInfo),
         10 => Ok(PacketType::CarDamage),
         11 => Ok(PacketType::SessionHistory),
-        _ => Err(UnpackError(format!("Invalid PacketType: {}", value))),
+        _ => Err(UnpackError::InvalidValue {
+            field: "PacketType",
+            value: value.into(),
+        }),
     }
 }
 
@@ -107,7 +110,7 @@ pub(crate) fn parse_header<T: BufRead>(
 ) -> Result<PacketHeader, UnpackError> {
     assert_packet_at_least_size(size, HEADER_SIZE)?;
 
-    let header: Header = bincode::deserialize_from(reader)?;
+    let header: Header = deserialize(reader)?;
 
     header.try_into()
 }
@@ -5,7 +5,7 @@ use serde::Deserialize;
 use crate::packet::header::PacketHeader;
 use crate::packet::lap::{DriverStatus, LapData, PacketLapData, PitStatus, Sector};
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize, invalid_value, CollectUnpacked};
 
 use super::consts::*;
 use super::generic::unpack_result_status;
@@ -15,7 +15,7 @@ fn unpack_pit_status(value: u8) -> Result<PitStatus, UnpackError> {
         0 => Ok(PitStatus::None),
         1 => Ok(PitStatus::Pitting),
         2 => Ok(PitStatus::PitLane),
-        _ => Err(UnpackError(format!("Invalid PitStatus value: {}", value))),
+        _ => invalid_value("PitStatus", value),
     }
 }
 
@@ -24,7 +24,7 @@ fn unpack_sector(value: u8) -> Result<Sector, UnpackError> {
         0 => Ok(Sector::Sector1),
         1 => Ok(Sector::Sector2),
         2 => Ok(Sector::Sector3),
-        _ => Err(UnpackError(format!("Invalid Sector value: {}", value))),
+        _ => invalid_value("Sector", value),
     }
 }
 
@@ -35,10 +35,7 @@ fn unpack_driver_status(value: u8) -> Result<DriverStatus, UnpackError> {
         2 => Ok(DriverStatus::InLap),
         3 => Ok(DriverStatus::OutLap),
         4 => Ok(DriverStatus::OnTrack),
-        _ => Err(UnpackError(format!(
-            "Invalid DriverStatus value: {}",
-            value
-        ))),
+        _ => invalid_value("DriverStatus", value),
     }
 }
 
@@ -154,12 +151,9 @@ pub(crate) fn parse_lap_data<T: BufRead>(
 ) -> Result<PacketLapData, UnpackError> {
     assert_packet_size(size, LAP_DATA_PACKET_SIZE)?;
 
-    let lap_data: [RawLapData; NUMBER_CARS] = bincode::deserialize_from(reader)?;
+    let lap_data: [RawLapData; NUMBER_CARS] = deserialize(reader)?;
 
-    let lap_data = lap_data
-        .iter()
-        .map(|ld| ld.try_into())
-        .collect::<Result<Vec<LapData>, UnpackError>>()?;
+    let lap_data = lap_data.iter().map(|ld| ld.try_into()).collect_unpacked()?;
 
     Ok(PacketLapData {
         header,
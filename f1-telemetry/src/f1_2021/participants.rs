@@ -6,7 +6,9 @@ use crate::f1_2021::generic::{unpack_nationality, unpack_team};
 use crate::packet::header::PacketHeader;
 use crate::packet::participants::{Driver, PacketParticipantsData, ParticipantData, Telemetry};
 use crate::packet::UnpackError;
-use crate::utils::{assert_packet_size, unpack_string};
+use crate::utils::{
+    assert_packet_size, deserialize, invalid_value, unpack_string, CollectUnpacked,
+};
 
 use super::consts::*;
 
@@ -126,7 +128,7 @@ fn unpack_driver(value: u8) -> Result<Driver, UnpackError> {
         122 => Ok(Driver::BentViscaal),
         123 => Ok(Driver::EnzoFittipaldi),
         255 => Ok(Driver::Player),
-        _ => Err(UnpackError(format!("Invalid Driver value: {}", value))),
+        _ => invalid_value("Driver", value),
     }
 }
 
@@ -134,7 +136,7 @@ fn unpack_telemetry(value: u8) -> Result<Telemetry, UnpackError> {
     match value {
         0 => Ok(Telemetry::Restricted),
         1 => Ok(Telemetry::Public),
-        _ => Err(UnpackError(format!("Invalid Telemetry value: {}", value))),
+        _ => invalid_value("Telemetry", value),
     }
 }
 
@@ -206,7 +208,7 @@ impl TryFrom<&RawParticipant> for ParticipantData {
         let driver = unpack_driver(participant.driver_id)?;
         let team = unpack_team(participant.team_id)?;
         let nationality = unpack_nationality(participant.nationality)?;
-        let name = unpack_string(&name)?;
+        let name = unpack_string(&name);
         let telemetry_access = unpack_telemetry(participant.telemetry)?;
 
         Ok(Self {
@@ -231,12 +233,12 @@ pub(crate) fn parse_participants_data<T: BufRead>(
 ) -> Result<PacketParticipantsData, UnpackError> {
     assert_packet_size(size, PARTICIPANTS_PACKET_SIZE)?;
 
-    let participant_data: RawParticipantData = bincode::deserialize_from(reader)?;
+    let participant_data: RawParticipantData = deserialize(reader)?;
     let participants: Vec<ParticipantData> = participant_data
         .participants
         .iter()
         .map(|p| p.try_into())
-        .collect::<Result<Vec<ParticipantData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketParticipantsData {
         header,
@@ -2,14 +2,15 @@ use std::io::BufRead;
 
 use serde::Deserialize;
 
-use crate::packet::generic::SessionType;
-use crate::packet::header::PacketHeader;
+use crate::packet::generic::{Flag, SessionType};
+use crate::packet::header::{GameVersion, PacketHeader};
 use crate::packet::session::*;
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{
+    assert_count_at_most, assert_packet_size, deserialize, invalid_value, CollectUnpacked,
+};
 
 use super::consts::*;
-use super::generic::unpack_flag;
 
 fn unpack_weather(value: u8) -> Result<Weather, UnpackError> {
     match value {
@@ -19,60 +20,21 @@ fn unpack_weather(value: u8) -> Result<Weather, UnpackError> {
         3 => Ok(Weather::LightRain),
         4 => Ok(Weather::HeavyRain),
         5 => Ok(Weather::Storm),
-        _ => Err(UnpackError(format!("Invalid Weather value: {}", value))),
+        _ => invalid_value("Weather", value),
     }
 }
 
 fn unpack_session_type(value: u8) -> Result<SessionType, UnpackError> {
-    match value {
-        0 => Ok(SessionType::Unknown),
-        1 => Ok(SessionType::Practice1),
-        2 => Ok(SessionType::Practice2),
-        3 => Ok(SessionType::Practice3),
-        4 => Ok(SessionType::PracticeShort),
-        5 => Ok(SessionType::Qualifying1),
-        6 => Ok(SessionType::Qualifying2),
-        7 => Ok(SessionType::Qualifying3),
-        8 => Ok(SessionType::QualifyingShort),
-        9 => Ok(SessionType::OneShotQualifying),
-        10 => Ok(SessionType::Race),
-        11 => Ok(SessionType::Race2),
-        12 => Ok(SessionType::TimeTrial),
-        _ => Err(UnpackError(format!("Invalid SessionType value: {}", value))),
-    }
+    SessionType::from_id(value, GameVersion::F1_2021)
 }
 
+/// The highest track ID sent by F1 2021; later IDs belong to tracks added by newer games.
+const LAST_TRACK_ID: i8 = 26;
+
 fn unpack_track(value: i8) -> Result<Track, UnpackError> {
     match value {
-        0 => Ok(Track::Melbourne),
-        1 => Ok(Track::PaulRicard),
-        2 => Ok(Track::Shanghai),
-        3 => Ok(Track::Sakhir),
-        4 => Ok(Track::Catalunya),
-        5 => Ok(Track::Monaco),
-        6 => Ok(Track::Montreal),
-        7 => Ok(Track::Silverstone),
-        8 => Ok(Track::Hockenheim),
-        9 => Ok(Track::Hungaroring),
-        10 => Ok(Track::Spa),
-        11 => Ok(Track::Monza),
-        12 => Ok(Track::Singapore),
-        13 => Ok(Track::Suzuka),
-        14 => Ok(Track::AbuDhabi),
-        15 => Ok(Track::Texas),
-        16 => Ok(Track::Brazil),
-        17 => Ok(Track::Austria),
-        18 => Ok(Track::Sochi),
-        19 => Ok(Track::Mexico),
-        20 => Ok(Track::Baku),
-        21 => Ok(Track::SakhirShort),
-        22 => Ok(Track::SilverstoneShort),
-        23 => Ok(Track::TexasShort),
-        24 => Ok(Track::SuzukaShort),
-        25 => Ok(Track::Hanoi),
-        26 => Ok(Track::Zandvoort),
-        -1 => Ok(Track::Unknown),
-        _ => Err(UnpackError(format!("Invalid Track value: {}", value))),
+        -1..=LAST_TRACK_ID => Track::try_from(value as u8),
+        _ => invalid_value("Track", value),
     }
 }
 
@@ -82,7 +44,7 @@ fn unpack_formula(value: u8) -> Result<Formula, UnpackError> {
         1 => Ok(Formula::F1Classic),
         2 => Ok(Formula::F2),
         3 => Ok(Formula::F1Generic),
-        _ => Err(UnpackError(format!("Invalid Formula value: {}", value))),
+        _ => invalid_value("Formula", value),
     }
 }
 
@@ -91,7 +53,7 @@ fn unpack_safety_car(value: u8) -> Result<SafetyCar, UnpackError> {
         0 => Ok(SafetyCar::None),
         1 => Ok(SafetyCar::Full),
         2 => Ok(SafetyCar::Virtual),
-        _ => Err(UnpackError(format!("Invalid SafetyCar value: {}", value))),
+        _ => invalid_value("SafetyCar", value),
     }
 }
 
@@ -100,10 +62,7 @@ fn unpack_temperature_change(value: i8) -> Result<TemperatureChange, UnpackError
         0 => Ok(TemperatureChange::Up),
         1 => Ok(TemperatureChange::Down),
         2 => Ok(TemperatureChange::NoChange),
-        _ => Err(UnpackError(format!(
-            "Invalid TrackTemperature value: {}",
-            value
-        ))),
+        _ => invalid_value("TrackTemperature", value),
     }
 }
 
@@ -111,10 +70,7 @@ fn unpack_forecast_accuracy(value: u8) -> Result<ForecastAccuracy, UnpackError>
     match value {
         0 => Ok(ForecastAccuracy::Perfect),
         1 => Ok(ForecastAccuracy::Approximate),
-        _ => Err(UnpackError(format!(
-            "Invalid ForecastAccuracy value: {}",
-            value
-        ))),
+        _ => invalid_value("ForecastAccuracy", value),
     }
 }
 
@@ -124,10 +80,7 @@ fn unpack_braking_assist(value: u8) -> Result<BrakingAssist, UnpackError> {
         1 => Ok(BrakingAssist::Low),
         2 => Ok(BrakingAssist::Medium),
         3 => Ok(BrakingAssist::High),
-        _ => Err(UnpackError(format!(
-            "Invalid BrakingAssist value: {}",
-            value
-        ))),
+        _ => invalid_value("BrakingAssist", value),
     }
 }
 
@@ -136,10 +89,7 @@ fn unpack_gearbox_assist(value: u8) -> Result<GearboxAssist, UnpackError> {
         1 => Ok(GearboxAssist::Manual),
         2 => Ok(GearboxAssist::ManualAndSuggestedGear),
         3 => Ok(GearboxAssist::Automatic),
-        _ => Err(UnpackError(format!(
-            "Invalid GearboxAssist value: {}",
-            value
-        ))),
+        _ => invalid_value("GearboxAssist", value),
     }
 }
 
@@ -148,10 +98,7 @@ fn unpack_dynamic_racing_line(value: u8) -> Result<DynamicRacingLine, UnpackErro
         0 => Ok(DynamicRacingLine::Off),
         1 => Ok(DynamicRacingLine::CornersOnly),
         2 => Ok(DynamicRacingLine::Full),
-        _ => Err(UnpackError(format!(
-            "Invalid DynamicRacingLine value: {}",
-            value
-        ))),
+        _ => invalid_value("DynamicRacingLine", value),
     }
 }
 
@@ -159,10 +106,7 @@ fn unpack_dynamic_racing_line_type(value: u8) -> Result<DynamicRacingLineType, U
     match value {
         0 => Ok(DynamicRacingLineType::TwoDimensions),
         1 => Ok(DynamicRacingLineType::ThreeDimensions),
-        _ => Err(UnpackError(format!(
-            "Invalid DynamicRacingLineType value: {}",
-            value
-        ))),
+        _ => invalid_value("DynamicRacingLineType", value),
     }
 }
 
@@ -280,7 +224,7 @@ impl TryFrom<&RawMarshalZone> for MarshalZone {
     type Error = UnpackError;
 
     fn try_from(mz: &RawMarshalZone) -> Result<Self, Self::Error> {
-        let zone_flag = unpack_flag(mz.zone_flag)?;
+        let zone_flag = Flag::from(mz.zone_flag);
 
         Ok(Self {
             zone_start: mz.zone_start,
@@ -346,7 +290,18 @@ pub(crate) fn parse_session_data<T: BufRead>(
 ) -> Result<PacketSessionData, UnpackError> {
     assert_packet_size(size, SESSION_PACKET_SIZE)?;
 
-    let session_data: RawSessionData = bincode::deserialize_from(reader)?;
+    let session_data: RawSessionData = deserialize(reader)?;
+
+    assert_count_at_most(
+        "num_marshal_zones",
+        session_data.num_marshal_zones,
+        NUMBER_MARSHAL_ZONES,
+    )?;
+    assert_count_at_most(
+        "num_weather_forecast_samples",
+        session_data.num_weather_forecast_samples,
+        NUMBER_WEATHER_FORECASTS,
+    )?;
 
     let weather = unpack_weather(session_data.weather)?;
     let session_type = unpack_session_type(session_data.session_type)?;
@@ -356,7 +311,7 @@ pub(crate) fn parse_session_data<T: BufRead>(
         .marshal_zones
         .iter()
         .map(|mz| mz.try_into())
-        .collect::<Result<Vec<MarshalZone>, UnpackError>>()?;
+        .collect_unpacked()?;
     let safety_car_status = unpack_safety_car(session_data.safety_car_status)?;
     let forecast_accuracy = unpack_forecast_accuracy(session_data.forecast_accuracy)?;
     let braking_assist = unpack_braking_assist(session_data.braking_assist)?;
@@ -371,7 +326,7 @@ pub(crate) fn parse_session_data<T: BufRead>(
         .chain(session_data.weather_forecast_samples_2.iter())
         .take(session_data.num_weather_forecast_samples as usize)
         .map(|wf| wf.try_into())
-        .collect::<Result<Vec<WeatherForecastSample>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketSessionData {
         header,
@@ -6,7 +6,7 @@ use crate::packet::car_setup::{CarSetupData, PacketCarSetupData};
 use crate::packet::generic::WheelData;
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize};
 
 use super::consts::*;
 
@@ -110,7 +110,7 @@ pub(crate) fn parse_car_setup_data<T: BufRead>(
 ) -> Result<PacketCarSetupData, UnpackError> {
     assert_packet_size(size, CAR_SETUPS_PACKET_SIZE)?;
 
-    let car_setups: [RawCarSetup; NUMBER_CARS] = bincode::deserialize_from(reader)?;
+    let car_setups: [RawCarSetup; NUMBER_CARS] = deserialize(reader)?;
 
     let car_setups: Vec<CarSetupData> = car_setups
         .iter()
@@ -2,11 +2,12 @@ use std::io::BufRead;
 
 use serde::Deserialize;
 
-use crate::f1_2022::generic::{unpack_flag, unpack_tyre_compound, unpack_tyre_compound_visual};
+use crate::f1_2022::generic::{unpack_tyre_compound, unpack_tyre_compound_visual};
 use crate::packet::car_status::*;
+use crate::packet::generic::Flag;
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize, invalid_value, CollectUnpacked};
 
 use super::consts::*;
 
@@ -15,10 +16,7 @@ fn unpack_traction_control(value: u8) -> Result<TractionControl, UnpackError> {
         0 => Ok(TractionControl::Off),
         1 => Ok(TractionControl::Low),
         2 => Ok(TractionControl::High),
-        _ => Err(UnpackError(format!(
-            "Invalid TractionControl value: {}",
-            value
-        ))),
+        _ => invalid_value("TractionControl", value),
     }
 }
 
@@ -28,7 +26,7 @@ fn unpack_fuel_mix(value: u8) -> Result<FuelMix, UnpackError> {
         1 => Ok(FuelMix::Standard),
         2 => Ok(FuelMix::Rich),
         3 => Ok(FuelMix::Max),
-        _ => Err(UnpackError(format!("Invalid FuelMix value: {}", value))),
+        _ => invalid_value("FuelMix", value),
     }
 }
 
@@ -37,7 +35,7 @@ fn unpack_drs(value: i8) -> Result<DRS, UnpackError> {
         0 => Ok(DRS::NotAllowed),
         1 => Ok(DRS::Allowed),
         -1 => Ok(DRS::Unknown),
-        _ => Err(UnpackError(format!("Invalid DRS value: {}", value))),
+        _ => invalid_value("DRS", value),
     }
 }
 
@@ -47,10 +45,7 @@ fn unpack_ers_deploy_mode(value: u8) -> Result<ERSDeployMode, UnpackError> {
         1 => Ok(ERSDeployMode::Medium),
         2 => Ok(ERSDeployMode::Hotlap),
         3 => Ok(ERSDeployMode::Overtake),
-        _ => Err(UnpackError(format!(
-            "Invalid ERSDeployMode value: {}",
-            value
-        ))),
+        _ => invalid_value("ERSDeployMode", value),
     }
 }
 
@@ -133,7 +128,7 @@ impl TryFrom<&RawCarStatus> for CarStatusData {
         let drs_status = unpack_drs(packet.drs_allowed)?;
         let actual_tyre_compound = unpack_tyre_compound(packet.actual_tyre_compound)?;
         let visual_tyre_compound = unpack_tyre_compound_visual(packet.visual_tyre_compound)?;
-        let vehicle_fia_flag = unpack_flag(packet.vehicle_fia_flags)?;
+        let vehicle_fia_flag = Flag::from(packet.vehicle_fia_flags);
         let ers_deploy_mode = unpack_ers_deploy_mode(packet.ers_deploy_mode)?;
 
         Ok(CarStatusData {
@@ -172,12 +167,12 @@ pub fn parse_car_status_data<T: BufRead>(
 ) -> Result<PacketCarStatusData, UnpackError> {
     assert_packet_size(size, CAR_STATUS_PACKET_SIZE)?;
 
-    let car_status: [RawCarStatus; NUMBER_CARS] = bincode::deserialize_from(reader)?;
+    let car_status: [RawCarStatus; NUMBER_CARS] = deserialize(reader)?;
 
     let car_status_data = car_status
         .iter()
         .map(|cs| cs.try_into())
-        .collect::<Result<Vec<CarStatusData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketCarStatusData {
         header,
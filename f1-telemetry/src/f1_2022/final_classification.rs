@@ -6,10 +6,9 @@ use crate::f1_2022::generic::{
     unpack_result_status, unpack_tyre_compound, unpack_tyre_compound_visual,
 };
 use crate::packet::final_classification::{FinalClassification, PacketFinalClassificationData};
-use crate::packet::generic::{TyreCompound, TyreCompoundVisual};
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::{assert_packet_size, seconds_to_millis};
+use crate::utils::{assert_packet_size, deserialize, seconds_to_millis, CollectUnpacked};
 
 use super::consts::*;
 
@@ -82,13 +81,13 @@ impl TryFrom<&RawFinalClassification> for FinalClassification {
             .tyre_stints_actual
             .iter()
             .map(|&t| unpack_tyre_compound(t))
-            .collect::<Result<Vec<TyreCompound>, UnpackError>>()?;
+            .collect_unpacked()?;
 
         let tyre_stints_visual = fc
             .tyre_stints_visual
             .iter()
             .map(|&t| unpack_tyre_compound_visual(t))
-            .collect::<Result<Vec<TyreCompoundVisual>, UnpackError>>()?;
+            .collect_unpacked()?;
 
         let tyre_stints_end_lap = fc.tyre_stints_end_lap.to_vec();
 
@@ -118,13 +117,13 @@ pub(crate) fn parse_final_classification_data<T: BufRead>(
 ) -> Result<PacketFinalClassificationData, UnpackError> {
     assert_packet_size(size, FINAL_CLASSIFICATION_PACKET_SIZE)?;
 
-    let final_classification: RawFinalClassificationData = bincode::deserialize_from(reader)?;
+    let final_classification: RawFinalClassificationData = deserialize(reader)?;
 
     let final_classifications = final_classification
         .final_classifications
         .iter()
         .map(|fc| fc.try_into())
-        .collect::<Result<Vec<FinalClassification>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketFinalClassificationData {
         header,
@@ -1,19 +1,6 @@
-use crate::packet::generic::{
-    Flag, Nationality, ResultStatus, Team, TyreCompound, TyreCompoundVisual,
-};
+use crate::packet::generic::{Nationality, ResultStatus, Team, TyreCompound, TyreCompoundVisual};
 use crate::packet::UnpackError;
-
-pub(crate) fn unpack_flag(value: i8) -> Result<Flag, UnpackError> {
-    match value {
-        0 => Ok(Flag::None),
-        1 => Ok(Flag::Green),
-        2 => Ok(Flag::Blue),
-        3 => Ok(Flag::Yellow),
-        4 => Ok(Flag::Red),
-        -1 => Ok(Flag::Invalid),
-        _ => Err(UnpackError(format!("Invalid Flag value: {}", value))),
-    }
-}
+use crate::utils::invalid_value;
 
 pub(crate) fn unpack_nationality(value: u8) -> Result<Nationality, UnpackError> {
     match value {
@@ -105,7 +92,7 @@ pub(crate) fn unpack_nationality(value: u8) -> Result<Nationality, UnpackError>
         86 => Ok(Nationality::Welsh),
         87 => Ok(Nationality::Vietnamese),
         0 | 255 => Ok(Nationality::Invalid),
-        _ => Err(UnpackError(format!("Invalid Nationality value: {}", value))),
+        _ => invalid_value("Nationality", value),
     }
 }
 
@@ -154,7 +141,7 @@ pub(crate) fn unpack_team(value: u8) -> Result<Team, UnpackError> {
         116 => Ok(Team::Trident2021),
         117 => Ok(Team::MercedesAMGGTBlackSeries),
         255 => Ok(Team::MyTeam),
-        _ => Err(UnpackError(format!("Invalid Team value: {}", value))),
+        _ => invalid_value("Team", value),
     }
 }
 
@@ -168,10 +155,7 @@ pub(crate) fn unpack_result_status(value: u8) -> Result<ResultStatus, UnpackErro
         5 => Ok(ResultStatus::Disqualified),
         6 => Ok(ResultStatus::NotClassified),
         7 => Ok(ResultStatus::Retired),
-        _ => Err(UnpackError(format!(
-            "Invalid ResultStatus value: {}",
-            value
-        ))),
+        _ => invalid_value("ResultStatus", value),
     }
 }
 
@@ -192,10 +176,7 @@ pub(crate) fn unpack_tyre_compound(value: u8) -> Result<TyreCompound, UnpackErro
         14 => Ok(TyreCompound::F2Hard),
         15 => Ok(TyreCompound::F2Wet),
         0 | 255 => Ok(TyreCompound::Invalid),
-        _ => Err(UnpackError(format!(
-            "Invalid TyreCompound value: {}",
-            value
-        ))),
+        _ => invalid_value("TyreCompound", value),
     }
 }
 
@@ -214,9 +195,6 @@ pub(crate) fn unpack_tyre_compound_visual(value: u8) -> Result<TyreCompoundVisua
         21 => Ok(TyreCompoundVisual::F2Medium),
         22 => Ok(TyreCompoundVisual::F2Hard),
         0 => Ok(TyreCompoundVisual::Invalid),
-        _ => Err(UnpackError(format!(
-            "Invalid TyreCompoundVisual value: {}",
-            value
-        ))),
+        _ => invalid_value("TyreCompoundVisual", value),
     }
 }
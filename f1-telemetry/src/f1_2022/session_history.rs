@@ -6,7 +6,7 @@ use crate::f1_2022::generic::{unpack_tyre_compound, unpack_tyre_compound_visual}
 use crate::packet::header::PacketHeader;
 use crate::packet::session_history::{LapHistoryData, PacketSessionHistoryData, TyreStintData};
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize, CollectUnpacked};
 
 use super::consts::*;
 
@@ -124,7 +124,7 @@ pub(crate) fn parse_session_history_data<T: BufRead>(
 ) -> Result<PacketSessionHistoryData, UnpackError> {
     assert_packet_size(size, SESSION_HISTORY_PACKET_SIZE)?;
 
-    let session_history_data: RawSessionHistoryData = bincode::deserialize_from(reader)?;
+    let session_history_data: RawSessionHistoryData = deserialize(reader)?;
 
     let lap_history = session_history_data
         .lap_history_1
@@ -133,13 +133,13 @@ pub(crate) fn parse_session_history_data<T: BufRead>(
         .chain(session_history_data.lap_history_3.iter())
         .chain(session_history_data.lap_history_4.iter())
         .map(|lh| lh.try_into())
-        .collect::<Result<Vec<LapHistoryData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     let tyre_stints: Vec<TyreStintData> = session_history_data
         .tyre_stints
         .iter()
         .map(|ts| ts.try_into())
-        .collect::<Result<Vec<TyreStintData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketSessionHistoryData {
         header,
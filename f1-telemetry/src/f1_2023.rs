@@ -114,3 +114,23 @@ pub(crate) fn parse_packet(size: usize, packet: &[u8]) -> Result<Packet, UnpackE
         }
     }
 }
+
+/// Size in bytes of a packet of the given type, header included.
+pub(crate) const fn expected_size(packet_type: PacketType) -> Option<usize> {
+    match packet_type {
+        PacketType::Motion => Some(consts::MOTION_PACKET_SIZE),
+        PacketType::Session => Some(consts::SESSION_PACKET_SIZE),
+        PacketType::LapData => Some(consts::LAP_DATA_PACKET_SIZE),
+        PacketType::Event => Some(consts::EVENT_PACKET_SIZE),
+        PacketType::Participants => Some(consts::PARTICIPANTS_PACKET_SIZE),
+        PacketType::CarSetups => Some(consts::CAR_SETUPS_PACKET_SIZE),
+        PacketType::CarTelemetry => Some(consts::CAR_TELEMETRY_PACKET_SIZE),
+        PacketType::CarStatus => Some(consts::CAR_STATUS_PACKET_SIZE),
+        PacketType::FinalClassification => Some(consts::FINAL_CLASSIFICATION_PACKET_SIZE),
+        PacketType::LobbyInfo => Some(consts::LOBBY_INFO_PACKET_SIZE),
+        PacketType::CarDamage => Some(consts::CAR_DAMAGE_PACKET_SIZE),
+        PacketType::SessionHistory => Some(consts::SESSION_HISTORY_PACKET_SIZE),
+        PacketType::TyreSets => Some(consts::TYRE_SETS_PACKET_SIZE),
+        PacketType::MotionEx => Some(consts::MOTION_EX_PACKET_SIZE),
+    }
+}
@@ -6,7 +6,7 @@ use crate::packet::car_damage::*;
 use crate::packet::generic::WheelData;
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize, CollectUnpacked};
 
 use super::consts::*;
 
@@ -102,12 +102,12 @@ pub fn parse_car_damage_data<T: BufRead>(
 ) -> Result<PacketCarDamageData, UnpackError> {
     assert_packet_size(size, CAR_DAMAGE_PACKET_SIZE)?;
 
-    let car_damage: [RawCarDamage; NUMBER_CARS] = bincode::deserialize_from(reader)?;
+    let car_damage: [RawCarDamage; NUMBER_CARS] = deserialize(reader)?;
 
     let car_damage_data = car_damage
         .iter()
         .map(|cd| cd.try_into())
-        .collect::<Result<Vec<CarDamageData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketCarDamageData {
         header,
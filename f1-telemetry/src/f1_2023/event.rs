@@ -5,98 +5,10 @@ use serde::Deserialize;
 use crate::packet::event::*;
 use crate::packet::header::PacketHeader;
 use crate::packet::UnpackError;
-use crate::utils::{assert_packet_size, seconds_to_millis, unpack_string};
+use crate::utils::{assert_packet_size, deserialize, seconds_to_millis, unpack_string};
 
 use super::consts::*;
 
-fn unpack_penalty_type(value: u8) -> Result<PenaltyType, UnpackError> {
-    match value {
-        0 => Ok(PenaltyType::DriveThrough),
-        1 => Ok(PenaltyType::StopGo),
-        2 => Ok(PenaltyType::GridPenalty),
-        3 => Ok(PenaltyType::PenaltyReminder),
-        4 => Ok(PenaltyType::TimePenalty),
-        5 => Ok(PenaltyType::Warning),
-        6 => Ok(PenaltyType::Disqualified),
-        7 => Ok(PenaltyType::RemovedFromFormationLap),
-        8 => Ok(PenaltyType::ParkedTooLongTimer),
-        9 => Ok(PenaltyType::TyreRegulations),
-        10 => Ok(PenaltyType::ThisLapInvalidated),
-        11 => Ok(PenaltyType::ThisAndNextLapInvalidated),
-        12 => Ok(PenaltyType::ThisLapInvalidatedWithoutReason),
-        13 => Ok(PenaltyType::ThisAndNextLapInvalidatedWithoutReason),
-        14 => Ok(PenaltyType::ThisAndPreviousLapInvalidated),
-        15 => Ok(PenaltyType::ThisAndPreviousLapInvalidatedWithoutReason),
-        16 => Ok(PenaltyType::Retired),
-        17 => Ok(PenaltyType::BlackFlagTimer),
-        _ => Err(UnpackError(format!("Invalid PenaltyType value: {}", value))),
-    }
-}
-
-fn unpack_infringement_type(value: u8) -> Result<InfringementType, UnpackError> {
-    match value {
-        0 => Ok(InfringementType::BlockingBySlowDriving),
-        1 => Ok(InfringementType::BlockingByWrongWayDriving),
-        2 => Ok(InfringementType::ReversingOffTheStartLine),
-        3 => Ok(InfringementType::BigCollision),
-        4 => Ok(InfringementType::SmallCollision),
-        5 => Ok(InfringementType::CollisionFailedToHandBackPositionSingle),
-        6 => Ok(InfringementType::CollisionFailedToHandBackPositionMultiple),
-        7 => Ok(InfringementType::CornerCuttingGainedTime),
-        8 => Ok(InfringementType::CornerCuttingOvertakeSingle),
-        9 => Ok(InfringementType::CornerCuttingOvertakeMultiple),
-        10 => Ok(InfringementType::CrossedPitExitLane),
-        11 => Ok(InfringementType::IgnoringBlueFlags),
-        12 => Ok(InfringementType::IgnoringYellowFlags),
-        13 => Ok(InfringementType::IgnoringDriveThrough),
-        14 => Ok(InfringementType::TooManyDriveThroughs),
-        15 => Ok(InfringementType::DriveThroughReminderServeWithinNLaps),
-        16 => Ok(InfringementType::DriveThroughReminderServeThisLap),
-        17 => Ok(InfringementType::PitLaneSpeeding),
-        18 => Ok(InfringementType::ParkedForTooLong),
-        19 => Ok(InfringementType::IgnoringTyreRegulations),
-        20 => Ok(InfringementType::TooManyPenalties),
-        21 => Ok(InfringementType::MultipleWarnings),
-        22 => Ok(InfringementType::ApproachingDisqualification),
-        23 => Ok(InfringementType::TyreRegulationsSelectSingle),
-        24 => Ok(InfringementType::TyreRegulationsSelectMultiple),
-        25 => Ok(InfringementType::LapInvalidatedCornerCutting),
-        26 => Ok(InfringementType::LapInvalidatedRunningWide),
-        27 => Ok(InfringementType::CornerCuttingRanWideGainedTimeMinor),
-        28 => Ok(InfringementType::CornerCuttingRanWideGainedTimeSignificant),
-        29 => Ok(InfringementType::CornerCuttingRanWideGainedTimeExtreme),
-        30 => Ok(InfringementType::LapInvalidatedWallRiding),
-        31 => Ok(InfringementType::LapInvalidatedFlashbackUsed),
-        32 => Ok(InfringementType::LapInvalidatedResetToTrack),
-        33 => Ok(InfringementType::BlockingThePitlane),
-        34 => Ok(InfringementType::JumpStart),
-        35 => Ok(InfringementType::SafetyCarToCarCollision),
-        36 => Ok(InfringementType::SafetyCarIllegalOvertake),
-        37 => Ok(InfringementType::SafetyCarExceedingAllowedPace),
-        38 => Ok(InfringementType::VirtualSafetyCarExceedingAllowedPace),
-        39 => Ok(InfringementType::FormationLapBelowAllowedSpeed),
-        40 => Ok(InfringementType::FormationLapParking),
-        41 => Ok(InfringementType::RetiredMechanicalFailure),
-        42 => Ok(InfringementType::RetiredTerminallyDamaged),
-        43 => Ok(InfringementType::SafetyCarFallingTooFarBack),
-        44 => Ok(InfringementType::BlackFlagTimer),
-        45 => Ok(InfringementType::UnservedStopGoPenalty),
-        46 => Ok(InfringementType::UnservedDriveThroughPenalty),
-        47 => Ok(InfringementType::EngineComponentChange),
-        48 => Ok(InfringementType::GearboxChange),
-        49 => Ok(InfringementType::ParcFermeChange),
-        50 => Ok(InfringementType::LeagueGridPenalty),
-        51 => Ok(InfringementType::RetryPenalty),
-        52 => Ok(InfringementType::IllegalTimeGain),
-        53 => Ok(InfringementType::MandatoryPitstop),
-        54 => Ok(InfringementType::AttributeAssigned),
-        _ => Err(UnpackError(format!(
-            "Invalid InfringementType value: {}",
-            value
-        ))),
-    }
-}
-
 /// This packet gives details of events that happen during the course of a session.
 ///
 /// Frequency: When the event occurs
@@ -131,6 +43,7 @@ fn unpack_infringement_type(value: u8) -> Result<InfringementType, UnpackError>
 /// Stop go served          SGSV    Stop go penalty served
 /// Flashback               FLBK    Flashback activated
 /// Button status           BUTN    Button status changed
+/// Red flag                RDFL    Red flag shown
 /// Overtake                OVTK    Overtake occurred
 /// ```
 #[derive(Deserialize)]
@@ -360,60 +273,60 @@ struct OvertakeDetails {
 }
 
 pub(crate) fn parse_event_data<T: BufRead>(
-    mut reader: &mut T,
+    reader: &mut T,
     header: PacketHeader,
     size: usize,
 ) -> Result<PacketEventData, UnpackError> {
     assert_packet_size(size, EVENT_PACKET_SIZE)?;
 
-    let event: RawEvent = bincode::deserialize_from(&mut reader)?;
+    let event: RawEvent = deserialize(reader)?;
 
-    let event_code = unpack_string(&event.event_code)?;
+    let event_code = unpack_string(&event.event_code);
 
     let event = match event_code.as_str() {
-        "SSTA" => Ok(Event::SessionStarted),
-        "SEND" => Ok(Event::SessionEnded),
+        "SSTA" => Event::SessionStarted,
+        "SEND" => Event::SessionEnded,
         "FTLP" => {
-            let details: FastestLapDetails = bincode::deserialize_from(reader)?;
+            let details: FastestLapDetails = deserialize(reader)?;
 
             let evt_detail = FastestLap {
                 vehicle_idx: details.vehicle_idx,
                 lap_time: seconds_to_millis(details.lap_time as f64),
             };
-            Ok(Event::FastestLap(evt_detail))
+            Event::FastestLap(evt_detail)
         }
         "RTMT" => {
-            let details: RetirementDetails = bincode::deserialize_from(reader)?;
+            let details: RetirementDetails = deserialize(reader)?;
 
             let evt_detail = Retirement {
                 vehicle_idx: details.vehicle_idx,
             };
-            Ok(Event::Retirement(evt_detail))
+            Event::Retirement(evt_detail)
         }
-        "DRSE" => Ok(Event::DRSEnabled),
-        "DRSD" => Ok(Event::DRSDisabled),
+        "DRSE" => Event::DRSEnabled,
+        "DRSD" => Event::DRSDisabled,
         "TMPT" => {
-            let details: TeamMateInPitsDetails = bincode::deserialize_from(reader)?;
+            let details: TeamMateInPitsDetails = deserialize(reader)?;
 
             let evt_detail = TeamMateInPits {
                 vehicle_idx: details.vehicle_idx,
             };
-            Ok(Event::TeamMateInPits(evt_detail))
+            Event::TeamMateInPits(evt_detail)
         }
-        "CHQF" => Ok(Event::ChequeredFlag),
+        "CHQF" => Event::ChequeredFlag,
         "RCWN" => {
-            let details: RaceWinnerDetails = bincode::deserialize_from(reader)?;
+            let details: RaceWinnerDetails = deserialize(reader)?;
 
             let evt_detail = RaceWinner {
                 vehicle_idx: details.vehicle_idx,
             };
-            Ok(Event::RaceWinner(evt_detail))
+            Event::RaceWinner(evt_detail)
         }
         "PENA" => {
-            let details: PenaltyDetails = bincode::deserialize_from(reader)?;
+            let details: PenaltyDetails = deserialize(reader)?;
 
-            let penalty_type = unpack_penalty_type(details.penalty_type)?;
-            let infringement_type = unpack_infringement_type(details.infringement_type)?;
+            let penalty_type = PenaltyType::from(details.penalty_type);
+            let infringement_type = InfringementType::from(details.infringement_type);
 
             let evt_detail = Penalty {
                 vehicle_idx: details.vehicle_idx,
@@ -424,10 +337,10 @@ pub(crate) fn parse_event_data<T: BufRead>(
                 lap_num: details.lap_num,
                 places_gained: details.places_gained,
             };
-            Ok(Event::Penalty(evt_detail))
+            Event::Penalty(evt_detail)
         }
         "SPTP" => {
-            let details: SpeedTrapDetails = bincode::deserialize_from(reader)?;
+            let details: SpeedTrapDetails = deserialize(reader)?;
 
             let evt_detail = SpeedTrap {
                 vehicle_idx: details.vehicle_idx,
@@ -437,61 +350,64 @@ pub(crate) fn parse_event_data<T: BufRead>(
                 fastest_vehicle_idx_in_session: Some(details.fastest_vehicle_idx_in_session),
                 fastest_speed_in_session: Some(details.fastest_speed_in_session),
             };
-            Ok(Event::SpeedTrap(evt_detail))
+            Event::SpeedTrap(evt_detail)
         }
         "STLG" => {
-            let details: StartLightsDetails = bincode::deserialize_from(reader)?;
+            let details: StartLightsDetails = deserialize(reader)?;
 
             let evt_detail = StartLights {
                 number_of_lights: details.number_of_lights,
             };
-            Ok(Event::StartLights(evt_detail))
+            Event::StartLights(evt_detail)
         }
-        "LGOT" => Ok(Event::LightsOut),
+        "LGOT" => Event::LightsOut,
         "DTSV" => {
-            let details: DriveThroughPenaltyServedDetails = bincode::deserialize_from(reader)?;
+            let details: DriveThroughPenaltyServedDetails = deserialize(reader)?;
 
             let evt_detail = DriveThroughPenaltyServed {
                 vehicle_idx: details.vehicle_idx,
             };
-            Ok(Event::DriveThroughPenaltyServed(evt_detail))
+            Event::DriveThroughPenaltyServed(evt_detail)
         }
         "SGSV" => {
-            let details: StopGoPenaltyServedDetails = bincode::deserialize_from(reader)?;
+            let details: StopGoPenaltyServedDetails = deserialize(reader)?;
 
             let evt_detail = StopGoPenaltyServed {
                 vehicle_idx: details.vehicle_idx,
             };
-            Ok(Event::StopGoPenaltyServed(evt_detail))
+            Event::StopGoPenaltyServed(evt_detail)
         }
         "FLBK" => {
-            let details: FlashbackDetails = bincode::deserialize_from(reader)?;
+            let details: FlashbackDetails = deserialize(reader)?;
 
             let evt_detail = Flashback {
                 frame_identifier: details.frame_identifier,
                 session_time: details.session_time,
             };
-            Ok(Event::Flashback(evt_detail))
+            Event::Flashback(evt_detail)
         }
         "BUTN" => {
-            let details: ButtonsDetails = bincode::deserialize_from(reader)?;
+            let details: ButtonsDetails = deserialize(reader)?;
 
             let evt_detail = Buttons {
                 button_status: details.button_status,
             };
-            Ok(Event::Buttons(evt_detail))
+            Event::Buttons(evt_detail)
         }
+        "RDFL" => Event::RedFlag,
         "OVTK" => {
-            let details: OvertakeDetails = bincode::deserialize_from(reader)?;
+            let details: OvertakeDetails = deserialize(reader)?;
 
             let evt_detail = Overtake {
                 overtaking_vehicle_idx: details.overtaking_vehicle_idx,
                 being_overtaken_vehicle_idx: details.being_overtaken_vehicle_idx,
             };
-            Ok(Event::Overtake(evt_detail))
+            Event::Overtake(evt_detail)
         }
-        _ => Err(UnpackError(format!("Invalid Event Code: {}", event_code))),
-    }?;
+        _ => Event::Other(UnknownEvent {
+            code: event.event_code,
+        }),
+    };
 
     Ok(PacketEventData { header, event })
 }
@@ -1,18 +1,8 @@
 use crate::packet::generic::{
-    Flag, Nationality, Platform, ResultStatus, SessionType, Team, TyreCompound, TyreCompoundVisual,
+    Nationality, Platform, ResultStatus, SessionType, Team, TyreCompound, TyreCompoundVisual,
 };
 use crate::packet::UnpackError;
-
-pub(crate) fn unpack_flag(value: i8) -> Result<Flag, UnpackError> {
-    match value {
-        0 => Ok(Flag::None),
-        1 => Ok(Flag::Green),
-        2 => Ok(Flag::Blue),
-        3 => Ok(Flag::Yellow),
-        -1 => Ok(Flag::Invalid),
-        _ => Err(UnpackError(format!("Invalid Flag value: {}", value))),
-    }
-}
+use crate::utils::invalid_value;
 
 pub(crate) fn unpack_nationality(value: u8) -> Result<Nationality, UnpackError> {
     match value {
@@ -104,7 +94,7 @@ pub(crate) fn unpack_nationality(value: u8) -> Result<Nationality, UnpackError>
         86 => Ok(Nationality::Welsh),
         87 => Ok(Nationality::Vietnamese),
         0 | 255 => Ok(Nationality::Invalid),
-        _ => Err(UnpackError(format!("Invalid Nationality value: {}", value))),
+        _ => invalid_value("Nationality", value),
     }
 }
 
@@ -188,7 +178,7 @@ pub(crate) fn unpack_team(value: u8) -> Result<Team, UnpackError> {
         139 => Ok(Team::Hitech2022),
         140 => Ok(Team::ArtGP2022),
         255 => Ok(Team::MyTeam),
-        _ => Err(UnpackError(format!("Invalid Team value: {}", value))),
+        _ => invalid_value("Team", value),
     }
 }
 
@@ -199,7 +189,7 @@ pub(crate) fn unpack_platform(value: u8) -> Result<Platform, UnpackError> {
         4 => Ok(Platform::Xbox),
         6 => Ok(Platform::Origin),
         0 | 255 => Ok(Platform::Unknown),
-        _ => Err(UnpackError(format!("Invalid Platform value: {}", value))),
+        _ => invalid_value("Platform", value),
     }
 }
 
@@ -213,10 +203,7 @@ pub(crate) fn unpack_result_status(value: u8) -> Result<ResultStatus, UnpackErro
         5 => Ok(ResultStatus::Disqualified),
         6 => Ok(ResultStatus::NotClassified),
         7 => Ok(ResultStatus::Retired),
-        _ => Err(UnpackError(format!(
-            "Invalid ResultStatus value: {}",
-            value
-        ))),
+        _ => invalid_value("ResultStatus", value),
     }
 }
 
@@ -238,10 +225,7 @@ pub(crate) fn unpack_tyre_compound(value: u8) -> Result<TyreCompound, UnpackErro
         14 => Ok(TyreCompound::F2Hard),
         15 => Ok(TyreCompound::F2Wet),
         0 | 255 => Ok(TyreCompound::Invalid),
-        _ => Err(UnpackError(format!(
-            "Invalid TyreCompound value: {}",
-            value
-        ))),
+        _ => invalid_value("TyreCompound", value),
     }
 }
 
@@ -260,29 +244,10 @@ pub(crate) fn unpack_tyre_compound_visual(value: u8) -> Result<TyreCompoundVisua
         21 => Ok(TyreCompoundVisual::F2Medium),
         22 => Ok(TyreCompoundVisual::F2Hard),
         0 => Ok(TyreCompoundVisual::Invalid),
-        _ => Err(UnpackError(format!(
-            "Invalid TyreCompoundVisual value: {}",
-            value
-        ))),
+        _ => invalid_value("TyreCompoundVisual", value),
     }
 }
 
 pub(crate) fn unpack_session_type(value: u8) -> Result<SessionType, UnpackError> {
-    match value {
-        0 => Ok(SessionType::Unknown),
-        1 => Ok(SessionType::Practice1),
-        2 => Ok(SessionType::Practice2),
-        3 => Ok(SessionType::Practice3),
-        4 => Ok(SessionType::PracticeShort),
-        5 => Ok(SessionType::Qualifying1),
-        6 => Ok(SessionType::Qualifying2),
-        7 => Ok(SessionType::Qualifying3),
-        8 => Ok(SessionType::QualifyingShort),
-        9 => Ok(SessionType::OneShotQualifying),
-        10 => Ok(SessionType::Race),
-        11 => Ok(SessionType::Race2),
-        12 => Ok(SessionType::Race3),
-        13 => Ok(SessionType::TimeTrial),
-        _ => Err(UnpackError(format!("Invalid SessionType value: {}", value))),
-    }
+    SessionType::try_from(value)
 }
@@ -4,28 +4,12 @@ use serde::Deserialize;
 
 use crate::packet::header::PacketHeader;
 use crate::packet::{PacketType, UnpackError};
-use crate::utils::{assert_packet_at_least_size, seconds_to_millis};
+use crate::utils::{assert_packet_at_least_size, deserialize, seconds_to_millis};
 
 use super::consts::*;
 
 fn parse_packet_type(value: u8) -> Result<PacketType, UnpackError> {
-    match value {
-        0 => Ok(PacketType::Motion),
-        1 => Ok(PacketType::Session),
-        2 => Ok(PacketType::LapData),
-        3 => Ok(PacketType::Event),
-        4 => Ok(PacketType::Participants),
-        5 => Ok(PacketType::CarSetups),
-        6 => Ok(PacketType::CarTelemetry),
-        7 => Ok(PacketType::CarStatus),
-        8 => Ok(PacketType::FinalClassification),
-        9 => Ok(PacketType::LobbyInfo),
-        10 => Ok(PacketType::CarDamage),
-        11 => Ok(PacketType::SessionHistory),
-        12 => Ok(PacketType::TyreSets),
-        13 => Ok(PacketType::MotionEx),
-        _ => Err(UnpackError(format!("Invalid PacketType: {}", value))),
-    }
+    PacketType::try_from(value)
 }
 
 /// The header for each of the UDP telemetry packets.
@@ -116,7 +100,7 @@ pub(crate) fn parse_header<T: BufRead>(
 ) -> Result<PacketHeader, UnpackError> {
     assert_packet_at_least_size(size, HEADER_SIZE)?;
 
-    let header: Header = bincode::deserialize_from(reader)?;
+    let header: Header = deserialize(reader)?;
 
     header.try_into()
 }
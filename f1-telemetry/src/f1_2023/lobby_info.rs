@@ -5,7 +5,9 @@ use serde::Deserialize;
 use crate::packet::header::PacketHeader;
 use crate::packet::lobby_info::{PacketLobbyInfoData, Player, ReadyStatus};
 use crate::packet::UnpackError;
-use crate::utils::{assert_packet_size, unpack_string};
+use crate::utils::{
+    assert_packet_size, deserialize, invalid_value, unpack_string, CollectUnpacked,
+};
 
 use super::consts::*;
 use super::generic::{unpack_nationality, unpack_platform, unpack_team};
@@ -15,7 +17,7 @@ fn unpack_ready_status(value: u8) -> Result<ReadyStatus, UnpackError> {
         0 => Ok(ReadyStatus::NotReady),
         1 => Ok(ReadyStatus::Ready),
         2 => Ok(ReadyStatus::Spectating),
-        _ => Err(UnpackError(format!("Invalid ReadyStatus value: {}", value))),
+        _ => invalid_value("ReadyStatus", value),
     }
 }
 
@@ -79,7 +81,7 @@ impl TryFrom<&RawPlayer> for Player {
         let team = unpack_team(player.team_id)?;
         let nationality = unpack_nationality(player.nationality)?;
         let platform = unpack_platform(player.platform)?;
-        let name = unpack_string(&name)?;
+        let name = unpack_string(&name);
         let ready_status = unpack_ready_status(player.ready_status)?;
 
         Ok(Self {
@@ -101,13 +103,13 @@ pub(crate) fn parse_lobby_info_data<T: BufRead>(
 ) -> Result<PacketLobbyInfoData, UnpackError> {
     assert_packet_size(size, LOBBY_INFO_PACKET_SIZE)?;
 
-    let lobby_info: RawLobbyInfo = bincode::deserialize_from(reader)?;
+    let lobby_info: RawLobbyInfo = deserialize(reader)?;
 
     let players = lobby_info
         .players
         .iter()
         .map(|p| p.try_into())
-        .collect::<Result<Vec<Player>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketLobbyInfoData {
         header,
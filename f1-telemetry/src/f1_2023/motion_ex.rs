@@ -6,7 +6,7 @@ use crate::packet::generic::WheelData;
 use crate::packet::header::PacketHeader;
 use crate::packet::motion_ex::PacketMotionExData;
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize};
 
 use super::consts::*;
 
@@ -72,7 +72,7 @@ pub(crate) fn parse_motion_ex_data<T: BufRead>(
 ) -> Result<PacketMotionExData, UnpackError> {
     assert_packet_size(size, MOTION_EX_PACKET_SIZE)?;
 
-    let motion_data: RawMotionExData = bincode::deserialize_from(reader)?;
+    let motion_data: RawMotionExData = deserialize(reader)?;
 
     Ok(PacketMotionExData {
         header,
@@ -5,7 +5,7 @@ use serde::Deserialize;
 use crate::packet::header::PacketHeader;
 use crate::packet::tyre_sets::{PacketTyreSetsData, TyreSetData};
 use crate::packet::UnpackError;
-use crate::utils::assert_packet_size;
+use crate::utils::{assert_packet_size, deserialize, CollectUnpacked};
 
 use super::consts::*;
 use super::generic::{unpack_session_type, unpack_tyre_compound, unpack_tyre_compound_visual};
@@ -83,12 +83,12 @@ pub(crate) fn parse_tyre_sets_data<T: BufRead>(
 ) -> Result<PacketTyreSetsData, UnpackError> {
     assert_packet_size(size, TYRE_SETS_PACKET_SIZE)?;
 
-    let tyre_set_data: RawTyreSetsData = bincode::deserialize_from(reader)?;
+    let tyre_set_data: RawTyreSetsData = deserialize(reader)?;
     let lap_history: Vec<TyreSetData> = tyre_set_data
         .lap_history
         .iter()
         .map(|l| l.try_into())
-        .collect::<Result<Vec<TyreSetData>, UnpackError>>()?;
+        .collect_unpacked()?;
 
     Ok(PacketTyreSetsData {
         header,
@@ -0,0 +1,153 @@
+//! Per-frame records combining the motion, telemetry and status of a car, e.g. to produce one row
+//! of training data per frame.
+//!
+//! The motion, car telemetry and car status packets of a frame are sent separately, with the same
+//! [`frame_identifier`](crate::packet::header::PacketHeader::frame_identifier). [`FrameAssembler`]
+//! keeps the latest packet of each type and combines them once all three are from the same frame,
+//! and [`FrameSource`] does the same over a [`PacketSource`].
+//!
+//! ## Packet frequencies
+//!
+//! The three packets are sent at the rate set in the telemetry settings, so a full set is normally
+//! available for every frame. When one of them is sent less often, e.g. the car status in some
+//! games, frames are only emitted at the rate of the slowest packet: the frames missing one of the
+//! packets are skipped rather than combined with data of another frame. Only the latest packet of
+//! each type is kept, so a frame is also skipped if its packets are interleaved with those of the
+//! next frame.
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+
+use crate::packet::car_status::{CarStatusData, PacketCarStatusData};
+use crate::packet::car_telemetry::{CarTelemetryData, PacketCarTelemetryData};
+use crate::packet::header::PacketHeader;
+use crate::packet::motion::{CarMotionData, PacketMotionData};
+use crate::packet::{Packet, UnpackError};
+use crate::source::PacketSource;
+
+/// Motion, telemetry and status of a car on a single frame
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CarFrame {
+    pub session_uid: u64,
+    pub frame_identifier: u32,
+    /// Session timestamp of the motion packet, in milliseconds
+    pub session_time: u32,
+    pub car_index: u8,
+    /// Position, velocity and g-forces
+    pub motion: CarMotionData,
+    /// Speed, inputs and gear
+    pub telemetry: CarTelemetryData,
+    /// Tyres, fuel and energy
+    pub status: CarStatusData,
+}
+
+/// Combines the motion, car telemetry and car status packets of a frame into a [`CarFrame`] for
+/// every car. See the [module documentation](self) for the frames that are skipped.
+#[derive(Debug, Default)]
+pub struct FrameAssembler {
+    motion: Option<PacketMotionData>,
+    telemetry: Option<PacketCarTelemetryData>,
+    status: Option<PacketCarStatusData>,
+    /// Session and frame identifier of the last frame emitted
+    last_frame: Option<(u64, u32)>,
+}
+
+impl FrameAssembler {
+    pub fn new() -> FrameAssembler {
+        FrameAssembler::default()
+    }
+
+    /// Keeps the packet if it is one of the combined types, and returns the frames of all cars
+    /// once the motion, telemetry and status of a frame have been received. Returns an empty list
+    /// otherwise.
+    pub fn observe(&mut self, packet: &Packet) -> Vec<CarFrame> {
+        match packet {
+            Packet::Motion(p) => self.motion = Some(p.clone()),
+            Packet::CarTelemetry(p) => self.telemetry = Some(p.clone()),
+            Packet::CarStatus(p) => self.status = Some(p.clone()),
+            _ => return Vec::new(),
+        }
+
+        let (Some(motion), Some(telemetry), Some(status)) =
+            (&self.motion, &self.telemetry, &self.status)
+        else {
+            return Vec::new();
+        };
+
+        let frame = frame_of(&motion.header);
+
+        if frame_of(&telemetry.header) != frame
+            || frame_of(&status.header) != frame
+            || self.last_frame == Some(frame)
+        {
+            return Vec::new();
+        }
+
+        self.last_frame = Some(frame);
+
+        motion
+            .motion_data
+            .iter()
+            .zip(&telemetry.car_telemetry_data)
+            .zip(&status.car_status_data)
+            .enumerate()
+            .map(|(car_index, ((m, t), s))| CarFrame {
+                session_uid: frame.0,
+                frame_identifier: frame.1,
+                session_time: motion.header.session_time,
+                car_index: car_index as u8,
+                motion: m.clone(),
+                telemetry: t.clone(),
+                status: s.clone(),
+            })
+            .collect()
+    }
+}
+
+fn frame_of(header: &PacketHeader) -> (u64, u32) {
+    (header.session_uid, header.frame_identifier)
+}
+
+/// Reads the packets of a [`PacketSource`] and returns the [`CarFrame`]s assembled from them,
+/// one car at a time.
+#[derive(Debug)]
+pub struct FrameSource<S> {
+    source: S,
+    assembler: FrameAssembler,
+    pending: VecDeque<CarFrame>,
+}
+
+impl<S: PacketSource> FrameSource<S> {
+    pub fn new(source: S) -> FrameSource<S> {
+        FrameSource {
+            source,
+            assembler: FrameAssembler::new(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Returns the next car frame, or `None` once the source is exhausted. The frames of a
+    /// frame identifier are returned in car index order.
+    pub async fn next_frame(&mut self) -> Result<Option<CarFrame>, UnpackError> {
+        loop {
+            if let Some(frame) = self.pending.pop_front() {
+                return Ok(Some(frame));
+            }
+
+            match self.source.next_packet().await? {
+                Some(packet) => self.pending.extend(self.assembler.observe(&packet)),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// The underlying source, e.g. to [`recover`](PacketSource::recover) it after a fatal error.
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+}
@@ -1,69 +1,38 @@
-use std::net::SocketAddr;
-
-use tokio::net::{ToSocketAddrs, UdpSocket};
-use tokio::runtime::Runtime;
-
-use packet::{parse_packet, Packet, UnpackError};
-
+//! Parser for the UDP telemetry packets of the F1 games.
+//!
+//! Packets can be parsed from raw bytes with [`parse_packet`]. The `stream` feature, enabled by
+//! default, adds [`Stream`] and [`SyncStream`] to receive them from the game, and the [`capture`]
+//! module; without it, the crate does not depend on tokio or on sockets. The parser still needs
+//! `std` though, `#![no_std]` builds are not supported. Applications can be written against
+//! [`PacketSource`] to work with any of them.
+
+pub mod analysis;
+#[cfg(feature = "stream")]
+pub mod capture;
 mod f1_2019;
 mod f1_2020;
 mod f1_2021;
 mod f1_2022;
 mod f1_2023;
+pub mod frame;
 pub mod packet;
+mod source;
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 mod utils;
 
-pub struct Stream {
-    socket: UdpSocket,
-}
-
-impl Stream {
-    pub async fn new<T: ToSocketAddrs>(addr: T) -> std::io::Result<Stream> {
-        let socket = UdpSocket::bind(addr).await?;
-
-        Ok(Stream { socket })
-    }
-
-    pub async fn next(&self) -> Result<Packet, UnpackError> {
-        let mut buf = [0; 2048]; // All packets fit in 2048 bytes
-
-        match self.socket.recv(&mut buf).await {
-            Ok(len) => parse_packet(len, &buf),
-            Err(e) => Err(UnpackError(format!("Error reading from socket: {:?}", e))),
-        }
-    }
-
-    pub async fn next_from(&self) -> Result<(Packet, SocketAddr), UnpackError> {
-        let mut buf = [0; 2048]; // All packets fit in 2048 bytes
-
-        match self.socket.recv_from(&mut buf).await {
-            Ok((len, addr)) => parse_packet(len, &buf).map(|p| (p, addr)),
-            Err(e) => Err(UnpackError(format!("Error reading from socket: {:?}", e))),
-        }
-    }
-
-    pub fn socket(&self) -> &UdpSocket {
-        &self.socket
-    }
-}
-
-pub struct SyncStream {
-    stream: Stream,
-    rt: Runtime,
-}
-
-impl SyncStream {
-    pub fn new<T: ToSocketAddrs>(addr: T) -> std::io::Result<Self> {
-        let rt = Runtime::new().unwrap();
-        let stream = rt.block_on(Stream::new(addr))?;
+pub use source::{PacketSource, VecSource};
+#[cfg(feature = "futures")]
+pub use stream::PacketStream;
+#[cfg(feature = "stream")]
+pub use stream::{Packets, ParseErrorHook, Received, Stream, SyncStream};
 
-        Ok(SyncStream { stream, rt })
-    }
+use packet::{Packet, UnpackError};
 
-    pub fn next(&self) -> Result<Packet, UnpackError> {
-        self.rt.block_on(self.stream.next())
-    }
-    pub fn next_from(&self) -> Result<(Packet, SocketAddr), UnpackError> {
-        self.rt.block_on(self.stream.next_from())
-    }
+/// Parses a whole datagram, as received from the game, e.g. in a WebAssembly build fed from a
+/// WebSocket. The packets implement [`serde::Serialize`] to hand them back as JSON.
+pub fn parse_packet(data: &[u8]) -> Result<Packet, UnpackError> {
+    packet::parse_packet(data.len(), data)
 }
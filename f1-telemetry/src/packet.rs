@@ -1,3 +1,7 @@
+use std::error::Error;
+use std::ops::RangeInclusive;
+use std::{fmt, io};
+
 use serde::Serialize;
 
 use car_damage::PacketCarDamageData;
@@ -6,7 +10,7 @@ use car_status::PacketCarStatusData;
 use car_telemetry::PacketCarTelemetryData;
 use event::PacketEventData;
 use final_classification::PacketFinalClassificationData;
-use header::PacketHeader;
+use header::{GameVersion, PacketHeader};
 use lap::PacketLapData;
 use lobby_info::PacketLobbyInfoData;
 use motion::PacketMotionData;
@@ -16,6 +20,7 @@ use session::PacketSessionData;
 use session_history::PacketSessionHistoryData;
 use tyre_sets::PacketTyreSetsData;
 
+use super::utils::{assert_packet_at_least_size, with_lossy_values};
 use super::{f1_2019, f1_2020, f1_2021, f1_2022, f1_2023};
 
 pub mod car_damage;
@@ -35,16 +40,99 @@ pub mod session;
 pub mod session_history;
 pub mod tyre_sets;
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct UnpackError(pub String);
+/// Error returned when a packet cannot be decoded.
+#[derive(Debug)]
+pub enum UnpackError {
+    /// The packet is smaller than the minimum size required to decode it.
+    TooSmall { got: usize, expected: usize },
+    /// The packet is smaller than the size expected for its type.
+    InvalidSize { got: usize, expected: usize },
+    /// The packet format (game year) is not supported.
+    UnknownFormat(u16),
+    /// The datagram is not an F1 telemetry packet: its format or packet type is not one used by
+    /// the games. Such datagrams can be dropped silently, when other tools share the port.
+    NotF1Packet,
+    /// The packet type is not supported for this packet format.
+    UnsupportedPacketType(PacketType),
+    /// A field holds a value that does not map to any known variant.
+    InvalidValue { field: &'static str, value: i64 },
+    /// The packet data could not be decoded.
+    Decode(String),
+    /// An I/O error occurred while reading the packet.
+    Io(io::Error),
+}
+
+impl UnpackError {
+    /// Whether the socket failed, rather than the packet data. The stream is then unlikely to
+    /// receive anything until it is re-created with [`crate::Stream::rebind`].
+    ///
+    /// Other errors only affect the datagram that was received: it can be dropped and the stream
+    /// used as before. This includes read timeouts.
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            UnpackError::Io(e) => !matches!(
+                e.kind(),
+                io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for UnpackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnpackError::TooSmall { got, expected } => write!(
+                f,
+                "Packet too small: {} bytes (minimum: {} bytes)",
+                got, expected
+            ),
+            UnpackError::InvalidSize { got, expected } => write!(
+                f,
+                "Invalid packet size: {} bytes (expected {} bytes)",
+                got, expected
+            ),
+            UnpackError::UnknownFormat(format) => {
+                write!(f, "Invalid packet: unknown format ({})", format)
+            }
+            UnpackError::NotF1Packet => f.write_str("Not an F1 telemetry packet"),
+            UnpackError::UnsupportedPacketType(packet_type) => {
+                write!(f, "Unsupported packet type: {:?}", packet_type)
+            }
+            UnpackError::InvalidValue { field, value } => {
+                write!(f, "Invalid {} value: {}", field, value)
+            }
+            UnpackError::Decode(msg) => f.write_str(msg),
+            UnpackError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for UnpackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UnpackError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 impl From<Box<bincode::ErrorKind>> for UnpackError {
     fn from(e: Box<bincode::ErrorKind>) -> Self {
-        UnpackError(e.to_string())
+        match *e {
+            bincode::ErrorKind::Io(e) => UnpackError::Io(e),
+            e => UnpackError::Decode(e.to_string()),
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl From<io::Error> for UnpackError {
+    fn from(e: io::Error) -> Self {
+        UnpackError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(tag = "packet_type")]
 pub enum Packet {
     Motion(PacketMotionData),
@@ -102,22 +190,166 @@ pub enum PacketType {
     MotionEx,
 }
 
+impl TryFrom<u8> for PacketType {
+    type Error = UnpackError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PacketType::Motion),
+            1 => Ok(PacketType::Session),
+            2 => Ok(PacketType::LapData),
+            3 => Ok(PacketType::Event),
+            4 => Ok(PacketType::Participants),
+            5 => Ok(PacketType::CarSetups),
+            6 => Ok(PacketType::CarTelemetry),
+            7 => Ok(PacketType::CarStatus),
+            8 => Ok(PacketType::FinalClassification),
+            9 => Ok(PacketType::LobbyInfo),
+            10 => Ok(PacketType::CarDamage),
+            11 => Ok(PacketType::SessionHistory),
+            12 => Ok(PacketType::TyreSets),
+            13 => Ok(PacketType::MotionEx),
+            _ => Err(UnpackError::InvalidValue {
+                field: "PacketType",
+                value: value.into(),
+            }),
+        }
+    }
+}
+
+/// Options controlling how packets are decoded, see [`parse_packet_with`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ParseOptions {
+    /// Fail with [`UnpackError::InvalidValue`] when an enum field holds a value unknown to this
+    /// library (the default). Otherwise such values are replaced by the `Invalid` or `Unknown`
+    /// variant of the enum, and a warning is logged, so that a game patch adding new values does
+    /// not drop whole packets.
+    ///
+    /// Enums without such a variant, e.g. [`session::Weather`] or [`lap::DriverStatus`], are
+    /// always checked, as any of their variants would pass for real data. So are the packet type
+    /// and the element counts.
+    pub strict: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions { strict: true }
+    }
+}
+
 pub fn parse_packet(size: usize, packet: &[u8]) -> Result<Packet, UnpackError> {
-    let packet_format = parse_version(packet);
-
-    match packet_format {
-        2019 => Ok(f1_2019::parse_packet(size, packet)?),
-        2020 => Ok(f1_2020::parse_packet(size, packet)?),
-        2021 => Ok(f1_2021::parse_packet(size, packet)?),
-        2022 => Ok(f1_2022::parse_packet(size, packet)?),
-        2023 => Ok(f1_2023::parse_packet(size, packet)?),
-        _ => Err(UnpackError(format!(
-            "Invalid packet: unknown format ({})",
-            packet_format
-        ))),
+    parse_packet_with(size, packet, ParseOptions::default())
+}
+
+pub fn parse_packet_with(
+    size: usize,
+    packet: &[u8],
+    options: ParseOptions,
+) -> Result<Packet, UnpackError> {
+    let packet_format = check_f1_packet(packet)?;
+
+    with_lossy_values(!options.strict, || {
+        match GameVersion::from_format(packet_format) {
+            GameVersion::F1_2019 => Ok(f1_2019::parse_packet(size, packet)?),
+            GameVersion::F1_2020 => Ok(f1_2020::parse_packet(size, packet)?),
+            GameVersion::F1_2021 => Ok(f1_2021::parse_packet(size, packet)?),
+            GameVersion::F1_2022 => Ok(f1_2022::parse_packet(size, packet)?),
+            GameVersion::F1_2023 => Ok(f1_2023::parse_packet(size, packet)?),
+            GameVersion::Unknown(f) => Err(UnpackError::UnknownFormat(f)),
+        }
+    })
+}
+
+/// Formats raw packet data as a hex dump, 16 bytes per line with their offset and the printable
+/// ASCII characters, to inspect packets that cannot be parsed.
+pub fn hex_dump(data: &[u8]) -> String {
+    let mut dump = String::new();
+
+    for (i, line) in data.chunks(16).enumerate() {
+        let hex: Vec<String> = line.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = line
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() { b as char } else { '.' })
+            .collect();
+
+        dump.push_str(&format!(
+            "{:08x}  {:<47}  |{}|\n",
+            i * 16,
+            hex.join(" "),
+            ascii
+        ));
     }
+
+    dump
 }
 
-fn parse_version(packet: &[u8]) -> u16 {
-    packet[0] as u16 | ((packet[1] as u16) << 8)
+/// Size in bytes a packet of the given type and format must have, header included. Larger
+/// packets are accepted, the trailing bytes being ignored.
+///
+/// Returns `None` if the packet type does not exist for this format. This can be used to check
+/// captures against the layout expected by the parsers.
+pub fn expected_size(packet_type: PacketType, packet_format: u16) -> Option<usize> {
+    packet_length(packet_type, GameVersion::from_format(packet_format))
+}
+
+/// Same as [`expected_size`], for a known game version, e.g. to validate datagrams before
+/// forwarding them.
+pub const fn packet_length(packet_type: PacketType, version: GameVersion) -> Option<usize> {
+    match version {
+        GameVersion::F1_2019 => f1_2019::expected_size(packet_type),
+        GameVersion::F1_2020 => f1_2020::expected_size(packet_type),
+        GameVersion::F1_2021 => f1_2021::expected_size(packet_type),
+        GameVersion::F1_2022 => f1_2022::expected_size(packet_type),
+        GameVersion::F1_2023 => f1_2023::expected_size(packet_type),
+        GameVersion::Unknown(_) => None,
+    }
+}
+
+/// Formats of the F1 games, the year of the game, including the ones that are not supported yet.
+const F1_FORMATS: RangeInclusive<u16> = 2018..=2099;
+const SUPPORTED_FORMATS: RangeInclusive<u16> = 2019..=2023;
+
+/// Checks from the header that the datagram is a supported F1 packet, before decoding anything
+/// else.
+///
+/// Returns the packet format.
+fn check_f1_packet(packet: &[u8]) -> Result<u16, UnpackError> {
+    let packet_format = parse_version(packet)?;
+
+    if !SUPPORTED_FORMATS.contains(&packet_format) {
+        return Err(if F1_FORMATS.contains(&packet_format) {
+            UnpackError::UnknownFormat(packet_format)
+        } else {
+            UnpackError::NotF1Packet
+        });
+    }
+
+    let packet_type = match peek_packet_type(packet) {
+        Ok(packet_type) => packet_type,
+        Err(UnpackError::InvalidValue { .. }) => return Err(UnpackError::NotF1Packet),
+        Err(e) => return Err(e),
+    };
+
+    // A packet type added by a later game, e.g. tyre sets in an F1 22 packet
+    match expected_size(packet_type, packet_format) {
+        Some(_) => Ok(packet_format),
+        None => Err(UnpackError::UnsupportedPacketType(packet_type)),
+    }
+}
+
+fn parse_version(packet: &[u8]) -> Result<u16, UnpackError> {
+    assert_packet_at_least_size(packet.len(), 2)?;
+
+    Ok(packet[0] as u16 | ((packet[1] as u16) << 8))
+}
+
+/// Reads the type of a packet from its header, without decoding the rest of the packet.
+pub fn peek_packet_type(packet: &[u8]) -> Result<PacketType, UnpackError> {
+    let packet_format = parse_version(packet)?;
+
+    // F1 23 added the game year before the version fields
+    let offset = if packet_format >= 2023 { 6 } else { 5 };
+    assert_packet_at_least_size(packet.len(), offset + 1)?;
+
+    PacketType::try_from(packet[offset])
 }
@@ -69,3 +69,11 @@ pub struct PacketCarDamageData {
     pub header: PacketHeader,
     pub car_damage_data: Vec<CarDamageData>,
 }
+
+impl PacketCarDamageData {
+    /// Damage of the player's car, or `None` if there is no player car (e.g. when spectating).
+    pub fn player_data(&self) -> Option<&CarDamageData> {
+        self.car_damage_data
+            .get(self.header.player_car_index as usize)
+    }
+}
@@ -4,10 +4,16 @@ use crate::packet::generic::WheelData;
 
 use super::header::PacketHeader;
 
-/// This type is used for the 20-element `car_setups` array of the [`PacketCarSetupData`] type.
+/// This type is used for the 20-element (22 from F1 2020 onwards) `car_setups` array of the
+/// [`PacketCarSetupData`] type.
+///
+/// Fields are declared in the same order as they appear in the UDP specification.
+///
+/// F1 2019 only reports a front and a rear tyre pressure: both left and right wheels of an axle
+/// share the same value in `tyres_pressure`.
 ///
-/// ```text
 /// ## Specification
+/// ```text
 /// front_wing              Front wing aero
 /// rear_wing               Rear wing aero
 /// on_throttle             Differential adjustment on throttle (percentage)
@@ -67,3 +73,10 @@ pub struct PacketCarSetupData {
     pub header: PacketHeader,
     pub car_setups: Vec<CarSetupData>,
 }
+
+impl PacketCarSetupData {
+    /// Setup of the player's car, or `None` if there is no player car (e.g. when spectating).
+    pub fn player_data(&self) -> Option<&CarSetupData> {
+        self.car_setups.get(self.header.player_car_index as usize)
+    }
+}
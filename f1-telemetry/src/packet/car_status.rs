@@ -1,3 +1,5 @@
+use std::fmt;
+
 use serde::Serialize;
 
 use crate::packet::generic::{Flag, TyreCompound, TyreCompoundVisual, WheelData};
@@ -30,6 +32,22 @@ pub enum DRS {
     Unknown,
 }
 
+impl DRS {
+    pub fn name(self) -> &'static str {
+        match self {
+            DRS::NotAllowed => "Not Allowed",
+            DRS::Allowed => "Allowed",
+            DRS::Unknown => "Unknown",
+        }
+    }
+}
+
+impl fmt::Display for DRS {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum ERSDeployMode {
@@ -163,3 +181,11 @@ pub struct PacketCarStatusData {
     pub header: PacketHeader,
     pub car_status_data: Vec<CarStatusData>,
 }
+
+impl PacketCarStatusData {
+    /// Status of the player's car, or `None` if there is no player car (e.g. when spectating).
+    pub fn player_data(&self) -> Option<&CarStatusData> {
+        self.car_status_data
+            .get(self.header.player_car_index as usize)
+    }
+}
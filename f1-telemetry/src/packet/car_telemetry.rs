@@ -89,7 +89,7 @@ pub struct CarTelemetryData {
 /// 0x2000              Left Stick Click
 /// 0x4000              Right Stick Click
 /// ```
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum ButtonFlag {
     Cross = 0x0001,
     Triangle = 0x0002,
@@ -148,6 +148,12 @@ pub struct PacketCarTelemetryData {
 }
 
 impl PacketCarTelemetryData {
+    /// Telemetry of the player's car, or `None` if there is no player car (e.g. when spectating).
+    pub fn player_data(&self) -> Option<&CarTelemetryData> {
+        self.car_telemetry_data
+            .get(self.header.player_car_index as usize)
+    }
+
     pub fn get_pressed_buttons(&self) -> Vec<ButtonFlag> {
         let mask = self.button_status.unwrap_or_default();
         let mut buttons = Vec::new();
@@ -1,4 +1,6 @@
-use serde::Serialize;
+use std::fmt;
+
+use serde::{Serialize, Serializer};
 
 use super::header::PacketHeader;
 
@@ -53,6 +55,69 @@ pub enum PenaltyType {
     ThisAndPreviousLapInvalidatedWithoutReason,
     Retired,
     BlackFlagTimer,
+    /// A penalty ID not known to this library
+    Other(u8),
+}
+
+impl From<u8> for PenaltyType {
+    /// Decodes a penalty type ID, the IDs are the same for all games since F1 2020.
+    fn from(value: u8) -> Self {
+        match value {
+            0 => PenaltyType::DriveThrough,
+            1 => PenaltyType::StopGo,
+            2 => PenaltyType::GridPenalty,
+            3 => PenaltyType::PenaltyReminder,
+            4 => PenaltyType::TimePenalty,
+            5 => PenaltyType::Warning,
+            6 => PenaltyType::Disqualified,
+            7 => PenaltyType::RemovedFromFormationLap,
+            8 => PenaltyType::ParkedTooLongTimer,
+            9 => PenaltyType::TyreRegulations,
+            10 => PenaltyType::ThisLapInvalidated,
+            11 => PenaltyType::ThisAndNextLapInvalidated,
+            12 => PenaltyType::ThisLapInvalidatedWithoutReason,
+            13 => PenaltyType::ThisAndNextLapInvalidatedWithoutReason,
+            14 => PenaltyType::ThisAndPreviousLapInvalidated,
+            15 => PenaltyType::ThisAndPreviousLapInvalidatedWithoutReason,
+            16 => PenaltyType::Retired,
+            17 => PenaltyType::BlackFlagTimer,
+            _ => PenaltyType::Other(value),
+        }
+    }
+}
+
+impl fmt::Display for PenaltyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PenaltyType::DriveThrough => f.write_str("Drive-through"),
+            PenaltyType::StopGo => f.write_str("Stop-go"),
+            PenaltyType::GridPenalty => f.write_str("Grid penalty"),
+            PenaltyType::PenaltyReminder => f.write_str("Penalty reminder"),
+            PenaltyType::TimePenalty => f.write_str("Time penalty"),
+            PenaltyType::Warning => f.write_str("Warning"),
+            PenaltyType::Disqualified => f.write_str("Disqualified"),
+            PenaltyType::RemovedFromFormationLap => f.write_str("Removed from formation lap"),
+            PenaltyType::ParkedTooLongTimer => f.write_str("Parked too long timer"),
+            PenaltyType::TyreRegulations => f.write_str("Tyre regulations"),
+            PenaltyType::ThisLapInvalidated => f.write_str("This lap invalidated"),
+            PenaltyType::ThisAndNextLapInvalidated => f.write_str("This and next lap invalidated"),
+            PenaltyType::ThisLapInvalidatedWithoutReason => {
+                f.write_str("This lap invalidated without reason")
+            }
+            PenaltyType::ThisAndNextLapInvalidatedWithoutReason => {
+                f.write_str("This and next lap invalidated without reason")
+            }
+            PenaltyType::ThisAndPreviousLapInvalidated => {
+                f.write_str("This and previous lap invalidated")
+            }
+            PenaltyType::ThisAndPreviousLapInvalidatedWithoutReason => {
+                f.write_str("This and previous lap invalidated without reason")
+            }
+            PenaltyType::Retired => f.write_str("Retired"),
+            PenaltyType::BlackFlagTimer => f.write_str("Black flag timer"),
+            PenaltyType::Other(value) => write!(f, "Penalty {}", value),
+        }
+    }
 }
 
 /// List of possible infringments
@@ -113,6 +178,189 @@ pub enum InfringementType {
     FormationLapParking,
     ParcFermeChange,
     AttributeAssigned,
+    /// An infringement ID not known to this library
+    Other(u8),
+}
+
+impl From<u8> for InfringementType {
+    /// Decodes an infringement type ID, following the IDs used from F1 22 onwards. Earlier games
+    /// have no `FormationLapParking` and `ParcFermeChange` infringements, and shift the IDs
+    /// after them.
+    fn from(value: u8) -> Self {
+        match value {
+            0 => InfringementType::BlockingBySlowDriving,
+            1 => InfringementType::BlockingByWrongWayDriving,
+            2 => InfringementType::ReversingOffTheStartLine,
+            3 => InfringementType::BigCollision,
+            4 => InfringementType::SmallCollision,
+            5 => InfringementType::CollisionFailedToHandBackPositionSingle,
+            6 => InfringementType::CollisionFailedToHandBackPositionMultiple,
+            7 => InfringementType::CornerCuttingGainedTime,
+            8 => InfringementType::CornerCuttingOvertakeSingle,
+            9 => InfringementType::CornerCuttingOvertakeMultiple,
+            10 => InfringementType::CrossedPitExitLane,
+            11 => InfringementType::IgnoringBlueFlags,
+            12 => InfringementType::IgnoringYellowFlags,
+            13 => InfringementType::IgnoringDriveThrough,
+            14 => InfringementType::TooManyDriveThroughs,
+            15 => InfringementType::DriveThroughReminderServeWithinNLaps,
+            16 => InfringementType::DriveThroughReminderServeThisLap,
+            17 => InfringementType::PitLaneSpeeding,
+            18 => InfringementType::ParkedForTooLong,
+            19 => InfringementType::IgnoringTyreRegulations,
+            20 => InfringementType::TooManyPenalties,
+            21 => InfringementType::MultipleWarnings,
+            22 => InfringementType::ApproachingDisqualification,
+            23 => InfringementType::TyreRegulationsSelectSingle,
+            24 => InfringementType::TyreRegulationsSelectMultiple,
+            25 => InfringementType::LapInvalidatedCornerCutting,
+            26 => InfringementType::LapInvalidatedRunningWide,
+            27 => InfringementType::CornerCuttingRanWideGainedTimeMinor,
+            28 => InfringementType::CornerCuttingRanWideGainedTimeSignificant,
+            29 => InfringementType::CornerCuttingRanWideGainedTimeExtreme,
+            30 => InfringementType::LapInvalidatedWallRiding,
+            31 => InfringementType::LapInvalidatedFlashbackUsed,
+            32 => InfringementType::LapInvalidatedResetToTrack,
+            33 => InfringementType::BlockingThePitlane,
+            34 => InfringementType::JumpStart,
+            35 => InfringementType::SafetyCarToCarCollision,
+            36 => InfringementType::SafetyCarIllegalOvertake,
+            37 => InfringementType::SafetyCarExceedingAllowedPace,
+            38 => InfringementType::VirtualSafetyCarExceedingAllowedPace,
+            39 => InfringementType::FormationLapBelowAllowedSpeed,
+            40 => InfringementType::FormationLapParking,
+            41 => InfringementType::RetiredMechanicalFailure,
+            42 => InfringementType::RetiredTerminallyDamaged,
+            43 => InfringementType::SafetyCarFallingTooFarBack,
+            44 => InfringementType::BlackFlagTimer,
+            45 => InfringementType::UnservedStopGoPenalty,
+            46 => InfringementType::UnservedDriveThroughPenalty,
+            47 => InfringementType::EngineComponentChange,
+            48 => InfringementType::GearboxChange,
+            49 => InfringementType::ParcFermeChange,
+            50 => InfringementType::LeagueGridPenalty,
+            51 => InfringementType::RetryPenalty,
+            52 => InfringementType::IllegalTimeGain,
+            53 => InfringementType::MandatoryPitstop,
+            54 => InfringementType::AttributeAssigned,
+            _ => InfringementType::Other(value),
+        }
+    }
+}
+
+impl fmt::Display for InfringementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InfringementType::BlockingBySlowDriving => f.write_str("Blocking by slow driving"),
+            InfringementType::BlockingByWrongWayDriving => {
+                f.write_str("Blocking by wrong way driving")
+            }
+            InfringementType::ReversingOffTheStartLine => {
+                f.write_str("Reversing off the start line")
+            }
+            InfringementType::BigCollision => f.write_str("Big collision"),
+            InfringementType::SmallCollision => f.write_str("Small collision"),
+            InfringementType::CollisionFailedToHandBackPositionSingle => {
+                f.write_str("Collision, failed to hand back position")
+            }
+            InfringementType::CollisionFailedToHandBackPositionMultiple => {
+                f.write_str("Collision, failed to hand back positions")
+            }
+            InfringementType::CornerCuttingGainedTime => f.write_str("Corner cutting gained time"),
+            InfringementType::CornerCuttingOvertakeSingle => f.write_str("Corner cutting overtake"),
+            InfringementType::CornerCuttingOvertakeMultiple => {
+                f.write_str("Corner cutting overtakes")
+            }
+            InfringementType::CrossedPitExitLane => f.write_str("Crossed pit exit lane"),
+            InfringementType::IgnoringBlueFlags => f.write_str("Ignoring blue flags"),
+            InfringementType::IgnoringYellowFlags => f.write_str("Ignoring yellow flags"),
+            InfringementType::IgnoringDriveThrough => f.write_str("Ignoring drive-through"),
+            InfringementType::TooManyDriveThroughs => f.write_str("Too many drive-throughs"),
+            InfringementType::DriveThroughReminderServeWithinNLaps => {
+                f.write_str("Serve drive-through within laps")
+            }
+            InfringementType::DriveThroughReminderServeThisLap => {
+                f.write_str("Serve drive-through this lap")
+            }
+            InfringementType::PitLaneSpeeding => f.write_str("Pit lane speeding"),
+            InfringementType::ParkedForTooLong => f.write_str("Parked for too long"),
+            InfringementType::IgnoringTyreRegulations => f.write_str("Ignoring tyre regulations"),
+            InfringementType::TooManyPenalties => f.write_str("Too many penalties"),
+            InfringementType::MultipleWarnings => f.write_str("Multiple warnings"),
+            InfringementType::ApproachingDisqualification => {
+                f.write_str("Approaching disqualification")
+            }
+            InfringementType::TyreRegulationsSelectSingle => {
+                f.write_str("Tyre regulations, select a compound")
+            }
+            InfringementType::TyreRegulationsSelectMultiple => {
+                f.write_str("Tyre regulations, select compounds")
+            }
+            InfringementType::LapInvalidatedCornerCutting => {
+                f.write_str("Lap invalidated, corner cutting")
+            }
+            InfringementType::LapInvalidatedRunningWide => {
+                f.write_str("Lap invalidated, running wide")
+            }
+            InfringementType::CornerCuttingRanWideGainedTimeMinor => {
+                f.write_str("Ran wide, minor time gain")
+            }
+            InfringementType::CornerCuttingRanWideGainedTimeSignificant => {
+                f.write_str("Ran wide, significant time gain")
+            }
+            InfringementType::CornerCuttingRanWideGainedTimeExtreme => {
+                f.write_str("Ran wide, extreme time gain")
+            }
+            InfringementType::LapInvalidatedWallRiding => {
+                f.write_str("Lap invalidated, wall riding")
+            }
+            InfringementType::LapInvalidatedFlashbackUsed => {
+                f.write_str("Lap invalidated, flashback used")
+            }
+            InfringementType::LapInvalidatedResetToTrack => {
+                f.write_str("Lap invalidated, reset to track")
+            }
+            InfringementType::BlockingThePitlane => f.write_str("Blocking the pit lane"),
+            InfringementType::JumpStart => f.write_str("Jump start"),
+            InfringementType::SafetyCarToCarCollision => f.write_str("Safety car collision"),
+            InfringementType::SafetyCarIllegalOvertake => {
+                f.write_str("Safety car illegal overtake")
+            }
+            InfringementType::SafetyCarExceedingAllowedPace => {
+                f.write_str("Exceeding safety car pace")
+            }
+            InfringementType::VirtualSafetyCarExceedingAllowedPace => {
+                f.write_str("Exceeding virtual safety car pace")
+            }
+            InfringementType::FormationLapBelowAllowedSpeed => {
+                f.write_str("Formation lap below allowed speed")
+            }
+            InfringementType::RetiredMechanicalFailure => {
+                f.write_str("Retired, mechanical failure")
+            }
+            InfringementType::RetiredTerminallyDamaged => {
+                f.write_str("Retired, terminally damaged")
+            }
+            InfringementType::SafetyCarFallingTooFarBack => {
+                f.write_str("Falling too far behind the safety car")
+            }
+            InfringementType::BlackFlagTimer => f.write_str("Black flag timer"),
+            InfringementType::UnservedStopGoPenalty => f.write_str("Unserved stop-go penalty"),
+            InfringementType::UnservedDriveThroughPenalty => {
+                f.write_str("Unserved drive-through penalty")
+            }
+            InfringementType::EngineComponentChange => f.write_str("Engine component change"),
+            InfringementType::GearboxChange => f.write_str("Gearbox change"),
+            InfringementType::LeagueGridPenalty => f.write_str("League grid penalty"),
+            InfringementType::RetryPenalty => f.write_str("Retry penalty"),
+            InfringementType::IllegalTimeGain => f.write_str("Illegal time gain"),
+            InfringementType::MandatoryPitstop => f.write_str("Mandatory pit stop"),
+            InfringementType::FormationLapParking => f.write_str("Formation lap parking"),
+            InfringementType::ParcFermeChange => f.write_str("Parc fermé change"),
+            InfringementType::AttributeAssigned => f.write_str("Attribute assigned"),
+            InfringementType::Other(value) => write!(f, "Infringement {}", value),
+        }
+    }
 }
 
 /// Description of a penalty event
@@ -197,6 +445,25 @@ pub struct Overtake {
     pub being_overtaken_vehicle_idx: u8,
 }
 
+/// Description of an event with a code unknown to this library, e.g. one sent by a newer game
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub struct UnknownEvent {
+    /// Event string code
+    #[serde(serialize_with = "serialize_event_code")]
+    pub code: [u8; 4],
+}
+
+impl UnknownEvent {
+    /// Event string code, as text
+    pub fn code(&self) -> String {
+        String::from_utf8_lossy(&self.code).into_owned()
+    }
+}
+
+fn serialize_event_code<S: Serializer>(code: &[u8; 4], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&String::from_utf8_lossy(code))
+}
+
 /// List of possible events
 ///
 /// The following events were introduced in F1 2020:
@@ -211,8 +478,11 @@ pub struct Overtake {
 /// * [`Event::Flashback`]
 /// * [`Event::Buttons`]
 ///
-/// The following event was introduced in F1 23:
+/// The following events were introduced in F1 23:
+/// * [`Event::RedFlag`]
 /// * [`Event::Overtake`]
+///
+/// Events with an unknown code are reported as [`Event::Other`].
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 #[serde(tag = "event_type")]
@@ -251,13 +521,17 @@ pub enum Event {
     Flashback(Flashback),
     /// Button status changed
     Buttons(Buttons),
+    /// Red flag shown
+    RedFlag,
     /// Overtake
     Overtake(Overtake),
+    /// Event unknown to this library
+    Other(UnknownEvent),
 }
 
 impl Event {
     /// Get a human readable description of an event
-    pub fn description<'a>(self) -> &'a str {
+    pub fn description(self) -> &'static str {
         match self {
             Event::SessionStarted => "Session Started",
             Event::SessionEnded => "Session Ended",
@@ -276,7 +550,9 @@ impl Event {
             Event::StopGoPenaltyServed(_) => "Stop and go penalty served",
             Event::Flashback(_) => "Flashback activated",
             Event::Buttons(_) => "Button status changed",
+            Event::RedFlag => "Red Flag",
             Event::Overtake(_) => "Overtake",
+            Event::Other(_) => "Unknown event",
         }
     }
 
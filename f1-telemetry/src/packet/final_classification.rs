@@ -6,6 +6,8 @@ use super::header::PacketHeader;
 
 /// This type is used for the `classification_data` array of the [`PacketFinalClassificationData`] type.
 ///
+/// The tyre stint arrays always contain the full fixed-size block sent by the game: only the first
+/// `num_tyre_stints` entries are meaningful.
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
 pub struct FinalClassification {
     /// Finishing position
@@ -34,7 +36,7 @@ pub struct FinalClassification {
     pub tyre_stints_actual: Vec<TyreCompound>,
     /// Visual tyres used by this driver
     pub tyre_stints_visual: Vec<TyreCompoundVisual>,
-    /// The lap number stints end on
+    /// The lap number stints end on (empty for F1 2020, which does not send this field)
     pub tyre_stints_end_lap: Vec<u8>,
 }
 
@@ -52,3 +54,12 @@ pub struct PacketFinalClassificationData {
     /// List of final classifications.
     pub final_classifications: Vec<FinalClassification>,
 }
+
+impl PacketFinalClassificationData {
+    /// Final classification of the player's car, or `None` if there is no player car (e.g. when
+    /// spectating).
+    pub fn player_data(&self) -> Option<&FinalClassification> {
+        self.final_classifications
+            .get(self.header.player_car_index as usize)
+    }
+}
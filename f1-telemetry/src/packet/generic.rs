@@ -1,5 +1,11 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
+use crate::packet::header::GameVersion;
+use crate::packet::UnpackError;
+use crate::utils::invalid_value;
+
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum Flag {
     None,
@@ -11,7 +17,22 @@ pub enum Flag {
     Invalid,
 }
 
-#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
+/// Unknown values are mapped to [`Flag::Invalid`], which the game already uses for `-1`, so that a
+/// single corrupt flag doesn't discard the rest of the packet.
+impl From<i8> for Flag {
+    fn from(value: i8) -> Self {
+        match value {
+            0 => Flag::None,
+            1 => Flag::Green,
+            2 => Flag::Blue,
+            3 => Flag::Yellow,
+            4 => Flag::Red,
+            _ => Flag::Invalid,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash, Serialize)]
 pub enum Nationality {
     American,
     Argentinean,
@@ -294,6 +315,12 @@ impl Team {
     }
 }
 
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum Platform {
     Steam,
@@ -325,6 +352,35 @@ pub enum TyreCompound {
     Invalid,
 }
 
+impl TyreCompound {
+    pub fn name(self) -> &'static str {
+        match self {
+            TyreCompound::C0 => "C0",
+            TyreCompound::C1 => "C1",
+            TyreCompound::C2 => "C2",
+            TyreCompound::C3 => "C3",
+            TyreCompound::C4 => "C4",
+            TyreCompound::C5 => "C5",
+            TyreCompound::Inter => "Intermediate",
+            TyreCompound::Wet => "Wet",
+            TyreCompound::ClassicDry => "Dry (Classic)",
+            TyreCompound::ClassicWet => "Wet (Classic)",
+            TyreCompound::F2SuperSoft => "Super Soft (F2)",
+            TyreCompound::F2Soft => "Soft (F2)",
+            TyreCompound::F2Medium => "Medium (F2)",
+            TyreCompound::F2Hard => "Hard (F2)",
+            TyreCompound::F2Wet => "Wet (F2)",
+            TyreCompound::Invalid => "Invalid",
+        }
+    }
+}
+
+impl fmt::Display for TyreCompound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum TyreCompoundVisual {
     Soft,
@@ -344,7 +400,7 @@ pub enum TyreCompoundVisual {
 }
 
 impl TyreCompoundVisual {
-    pub fn name<'a>(self) -> &'a str {
+    pub fn name(self) -> &'static str {
         match self {
             TyreCompoundVisual::Soft => "Soft",
             TyreCompoundVisual::Medium => "Medium",
@@ -363,6 +419,12 @@ impl TyreCompoundVisual {
     }
 }
 
+impl fmt::Display for TyreCompoundVisual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum SessionType {
     #[default]
@@ -382,8 +444,72 @@ pub enum SessionType {
     TimeTrial,
 }
 
+impl TryFrom<u8> for SessionType {
+    type Error = UnpackError;
+
+    /// Decodes a session type ID as sent by F1 23. Use [`SessionType::from_id`] to decode the IDs
+    /// of earlier games, which differ from `12` onwards.
+    ///
+    /// Unknown values are an [`UnpackError::InvalidValue`] error. They are only replaced by the
+    /// default variant while a packet is decoded by [`crate::packet::parse_packet_with`] in lossy
+    /// mode.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        SessionType::from_id(value, GameVersion::F1_2023)
+    }
+}
+
 impl SessionType {
-    pub fn name<'a>(self) -> &'a str {
+    /// Decodes a session type ID as sent by the given game. F1 22 added `Race3` as `12`, so up to
+    /// F1 2021 `TimeTrial` is sent as `12` and `13` is not used.
+    pub fn from_id(id: u8, version: GameVersion) -> Result<Self, UnpackError> {
+        let has_race3 = !matches!(
+            version,
+            GameVersion::F1_2019 | GameVersion::F1_2020 | GameVersion::F1_2021
+        );
+
+        match id {
+            0 => Ok(SessionType::Unknown),
+            1 => Ok(SessionType::Practice1),
+            2 => Ok(SessionType::Practice2),
+            3 => Ok(SessionType::Practice3),
+            4 => Ok(SessionType::PracticeShort),
+            5 => Ok(SessionType::Qualifying1),
+            6 => Ok(SessionType::Qualifying2),
+            7 => Ok(SessionType::Qualifying3),
+            8 => Ok(SessionType::QualifyingShort),
+            9 => Ok(SessionType::OneShotQualifying),
+            10 => Ok(SessionType::Race),
+            11 => Ok(SessionType::Race2),
+            12 if has_race3 => Ok(SessionType::Race3),
+            12 => Ok(SessionType::TimeTrial),
+            13 if has_race3 => Ok(SessionType::TimeTrial),
+            _ => invalid_value("SessionType", id),
+        }
+    }
+
+    /// ID of the session type, the inverse of [`SessionType::try_from`]. These are the IDs used
+    /// from F1 23 onwards, e.g. to look up localized names.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            SessionType::Unknown => 0,
+            SessionType::Practice1 => 1,
+            SessionType::Practice2 => 2,
+            SessionType::Practice3 => 3,
+            SessionType::PracticeShort => 4,
+            SessionType::Qualifying1 => 5,
+            SessionType::Qualifying2 => 6,
+            SessionType::Qualifying3 => 7,
+            SessionType::QualifyingShort => 8,
+            SessionType::OneShotQualifying => 9,
+            SessionType::Race => 10,
+            SessionType::Race2 => 11,
+            SessionType::Race3 => 12,
+            SessionType::TimeTrial => 13,
+        }
+    }
+
+    /// English name of the session type
+    pub fn name(self) -> &'static str {
         match self {
             SessionType::Unknown => "Unknown",
             SessionType::Practice1 => "Free Practice 1",
@@ -403,6 +529,12 @@ impl SessionType {
     }
 }
 
+impl fmt::Display for SessionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct WheelData<T>
 where
@@ -426,15 +558,101 @@ where
             front_right,
         }
     }
+
+    /// Values of the four wheels, in the order used by the game: rear left, rear right, front left,
+    /// front right.
+    pub fn as_array(&self) -> [T; 4] {
+        [
+            self.rear_left,
+            self.rear_right,
+            self.front_left,
+            self.front_right,
+        ]
+    }
+
+    /// Iterates over the values of the four wheels, in the same order as [`WheelData::as_array`].
+    pub fn iter(&self) -> std::array::IntoIter<T, 4> {
+        self.as_array().into_iter()
+    }
+
+    /// Applies `f` to the value of each wheel.
+    pub fn map<U, F>(self, mut f: F) -> WheelData<U>
+    where
+        U: Clone + Copy,
+        F: FnMut(T) -> U,
+    {
+        WheelData {
+            rear_left: f(self.rear_left),
+            rear_right: f(self.rear_right),
+            front_left: f(self.front_left),
+            front_right: f(self.front_right),
+        }
+    }
 }
 
 impl From<WheelData<u8>> for WheelData<u16> {
     fn from(wheel_data: WheelData<u8>) -> Self {
-        Self {
-            rear_left: wheel_data.rear_left as u16,
-            rear_right: wheel_data.rear_right as u16,
-            front_left: wheel_data.front_left as u16,
-            front_right: wheel_data.front_right as u16,
-        }
+        wheel_data.map(u16::from)
+    }
+}
+
+#[cfg(test)]
+mod test_wheel_data {
+    use super::WheelData;
+
+    #[test]
+    fn test_wheel_data_order() {
+        let wheel_data = WheelData::new(1, 2, 3, 4);
+
+        assert_eq!(wheel_data.as_array(), [1, 2, 3, 4]);
+        assert_eq!(wheel_data.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_wheel_data_map() {
+        let wear = WheelData::new(10u8, 20, 30, 40).map(|w| w as f32 / 100.0);
+
+        assert_eq!(wear, WheelData::new(0.1, 0.2, 0.3, 0.4));
+        assert_eq!(wear.iter().sum::<f32>() / 4.0, 0.25);
+    }
+}
+
+#[cfg(test)]
+mod test_session_type {
+    use super::SessionType;
+    use crate::packet::header::GameVersion;
+
+    #[test]
+    fn test_session_type_from_id() {
+        assert_eq!(SessionType::try_from(0).unwrap(), SessionType::Unknown);
+        assert_eq!(SessionType::try_from(1).unwrap(), SessionType::Practice1);
+        assert_eq!(SessionType::try_from(10).unwrap(), SessionType::Race);
+        assert_eq!(SessionType::try_from(13).unwrap(), SessionType::TimeTrial);
+        assert!(SessionType::try_from(14).is_err());
+        assert!(SessionType::try_from(255).is_err());
+    }
+
+    #[test]
+    fn test_session_type_from_id_before_race3() {
+        let time_trial = SessionType::from_id(12, GameVersion::F1_2021).unwrap();
+        assert_eq!(time_trial, SessionType::TimeTrial);
+        assert!(SessionType::from_id(13, GameVersion::F1_2019).is_err());
+
+        let race3 = SessionType::from_id(12, GameVersion::F1_2022).unwrap();
+        assert_eq!(race3, SessionType::Race3);
+    }
+}
+
+#[cfg(test)]
+mod test_flag {
+    use super::Flag;
+
+    #[test]
+    fn test_flag_from_value() {
+        assert_eq!(Flag::from(0), Flag::None);
+        assert_eq!(Flag::from(1), Flag::Green);
+        assert_eq!(Flag::from(4), Flag::Red);
+        assert_eq!(Flag::from(-1), Flag::Invalid);
+        assert_eq!(Flag::from(42), Flag::Invalid);
     }
 }
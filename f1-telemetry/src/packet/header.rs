@@ -31,3 +31,48 @@ pub struct PacketHeader {
     /// Index of secondary player's car in the array, if any
     pub secondary_player_car_index: Option<u8>,
 }
+
+impl PacketHeader {
+    /// Game the packet was sent by, from its packet format
+    pub fn game_version(&self) -> GameVersion {
+        GameVersion::from_format(self.packet_format)
+    }
+}
+
+/// Game year, as identified by the packet format
+#[allow(non_camel_case_types)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize)]
+pub enum GameVersion {
+    F1_2019,
+    F1_2020,
+    F1_2021,
+    F1_2022,
+    F1_2023,
+    /// Format not supported by this library
+    Unknown(u16),
+}
+
+impl GameVersion {
+    pub fn from_format(packet_format: u16) -> GameVersion {
+        match packet_format {
+            2019 => GameVersion::F1_2019,
+            2020 => GameVersion::F1_2020,
+            2021 => GameVersion::F1_2021,
+            2022 => GameVersion::F1_2022,
+            2023 => GameVersion::F1_2023,
+            f => GameVersion::Unknown(f),
+        }
+    }
+
+    /// Packet format sent by the game, e.g. `2023`
+    pub fn format(self) -> u16 {
+        match self {
+            GameVersion::F1_2019 => 2019,
+            GameVersion::F1_2020 => 2020,
+            GameVersion::F1_2021 => 2021,
+            GameVersion::F1_2022 => 2022,
+            GameVersion::F1_2023 => 2023,
+            GameVersion::Unknown(f) => f,
+        }
+    }
+}
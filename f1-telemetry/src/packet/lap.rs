@@ -1,7 +1,10 @@
+use std::fmt;
+
 use serde::Serialize;
 
 use crate::packet::generic::ResultStatus;
 
+use super::event::{InfringementType, Penalty, PenaltyType};
 use super::header::PacketHeader;
 
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
@@ -22,7 +25,7 @@ pub enum DriverStatus {
     OnTrack,
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum Sector {
     #[default]
     Sector1,
@@ -30,6 +33,59 @@ pub enum Sector {
     Sector3,
 }
 
+/// Why a lap was invalidated.
+///
+/// The lap data only tells whether the current lap is invalid, the reason comes with the penalty
+/// event issued when the lap is invalidated, see [`InvalidationReason::from_penalty`]. F1 2019 has
+/// no penalty events, so the reason is never known.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+pub enum InvalidationReason {
+    CornerCutting,
+    RunningWide,
+    WallRiding,
+    FlashbackUsed,
+    ResetToTrack,
+    /// Another infringement, e.g. a corner cut gaining time
+    Other(InfringementType),
+}
+
+impl InvalidationReason {
+    /// Reason a penalty invalidated a lap, or `None` if the penalty does not invalidate a lap or
+    /// comes without a reason.
+    pub fn from_penalty(penalty: &Penalty) -> Option<Self> {
+        match penalty.penalty_type {
+            PenaltyType::ThisLapInvalidated
+            | PenaltyType::ThisAndNextLapInvalidated
+            | PenaltyType::ThisAndPreviousLapInvalidated => {}
+            _ => return None,
+        }
+
+        let reason = match penalty.infringement_type {
+            InfringementType::LapInvalidatedCornerCutting => InvalidationReason::CornerCutting,
+            InfringementType::LapInvalidatedRunningWide => InvalidationReason::RunningWide,
+            InfringementType::LapInvalidatedWallRiding => InvalidationReason::WallRiding,
+            InfringementType::LapInvalidatedFlashbackUsed => InvalidationReason::FlashbackUsed,
+            InfringementType::LapInvalidatedResetToTrack => InvalidationReason::ResetToTrack,
+            infringement => InvalidationReason::Other(infringement),
+        };
+
+        Some(reason)
+    }
+}
+
+impl fmt::Display for InvalidationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidationReason::CornerCutting => f.write_str("Corner cutting"),
+            InvalidationReason::RunningWide => f.write_str("Running wide"),
+            InvalidationReason::WallRiding => f.write_str("Wall riding"),
+            InvalidationReason::FlashbackUsed => f.write_str("Flashback used"),
+            InvalidationReason::ResetToTrack => f.write_str("Reset to track"),
+            InvalidationReason::Other(infringement) => infringement.fmt(f),
+        }
+    }
+}
+
 /// Lap data for a car on track
 #[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct LapData {
@@ -87,7 +143,7 @@ pub struct LapData {
     pub number_pit_stops: u8,
     /// Current sector
     pub sector: Sector,
-    /// Current lap invalid
+    /// Current lap invalid, see [`InvalidationReason`] for why
     pub current_lap_invalid: bool,
     /// Accumulated time penalties in seconds to be added
     pub penalties: u8,
@@ -115,6 +171,18 @@ pub struct LapData {
     pub pit_stop_should_serve_penalty: bool,
 }
 
+impl LapData {
+    /// Sector 1 time in milliseconds, including the whole minutes sent apart since F1 23
+    pub fn sector_1_total_time(&self) -> u32 {
+        self.sector_1_time_minutes as u32 * 60_000 + self.sector_1_time as u32
+    }
+
+    /// Sector 2 time in milliseconds, including the whole minutes sent apart since F1 23
+    pub fn sector_2_total_time(&self) -> u32 {
+        self.sector_2_time_minutes as u32 * 60_000 + self.sector_2_time as u32
+    }
+}
+
 /// The lap data packet gives details of all the cars in the session.
 ///
 /// Frequency: Rate as specified in menus
@@ -129,3 +197,121 @@ pub struct PacketLapData {
     /// Index of Rival car in time trial (if available)
     pub time_trial_rival_car_idx: Option<u8>,
 }
+
+impl PacketLapData {
+    /// Lap data of the player's car, or `None` if there is no player car (e.g. when spectating).
+    pub fn player_data(&self) -> Option<&LapData> {
+        self.lap_data.get(self.header.player_car_index as usize)
+    }
+
+    /// Cars sorted by race position, with their car index. Cars that are no longer racing (e.g.
+    /// retired or disqualified) come last, and unused entries are skipped.
+    ///
+    /// Cars can briefly share a position while the positions are updated, they are then sorted by
+    /// car index.
+    pub fn by_position(&self) -> Vec<(usize, &LapData)> {
+        let mut cars: Vec<(usize, &LapData)> = self
+            .lap_data
+            .iter()
+            .enumerate()
+            .filter(|(_, ld)| ld.result_status.is_valid())
+            .collect();
+
+        cars.sort_by_key(|(idx, ld)| {
+            let racing = matches!(
+                ld.result_status,
+                ResultStatus::Active | ResultStatus::Finished
+            );
+
+            (!racing, ld.car_position, *idx)
+        });
+
+        cars
+    }
+
+    /// Starting grid as `(grid position, car index)` pairs, sorted by grid position, or `None` if
+    /// the grid positions are unknown (e.g. outside of a race).
+    ///
+    /// The grid positions sent by the game are used rather than the race positions, so the grid
+    /// stays the same once the race has started. Retired or disqualified cars are included, they
+    /// were on the grid too.
+    pub fn grid_order(&self) -> Option<Vec<(u8, usize)>> {
+        let mut grid: Vec<(u8, usize)> = self
+            .lap_data
+            .iter()
+            .enumerate()
+            .filter(|(_, ld)| ld.result_status.is_valid() && ld.grid_position > 0)
+            .map(|(idx, ld)| (ld.grid_position, idx))
+            .collect();
+
+        if grid.is_empty() {
+            return None;
+        }
+
+        grid.sort_unstable();
+
+        Some(grid)
+    }
+}
+
+#[cfg(test)]
+mod test_invalidation_reason {
+    use crate::packet::event::{InfringementType, Penalty, PenaltyType};
+
+    use super::InvalidationReason;
+
+    fn penalty(penalty_type: PenaltyType, infringement_type: InfringementType) -> Penalty {
+        Penalty {
+            vehicle_idx: 0,
+            penalty_type,
+            infringement_type,
+            other_vehicle_idx: 255,
+            time: 255,
+            lap_num: 3,
+            places_gained: 255,
+        }
+    }
+
+    #[test]
+    fn test_from_penalty() {
+        assert_eq!(
+            InvalidationReason::from_penalty(&penalty(
+                PenaltyType::ThisLapInvalidated,
+                InfringementType::LapInvalidatedCornerCutting
+            )),
+            Some(InvalidationReason::CornerCutting)
+        );
+        assert_eq!(
+            InvalidationReason::from_penalty(&penalty(
+                PenaltyType::ThisAndNextLapInvalidated,
+                InfringementType::CornerCuttingRanWideGainedTimeMinor
+            )),
+            Some(InvalidationReason::Other(
+                InfringementType::CornerCuttingRanWideGainedTimeMinor
+            ))
+        );
+        assert_eq!(
+            InvalidationReason::from_penalty(&penalty(
+                PenaltyType::ThisLapInvalidatedWithoutReason,
+                InfringementType::LapInvalidatedRunningWide
+            )),
+            None
+        );
+        assert_eq!(
+            InvalidationReason::from_penalty(&penalty(
+                PenaltyType::Warning,
+                InfringementType::LapInvalidatedRunningWide
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(InvalidationReason::WallRiding.to_string(), "Wall riding");
+        assert_eq!(
+            InvalidationReason::Other(InfringementType::CornerCuttingGainedTime).to_string(),
+            InfringementType::CornerCuttingGainedTime.to_string()
+        );
+    }
+}
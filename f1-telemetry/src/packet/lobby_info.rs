@@ -4,7 +4,7 @@ use crate::packet::generic::{Nationality, Platform, Team};
 
 use super::header::PacketHeader;
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum ReadyStatus {
     #[default]
     NotReady,
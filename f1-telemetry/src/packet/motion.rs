@@ -9,7 +9,7 @@ use super::header::PacketHeader;
 /// N.B. For the normalised vectors below, to convert to float values divide by 32767.0f – 16-bit
 /// signed values are used to pack the data and on the assumption that direction values are always
 /// between -1.0f and 1.0f.
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct CarMotionData {
     /// World space X position (in m)
     pub world_position_x: f32,
@@ -99,3 +99,10 @@ pub struct PacketMotionData {
     /// Removed in F1 23. Replaced by [`MotionEx`](super::motion_ex::PacketMotionExData) packet.
     pub player_car_data: Option<PlayerCarData>,
 }
+
+impl PacketMotionData {
+    /// Motion data of the player's car, or `None` if there is no player car (e.g. when spectating).
+    pub fn player_data(&self) -> Option<&CarMotionData> {
+        self.motion_data.get(self.header.player_car_index as usize)
+    }
+}
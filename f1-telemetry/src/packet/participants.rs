@@ -170,7 +170,9 @@ pub struct ParticipantData {
     pub race_number: u8,
     /// Nationality of the driver.
     pub nationality: Nationality,
-    /// Name of participant.
+    /// Name of participant, decoded once when the packet is parsed.
+    ///
+    /// Invalid UTF-8 sequences in the name sent by the game are replaced with `U+FFFD`.
     pub name: String,
     /// The player's UDP setting. See [`Telemetry`].
     pub telemetry_access: Telemetry,
@@ -200,3 +202,11 @@ pub struct PacketParticipantsData {
     /// List of participants
     pub participants: Vec<ParticipantData>,
 }
+
+impl PacketParticipantsData {
+    /// Participant data of the player's car, or `None` if there is no player car (e.g. when
+    /// spectating).
+    pub fn player_data(&self) -> Option<&ParticipantData> {
+        self.participants.get(self.header.player_car_index as usize)
+    }
+}
@@ -1,6 +1,10 @@
+use std::fmt;
+
 use serde::Serialize;
 
 use crate::packet::generic::Flag;
+use crate::packet::UnpackError;
+use crate::utils::invalid_value;
 
 use super::generic::SessionType;
 use super::header::PacketHeader;
@@ -16,6 +20,33 @@ pub enum Weather {
     Storm,
 }
 
+impl Weather {
+    pub fn name(self) -> &'static str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::LightCloud => "Light Cloud",
+            Weather::Overcast => "Overcast",
+            Weather::LightRain => "Light Rain",
+            Weather::HeavyRain => "Heavy Rain",
+            Weather::Storm => "Storm",
+        }
+    }
+
+    /// Whether it is raining, which calls for intermediate or wet tyres
+    pub fn is_rain(self) -> bool {
+        matches!(
+            self,
+            Weather::LightRain | Weather::HeavyRain | Weather::Storm
+        )
+    }
+}
+
+impl fmt::Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum TemperatureChange {
     #[default]
@@ -62,8 +93,100 @@ pub enum Track {
     Unknown,
 }
 
+impl TryFrom<u8> for Track {
+    type Error = UnpackError;
+
+    /// Decodes a track ID, as sent in the session packet. Unknown tracks are sent as `-1`, i.e.
+    /// `255` when read as an unsigned byte.
+    ///
+    /// Unknown values are an [`UnpackError::InvalidValue`] error. They are only replaced by the
+    /// default variant while a packet is decoded by [`crate::packet::parse_packet_with`] in lossy
+    /// mode.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Track::Melbourne),
+            1 => Ok(Track::PaulRicard),
+            2 => Ok(Track::Shanghai),
+            3 => Ok(Track::Sakhir),
+            4 => Ok(Track::Catalunya),
+            5 => Ok(Track::Monaco),
+            6 => Ok(Track::Montreal),
+            7 => Ok(Track::Silverstone),
+            8 => Ok(Track::Hockenheim),
+            9 => Ok(Track::Hungaroring),
+            10 => Ok(Track::Spa),
+            11 => Ok(Track::Monza),
+            12 => Ok(Track::Singapore),
+            13 => Ok(Track::Suzuka),
+            14 => Ok(Track::AbuDhabi),
+            15 => Ok(Track::Texas),
+            16 => Ok(Track::Brazil),
+            17 => Ok(Track::Austria),
+            18 => Ok(Track::Sochi),
+            19 => Ok(Track::Mexico),
+            20 => Ok(Track::Baku),
+            21 => Ok(Track::SakhirShort),
+            22 => Ok(Track::SilverstoneShort),
+            23 => Ok(Track::TexasShort),
+            24 => Ok(Track::SuzukaShort),
+            25 => Ok(Track::Hanoi),
+            26 => Ok(Track::Zandvoort),
+            27 => Ok(Track::Imola),
+            28 => Ok(Track::Portimao),
+            29 => Ok(Track::Jeddah),
+            30 => Ok(Track::Miami),
+            31 => Ok(Track::LasVegas),
+            32 => Ok(Track::Losail),
+            255 => Ok(Track::Unknown),
+            _ => invalid_value("Track", value),
+        }
+    }
+}
+
 impl Track {
-    pub fn name<'a>(self) -> &'a str {
+    /// ID of the track in the session packet, the inverse of [`Track::try_from`]. IDs are stable
+    /// across games, e.g. to look up localized names.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            Track::Melbourne => 0,
+            Track::PaulRicard => 1,
+            Track::Shanghai => 2,
+            Track::Sakhir => 3,
+            Track::Catalunya => 4,
+            Track::Monaco => 5,
+            Track::Montreal => 6,
+            Track::Silverstone => 7,
+            Track::Hockenheim => 8,
+            Track::Hungaroring => 9,
+            Track::Spa => 10,
+            Track::Monza => 11,
+            Track::Singapore => 12,
+            Track::Suzuka => 13,
+            Track::AbuDhabi => 14,
+            Track::Texas => 15,
+            Track::Brazil => 16,
+            Track::Austria => 17,
+            Track::Sochi => 18,
+            Track::Mexico => 19,
+            Track::Baku => 20,
+            Track::SakhirShort => 21,
+            Track::SilverstoneShort => 22,
+            Track::TexasShort => 23,
+            Track::SuzukaShort => 24,
+            Track::Hanoi => 25,
+            Track::Zandvoort => 26,
+            Track::Imola => 27,
+            Track::Portimao => 28,
+            Track::Jeddah => 29,
+            Track::Miami => 30,
+            Track::LasVegas => 31,
+            Track::Losail => 32,
+            Track::Unknown => 255,
+        }
+    }
+
+    /// English name of the track
+    pub fn name(self) -> &'static str {
         match self {
             Track::Melbourne => "Melbourne Grand Prix Circuit",
             Track::PaulRicard => "Circuit Paul Ricard",
@@ -103,6 +226,12 @@ impl Track {
     }
 }
 
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum Formula {
     F1Modern,
@@ -114,6 +243,54 @@ pub enum Formula {
     Esports,
     F2_21,
     F1WorldCar,
+    Unknown,
+}
+
+impl Formula {
+    pub fn name(self) -> &'static str {
+        match self {
+            Formula::F1Modern => "F1 Modern",
+            Formula::F1Classic => "F1 Classic",
+            Formula::F2 => "F2",
+            Formula::F1Generic => "F1 Generic",
+            Formula::Beta => "Beta",
+            Formula::Supercars => "Supercars",
+            Formula::Esports => "Esports",
+            Formula::F2_21 => "F2 2021",
+            Formula::F1WorldCar => "F1 World",
+            Formula::Unknown => "[UNKNOWN]",
+        }
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl TryFrom<u8> for Formula {
+    type Error = UnpackError;
+
+    /// Decodes a formula ID, as sent in the session packet.
+    ///
+    /// Unknown values are an [`UnpackError::InvalidValue`] error. They are only replaced by the
+    /// default variant while a packet is decoded by [`crate::packet::parse_packet_with`] in lossy
+    /// mode.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Formula::F1Modern),
+            1 => Ok(Formula::F1Classic),
+            2 => Ok(Formula::F2),
+            3 => Ok(Formula::F1Generic),
+            4 => Ok(Formula::Beta),
+            5 => Ok(Formula::Supercars),
+            6 => Ok(Formula::Esports),
+            7 => Ok(Formula::F2_21),
+            8 => Ok(Formula::F1WorldCar),
+            _ => invalid_value("Formula", value),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
@@ -125,7 +302,7 @@ pub enum SafetyCar {
 }
 
 impl SafetyCar {
-    pub fn name<'a>(self) -> &'a str {
+    pub fn name(self) -> &'static str {
         match self {
             SafetyCar::None => "No Safety Car",
             SafetyCar::Virtual => "Virtual Safety Car",
@@ -134,6 +311,12 @@ impl SafetyCar {
     }
 }
 
+impl fmt::Display for SafetyCar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// Weather forecast
 #[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
 pub struct WeatherForecast {
@@ -175,7 +358,25 @@ pub struct MarshalZone {
     pub zone_flag: Flag,
 }
 
-#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize)]
+impl MarshalZone {
+    /// Marshal zone of `zones` that is `lap_distance` metres into a lap of `track_length` metres,
+    /// see [`PacketSessionData::marshal_zone_at`].
+    pub fn at(zones: &[MarshalZone], track_length: u16, lap_distance: f32) -> Option<&MarshalZone> {
+        if track_length == 0 {
+            return None;
+        }
+
+        let fraction = lap_distance / track_length as f32;
+
+        zones
+            .iter()
+            .rev()
+            .find(|z| z.zone_start <= fraction)
+            .or_else(|| zones.last())
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Serialize)]
 pub enum ForecastAccuracy {
     Perfect,
     Approximate,
@@ -183,7 +384,7 @@ pub enum ForecastAccuracy {
     Unknown,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum BrakingAssist {
     Off,
     Low,
@@ -191,24 +392,26 @@ pub enum BrakingAssist {
     High,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum GearboxAssist {
     Manual,
     ManualAndSuggestedGear,
     Automatic,
+    Unknown,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum DynamicRacingLine {
     Off,
     CornersOnly,
     Full,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum DynamicRacingLineType {
     TwoDimensions,
     ThreeDimensions,
+    Unknown,
 }
 
 /// Status of various driving assistances
@@ -234,7 +437,7 @@ pub struct DrivingAssists {
     pub dynamic_racing_line_type: DynamicRacingLineType,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum GameMode {
     EventMode,
     GrandPrix,
@@ -254,9 +457,10 @@ pub enum GameMode {
     Career23,
     Career23Online,
     Benchmark,
+    Unknown,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum RuleSet {
     PracticeAndQualifying,
     Race,
@@ -267,9 +471,10 @@ pub enum RuleSet {
     Drift,
     AverageSpeedZone,
     RivalDuel,
+    Unknown,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum SessionLength {
     None,
     VeryShort,
@@ -280,16 +485,18 @@ pub enum SessionLength {
     Full,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum SpeedUnits {
     MPH,
     KPH,
+    Unknown,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
 pub enum TemperatureUnits {
     Celsius,
     Fahrenheit,
+    Unknown,
 }
 
 /// The session packet includes details about the current session in progress
@@ -378,3 +585,112 @@ pub struct PacketSessionData {
     /// Number of red flags called during session. New in F1 23.
     pub num_red_flag_periods: Option<u8>,
 }
+
+/// A meaningful transition between two session packets, see [`PacketSessionData::changes_since`]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+pub enum SessionChange {
+    WeatherChanged { from: Weather, to: Weather },
+    SafetyCarChanged { from: SafetyCar, to: SafetyCar },
+    SessionTypeChanged { from: SessionType, to: SessionType },
+    TrackChanged { from: Track, to: Track },
+    AiDifficultyChanged { from: Option<u8>, to: Option<u8> },
+    GamePausedChanged { paused: bool },
+}
+
+impl PacketSessionData {
+    /// Time elapsed in the session in seconds. The time left can briefly exceed the duration at
+    /// the start of a session, the elapsed time is then 0.
+    pub fn elapsed_time(&self) -> u16 {
+        self.session_duration.saturating_sub(self.session_time_left)
+    }
+
+    /// Marshal zone `lap_distance` metres into the lap, `None` if the track length or the marshal
+    /// zones are unknown. Before the start of the first zone, the track is still in the last zone
+    /// of the lap.
+    pub fn marshal_zone_at(&self, lap_distance: f32) -> Option<&MarshalZone> {
+        MarshalZone::at(&self.marshal_zones, self.track_length, lap_distance)
+    }
+
+    /// Lists the transitions between `prev` and this packet, e.g. the weather turning to rain or
+    /// the safety car being deployed. Returns an empty list if nothing meaningful changed.
+    pub fn changes_since(&self, prev: &PacketSessionData) -> Vec<SessionChange> {
+        let mut changes = Vec::new();
+
+        if self.weather != prev.weather {
+            changes.push(SessionChange::WeatherChanged {
+                from: prev.weather,
+                to: self.weather,
+            });
+        }
+
+        if self.safety_car_status != prev.safety_car_status {
+            changes.push(SessionChange::SafetyCarChanged {
+                from: prev.safety_car_status,
+                to: self.safety_car_status,
+            });
+        }
+
+        if self.session_type != prev.session_type {
+            changes.push(SessionChange::SessionTypeChanged {
+                from: prev.session_type,
+                to: self.session_type,
+            });
+        }
+
+        if self.track != prev.track {
+            changes.push(SessionChange::TrackChanged {
+                from: prev.track,
+                to: self.track,
+            });
+        }
+
+        if self.ai_difficulty != prev.ai_difficulty {
+            changes.push(SessionChange::AiDifficultyChanged {
+                from: prev.ai_difficulty,
+                to: self.ai_difficulty,
+            });
+        }
+
+        if self.game_paused != prev.game_paused {
+            changes.push(SessionChange::GamePausedChanged {
+                paused: self.game_paused,
+            });
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod test_track {
+    use super::Track;
+
+    #[test]
+    fn test_track_from_id() {
+        assert_eq!(Track::try_from(0).unwrap(), Track::Melbourne);
+        assert_eq!(Track::try_from(10).unwrap(), Track::Spa);
+        assert_eq!(Track::try_from(26).unwrap(), Track::Zandvoort);
+        assert_eq!(Track::try_from(32).unwrap(), Track::Losail);
+        assert_eq!(Track::try_from(-1i8 as u8).unwrap(), Track::Unknown);
+        assert!(Track::try_from(33).is_err());
+    }
+
+    #[test]
+    fn test_track_display() {
+        assert_eq!(Track::Monaco.to_string(), Track::Monaco.name());
+        assert_eq!(format!("{}", Track::Monaco), "Circuit de Monaco");
+    }
+}
+
+#[cfg(test)]
+mod test_formula {
+    use super::Formula;
+
+    #[test]
+    fn test_formula_from_id() {
+        assert_eq!(Formula::try_from(0).unwrap(), Formula::F1Modern);
+        assert_eq!(Formula::try_from(3).unwrap(), Formula::F1Generic);
+        assert_eq!(Formula::try_from(8).unwrap(), Formula::F1WorldCar);
+        assert!(Formula::try_from(9).is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::io;
+
+use crate::packet::{Packet, UnpackError};
+
+/// A source of packets, so that applications can be written once for live telemetry
+/// ([`Stream`](crate::Stream)), recorded sessions
+/// ([`capture::FileSource`](crate::capture::FileSource)) and packets built in memory
+/// ([`VecSource`]).
+///
+/// The method is named `next_packet` rather than `next`, so that it does not shadow the inherent
+/// `next` methods of the sources, which keep their own return types.
+pub trait PacketSource {
+    /// Returns the next packet, or `None` once the source is exhausted. A live stream never ends.
+    fn next_packet(&mut self) -> impl Future<Output = Result<Option<Packet>, UnpackError>> + Send;
+
+    /// Tries to make the source usable again after [`UnpackError::is_fatal`] errors, e.g. by
+    /// re-creating a socket. Does nothing by default.
+    fn recover(&mut self) -> impl Future<Output = io::Result<()>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// Returns packets from memory in order, e.g. to test the logic of an application without a game
+/// or a capture file.
+#[derive(Debug, Clone, Default)]
+pub struct VecSource {
+    packets: VecDeque<Packet>,
+}
+
+impl VecSource {
+    pub fn new(packets: Vec<Packet>) -> VecSource {
+        VecSource {
+            packets: packets.into(),
+        }
+    }
+
+    /// Number of packets left to return.
+    pub fn len(&self) -> usize {
+        self.packets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packets.is_empty()
+    }
+}
+
+impl From<Vec<Packet>> for VecSource {
+    fn from(packets: Vec<Packet>) -> VecSource {
+        VecSource::new(packets)
+    }
+}
+
+impl FromIterator<Packet> for VecSource {
+    fn from_iter<T: IntoIterator<Item = Packet>>(iter: T) -> VecSource {
+        VecSource {
+            packets: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl PacketSource for VecSource {
+    async fn next_packet(&mut self) -> Result<Option<Packet>, UnpackError> {
+        Ok(self.packets.pop_front())
+    }
+}
@@ -0,0 +1,511 @@
+use std::future::Future;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use tokio::net::{ToSocketAddrs, UdpSocket};
+use tokio::runtime::Runtime;
+
+use crate::capture::Recorder;
+use crate::packet::{
+    parse_packet_with, peek_packet_type, Packet, PacketType, ParseOptions, UnpackError,
+};
+use crate::source::PacketSource;
+
+/// A packet, along with the time it was received at.
+///
+/// Unlike the session time of the packet header, these clocks keep running while the game is
+/// paused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Received {
+    pub packet: Packet,
+    /// Wall-clock time, to correlate packets with other sources of data
+    pub received_at: SystemTime,
+    /// Monotonic time, to measure the time elapsed between packets
+    pub instant: Instant,
+}
+
+/// Called with the raw datagram and the error when a packet cannot be parsed, see
+/// [`Stream::on_parse_error`].
+pub type ParseErrorHook = Box<dyn Fn(&[u8], &UnpackError) + Send + Sync>;
+
+/// Default number of consecutive datagrams dropped by [`Stream::set_skip_errors`]
+const DEFAULT_MAX_SKIPPED_ERRORS: u32 = 10;
+
+/// Address the received datagrams are re-sent to, see [`Stream::forward_to`].
+///
+/// Each target has its own unconnected socket: on Windows, the ICMP port unreachable message of a
+/// dead target is reported by the next read of the socket that sent the datagram, which must not
+/// be the socket receiving the packets of the game.
+struct ForwardTarget {
+    addr: SocketAddr,
+    socket: std::net::UdpSocket,
+    /// Whether the last datagram could not be sent, so that failures are only logged once
+    failing: AtomicBool,
+}
+
+impl ForwardTarget {
+    fn new(addr: SocketAddr) -> std::io::Result<ForwardTarget> {
+        let socket = match addr {
+            SocketAddr::V4(_) => std::net::UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?,
+            SocketAddr::V6(_) => std::net::UdpSocket::bind((Ipv6Addr::UNSPECIFIED, 0))?,
+        };
+        socket.set_nonblocking(true)?;
+
+        Ok(ForwardTarget {
+            addr,
+            socket,
+            failing: AtomicBool::new(false),
+        })
+    }
+
+    fn send(&self, data: &[u8]) {
+        match self.socket.send_to(data, self.addr) {
+            Ok(_) => {
+                if self.failing.swap(false, Ordering::Relaxed) {
+                    log::info!("Forwarding packets to {} again", self.addr);
+                }
+            }
+            Err(e) => {
+                if !self.failing.swap(true, Ordering::Relaxed) {
+                    log::warn!("Unable to forward packets to {}: {}", self.addr, e);
+                }
+            }
+        }
+    }
+}
+
+pub struct Stream {
+    socket: UdpSocket,
+    addr: SocketAddr,
+    recorder: Option<Recorder>,
+    forward_to: Vec<ForwardTarget>,
+    subscription: Option<Vec<PacketType>>,
+    read_timeout: Option<Duration>,
+    parse_options: ParseOptions,
+    on_parse_error: Option<ParseErrorHook>,
+    skip_errors: bool,
+    max_skipped_errors: u32,
+    /// Number of consecutive datagrams dropped since the last packet was returned
+    skipped_errors: AtomicU32,
+}
+
+impl Stream {
+    pub async fn new<T: ToSocketAddrs>(addr: T) -> std::io::Result<Stream> {
+        let socket = UdpSocket::bind(addr).await?;
+        let addr = socket.local_addr()?;
+
+        Ok(Stream {
+            socket,
+            addr,
+            recorder: None,
+            forward_to: Vec::new(),
+            subscription: None,
+            read_timeout: None,
+            parse_options: ParseOptions::default(),
+            on_parse_error: None,
+            skip_errors: false,
+            max_skipped_errors: DEFAULT_MAX_SKIPPED_ERRORS,
+            skipped_errors: AtomicU32::new(0),
+        })
+    }
+
+    /// Re-creates the socket on the address it was bound to, e.g. after the network interface
+    /// went down and [`UnpackError::is_fatal`] errors are returned.
+    pub async fn rebind(&mut self) -> std::io::Result<()> {
+        // The address must be released before binding it again. The placeholder is only kept if
+        // the new bind fails, in which case rebinding can be retried later.
+        let placeholder = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        drop(std::mem::replace(&mut self.socket, placeholder));
+
+        self.socket = UdpSocket::bind(self.addr).await?;
+
+        Ok(())
+    }
+
+    /// Write every received datagram to a capture file, which can be read back with
+    /// [`capture::FileSource`](crate::capture::FileSource). The file is flushed every
+    /// `flush_interval`, which must not be zero.
+    pub async fn record_to<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        flush_interval: Duration,
+    ) -> std::io::Result<()> {
+        self.recorder = Some(Recorder::create(path, flush_interval).await?);
+
+        Ok(())
+    }
+
+    /// Stops writing the received datagrams to the capture file, once the datagrams recorded so
+    /// far are written and the file is flushed. Returns the error that stopped the recording, if
+    /// any.
+    pub async fn stop_recording(&mut self) -> std::io::Result<()> {
+        match self.recorder.take() {
+            Some(recorder) => recorder.finish().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Re-sends every received datagram to `targets`, before it is parsed, so that other tools can
+    /// consume the feed the game sends to this stream only. Forwarding errors are logged once per
+    /// target until it is reachable again, and otherwise ignored: an unreachable target does not
+    /// affect the stream.
+    ///
+    /// Returns an error if the sockets used to forward the datagrams cannot be created.
+    pub fn forward_to(&mut self, targets: &[SocketAddr]) -> std::io::Result<()> {
+        self.forward_to = targets
+            .iter()
+            .map(|addr| ForwardTarget::new(*addr))
+            .collect::<std::io::Result<_>>()?;
+
+        Ok(())
+    }
+
+    /// Only parse and return packets of the given types, other packets are skipped.
+    pub fn subscribe(&mut self, packet_types: &[PacketType]) {
+        self.subscription = Some(packet_types.to_vec());
+    }
+
+    /// Parse and return packets of all types again.
+    pub fn unsubscribe(&mut self) {
+        self.subscription = None;
+    }
+
+    /// Maximum time to wait for a datagram in [`Stream::next`] and [`Stream::next_from`] before
+    /// returning an [`UnpackError::Io`] error of kind [`ErrorKind::TimedOut`]. `None` waits
+    /// forever.
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
+    }
+
+    /// Options used to decode the received packets, strict by default.
+    pub fn set_parse_options(&mut self, options: ParseOptions) {
+        self.parse_options = options;
+    }
+
+    /// Drops the datagrams that fail to parse, e.g. truncated on a congested network, and waits for
+    /// the next one instead of returning the error. The error is only returned after
+    /// [`Stream::set_max_skipped_errors`] consecutive datagrams were dropped. Socket errors are
+    /// always returned. Disabled by default.
+    pub fn set_skip_errors(&mut self, skip: bool) {
+        self.skip_errors = skip;
+        self.skipped_errors.store(0, Ordering::Relaxed);
+    }
+
+    /// Number of consecutive datagrams dropped before a parse error is returned, when
+    /// [`Stream::set_skip_errors`] is enabled. Defaults to 10.
+    pub fn set_max_skipped_errors(&mut self, max: u32) {
+        self.max_skipped_errors = max;
+    }
+
+    /// Passes the datagrams that fail to parse to `hook`, e.g. to dump them with
+    /// [`packet::hex_dump`](crate::packet::hex_dump) when a game patch changes the layout of the
+    /// packets. Socket errors are not reported, as there is no datagram then.
+    pub fn on_parse_error(&mut self, hook: impl Fn(&[u8], &UnpackError) + Send + Sync + 'static) {
+        self.on_parse_error = Some(Box::new(hook));
+    }
+
+    pub async fn next(&self) -> Result<Packet, UnpackError> {
+        self.receive().await.map(|(received, _)| received.packet)
+    }
+
+    pub async fn next_from(&self) -> Result<(Packet, SocketAddr), UnpackError> {
+        self.receive()
+            .await
+            .map(|(received, addr)| (received.packet, addr))
+    }
+
+    /// Same as [`Stream::next`], with the time the packet was received at.
+    pub async fn next_received(&self) -> Result<Received, UnpackError> {
+        self.receive().await.map(|(received, _)| received)
+    }
+
+    /// Returns the next packet if a datagram is ready to be read, or `None` without waiting.
+    ///
+    /// Readiness is tracked by the tokio runtime: a datagram is only seen once the runtime's I/O
+    /// driver has polled the socket since it arrived. This must be called from a runtime that is
+    /// driving I/O, e.g. a multi-threaded one, or after awaiting [`UdpSocket::readable`] on
+    /// [`Stream::socket`]. Otherwise `None` may be returned although a datagram is waiting.
+    pub fn try_next(&self) -> Result<Option<Packet>, UnpackError> {
+        let mut buf = [0; 2048]; // All packets fit in 2048 bytes
+
+        loop {
+            match self.socket.try_recv(&mut buf) {
+                Ok(len) => {
+                    self.record(&buf[..len]);
+                    self.forward(&buf[..len]);
+
+                    if self.is_subscribed(&buf[..len]) {
+                        let result = self.parse(len, &buf);
+
+                        if !self.skip(&result) {
+                            return result.map(Some);
+                        }
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return Ok(None),
+                Err(e) => return Err(UnpackError::Io(e)),
+            }
+        }
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Turns this stream into a [`futures_core::Stream`] of packets. It never ends.
+    #[cfg(feature = "futures")]
+    pub fn into_stream(self) -> PacketStream {
+        PacketStream {
+            next: Box::pin(next_owned(self)),
+        }
+    }
+
+    /// Waits for the next packet, timestamped as soon as its datagram is received, before it is
+    /// parsed or other datagrams are skipped.
+    async fn receive(&self) -> Result<(Received, SocketAddr), UnpackError> {
+        let mut buf = [0; 2048]; // All packets fit in 2048 bytes
+
+        loop {
+            let (len, addr) = self
+                .with_timeout(self.socket.recv_from(&mut buf))
+                .await
+                .map_err(UnpackError::Io)?;
+            let received_at = SystemTime::now();
+            let instant = Instant::now();
+
+            self.record(&buf[..len]);
+            self.forward(&buf[..len]);
+
+            if self.is_subscribed(&buf[..len]) {
+                let result = self.parse(len, &buf);
+
+                if !self.skip(&result) {
+                    let received = Received {
+                        packet: result?,
+                        received_at,
+                        instant,
+                    };
+
+                    return Ok((received, addr));
+                }
+            }
+        }
+    }
+
+    async fn with_timeout<T>(
+        &self,
+        f: impl Future<Output = std::io::Result<T>>,
+    ) -> std::io::Result<T> {
+        match self.read_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, f).await.unwrap_or_else(|_| {
+                Err(std::io::Error::new(
+                    ErrorKind::TimedOut,
+                    "Timed out waiting for a packet",
+                ))
+            }),
+            None => f.await,
+        }
+    }
+
+    fn parse(&self, len: usize, buf: &[u8]) -> Result<Packet, UnpackError> {
+        let result = parse_packet_with(len, buf, self.parse_options);
+
+        if let (Err(e), Some(hook)) = (&result, &self.on_parse_error) {
+            hook(&buf[..len], e);
+        }
+
+        result
+    }
+
+    /// Whether a parse error is dropped rather than returned, see [`Stream::set_skip_errors`].
+    fn skip(&self, result: &Result<Packet, UnpackError>) -> bool {
+        match result {
+            Err(UnpackError::Io(_)) => false,
+            Err(_) if self.skip_errors => {
+                let skipped = self.skipped_errors.fetch_add(1, Ordering::Relaxed) + 1;
+
+                if skipped > self.max_skipped_errors {
+                    self.skipped_errors.store(0, Ordering::Relaxed);
+                    false
+                } else {
+                    true
+                }
+            }
+            Err(_) => false,
+            Ok(_) => {
+                self.skipped_errors.store(0, Ordering::Relaxed);
+                false
+            }
+        }
+    }
+
+    /// Packets which type cannot be read are let through, so that the parsing error is reported.
+    fn is_subscribed(&self, data: &[u8]) -> bool {
+        match (&self.subscription, peek_packet_type(data)) {
+            (Some(packet_types), Ok(packet_type)) => packet_types.contains(&packet_type),
+            _ => true,
+        }
+    }
+
+    fn record(&self, data: &[u8]) {
+        if let Some(recorder) = &self.recorder {
+            recorder.record(data);
+        }
+    }
+
+    fn forward(&self, data: &[u8]) {
+        for target in &self.forward_to {
+            target.send(data);
+        }
+    }
+}
+
+impl PacketSource for Stream {
+    async fn next_packet(&mut self) -> Result<Option<Packet>, UnpackError> {
+        self.next().await.map(Some)
+    }
+
+    async fn recover(&mut self) -> std::io::Result<()> {
+        self.rebind().await
+    }
+}
+
+pub struct SyncStream {
+    stream: Stream,
+    rt: Runtime,
+}
+
+impl SyncStream {
+    pub fn new<T: ToSocketAddrs>(addr: T) -> std::io::Result<Self> {
+        let rt = Runtime::new()?;
+        let stream = rt.block_on(Stream::new(addr))?;
+
+        Ok(SyncStream { stream, rt })
+    }
+
+    pub fn record_to<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        flush_interval: Duration,
+    ) -> std::io::Result<()> {
+        self.rt
+            .block_on(self.stream.record_to(path, flush_interval))
+    }
+
+    pub fn stop_recording(&mut self) -> std::io::Result<()> {
+        self.rt.block_on(self.stream.stop_recording())
+    }
+
+    pub fn forward_to(&mut self, targets: &[SocketAddr]) -> std::io::Result<()> {
+        self.stream.forward_to(targets)
+    }
+
+    pub fn subscribe(&mut self, packet_types: &[PacketType]) {
+        self.stream.subscribe(packet_types)
+    }
+
+    pub fn unsubscribe(&mut self) {
+        self.stream.unsubscribe()
+    }
+
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.stream.set_read_timeout(timeout)
+    }
+
+    pub fn rebind(&mut self) -> std::io::Result<()> {
+        self.rt.block_on(self.stream.rebind())
+    }
+
+    pub fn set_parse_options(&mut self, options: ParseOptions) {
+        self.stream.set_parse_options(options)
+    }
+
+    pub fn set_skip_errors(&mut self, skip: bool) {
+        self.stream.set_skip_errors(skip)
+    }
+
+    pub fn set_max_skipped_errors(&mut self, max: u32) {
+        self.stream.set_max_skipped_errors(max)
+    }
+
+    pub fn on_parse_error(&mut self, hook: impl Fn(&[u8], &UnpackError) + Send + Sync + 'static) {
+        self.stream.on_parse_error(hook)
+    }
+
+    pub fn next(&self) -> Result<Packet, UnpackError> {
+        self.rt.block_on(self.stream.next())
+    }
+
+    /// Returns the next packet if a datagram is ready to be read, or `None` without waiting. The
+    /// I/O of the stream is driven by its runtime in the background, so a datagram that just
+    /// arrived may take a moment to be seen, see [`Stream::try_next`].
+    pub fn try_next(&self) -> Result<Option<Packet>, UnpackError> {
+        self.stream.try_next()
+    }
+
+    /// Same as [`SyncStream::next`], with the time the packet was received at.
+    pub fn next_received(&self) -> Result<Received, UnpackError> {
+        self.rt.block_on(self.stream.next_received())
+    }
+
+    pub fn next_from(&self) -> Result<(Packet, SocketAddr), UnpackError> {
+        self.rt.block_on(self.stream.next_from())
+    }
+
+    /// Returns an iterator blocking until each packet is received.
+    pub fn packets(&self) -> Packets<'_> {
+        Packets { stream: self }
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        self.stream.socket()
+    }
+}
+
+/// Blocking iterator over the packets received by a [`SyncStream`]. It never ends.
+pub struct Packets<'a> {
+    stream: &'a SyncStream,
+}
+
+impl Iterator for Packets<'_> {
+    type Item = Result<Packet, UnpackError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.stream.next())
+    }
+}
+
+#[cfg(feature = "futures")]
+type NextPacket =
+    std::pin::Pin<Box<dyn Future<Output = (Stream, Result<Packet, UnpackError>)> + Send>>;
+
+/// Asynchronous stream over the packets received by a [`Stream`], see [`Stream::into_stream`].
+#[cfg(feature = "futures")]
+pub struct PacketStream {
+    next: NextPacket,
+}
+
+#[cfg(feature = "futures")]
+async fn next_owned(stream: Stream) -> (Stream, Result<Packet, UnpackError>) {
+    let result = stream.next().await;
+
+    (stream, result)
+}
+
+#[cfg(feature = "futures")]
+impl futures_core::Stream for PacketStream {
+    type Item = Result<Packet, UnpackError>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let (stream, result) = futures_core::ready!(self.next.as_mut().poll(cx));
+        self.next = Box::pin(next_owned(stream));
+
+        std::task::Poll::Ready(Some(result))
+    }
+}
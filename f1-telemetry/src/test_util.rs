@@ -0,0 +1,409 @@
+//! Builders to fabricate packets in tests, without having to go through the UDP format.
+//!
+//! Every builder starts from a plausible F1 23 packet, so only the fields relevant to a test
+//! need to be set:
+//!
+//! ```
+//! use f1_telemetry::packet::session::Weather;
+//! use f1_telemetry::test_util::PacketSessionDataBuilder;
+//!
+//! let session = PacketSessionDataBuilder::default()
+//!     .with_weather(Weather::HeavyRain)
+//!     .with_total_laps(50)
+//!     .build();
+//!
+//! assert_eq!(session.total_laps, 50);
+//! ```
+use crate::packet::car_status::{CarStatusData, PacketCarStatusData};
+use crate::packet::car_telemetry::{CarTelemetryData, MFDPanel, PacketCarTelemetryData};
+use crate::packet::generic::{ResultStatus, SessionType};
+use crate::packet::header::PacketHeader;
+use crate::packet::lap::{DriverStatus, LapData, PacketLapData, PitStatus, Sector};
+use crate::packet::motion::{CarMotionData, PacketMotionData};
+use crate::packet::session::{
+    ForecastAccuracy, Formula, MarshalZone, PacketSessionData, SafetyCar, Track, Weather,
+    WeatherForecast, WeatherForecastSample,
+};
+use crate::packet::PacketType;
+
+macro_rules! setters {
+    ($target:ident { $($setter:ident => $field:ident: $ty:ty),* $(,)? }) => {
+        $(
+            pub fn $setter(mut self, $field: $ty) -> Self {
+                self.$target.$field = $field;
+                self
+            }
+        )*
+    };
+}
+
+pub struct PacketHeaderBuilder {
+    header: PacketHeader,
+}
+
+impl Default for PacketHeaderBuilder {
+    fn default() -> Self {
+        Self {
+            header: PacketHeader {
+                packet_format: 2023,
+                game_year: 23,
+                game_major_version: 1,
+                game_minor_version: 0,
+                packet_version: 1,
+                packet_type: PacketType::Motion,
+                session_uid: 1,
+                session_time: 0,
+                frame_identifier: 0,
+                overall_frame_identifier: Some(0),
+                player_car_index: 0,
+                secondary_player_car_index: None,
+            },
+        }
+    }
+}
+
+impl PacketHeaderBuilder {
+    /// Header of a packet sent `session_time` milliseconds into the session `session_uid`.
+    pub fn in_session(session_uid: u64, session_time: u32) -> Self {
+        Self::default()
+            .with_session_uid(session_uid)
+            .with_session_time(session_time)
+    }
+
+    setters!(header {
+        with_packet_format => packet_format: u16,
+        with_packet_type => packet_type: PacketType,
+        with_session_uid => session_uid: u64,
+        with_session_time => session_time: u32,
+        with_frame_identifier => frame_identifier: u32,
+        with_overall_frame_identifier => overall_frame_identifier: Option<u32>,
+        with_player_car_index => player_car_index: u8,
+    });
+
+    pub fn build(self) -> PacketHeader {
+        self.header
+    }
+}
+
+pub struct PacketSessionDataBuilder {
+    packet: PacketSessionData,
+}
+
+impl Default for PacketSessionDataBuilder {
+    fn default() -> Self {
+        Self {
+            packet: PacketSessionData {
+                header: PacketHeaderBuilder::default().build(),
+                weather: Weather::Clear,
+                track_temperature: 30,
+                air_temperature: 24,
+                total_laps: 5,
+                track_length: 5303,
+                session_type: SessionType::Race,
+                track: Track::Melbourne,
+                formula: Formula::F1Modern,
+                session_time_left: 7200,
+                session_duration: 7200,
+                pit_speed_limit: 80,
+                game_paused: false,
+                is_spectating: false,
+                spectator_car_index: 255,
+                sli_pro_native_support: false,
+                num_marshal_zones: 0,
+                marshal_zones: Vec::new(),
+                safety_car_status: SafetyCar::None,
+                network_game: false,
+                weather_forecast: Some(WeatherForecast::default()),
+                ai_difficulty: None,
+                season_identifier: None,
+                weekend_identifier: None,
+                session_identifier: None,
+                pit_stop_window_ideal_lap: None,
+                pit_stop_window_latest_lap: None,
+                pit_stop_rejoin_position: None,
+                driving_assists: None,
+                game_mode: None,
+                rule_set: None,
+                time_of_day: None,
+                session_length: None,
+                speed_units_lead_player: None,
+                temperature_units_lead_player: None,
+                speed_units_secondary_player: None,
+                temperature_units_secondary_player: None,
+                num_safety_car_periods: None,
+                num_virtual_safety_car_periods: None,
+                num_red_flag_periods: None,
+            },
+        }
+    }
+}
+
+impl PacketSessionDataBuilder {
+    setters!(packet {
+        with_header => header: PacketHeader,
+        with_weather => weather: Weather,
+        with_track_temperature => track_temperature: i8,
+        with_air_temperature => air_temperature: i8,
+        with_total_laps => total_laps: u8,
+        with_track_length => track_length: u16,
+        with_session_type => session_type: SessionType,
+        with_track => track: Track,
+        with_formula => formula: Formula,
+        with_session_time_left => session_time_left: u16,
+        with_session_duration => session_duration: u16,
+        with_safety_car_status => safety_car_status: SafetyCar,
+        with_is_spectating => is_spectating: bool,
+        with_spectator_car_index => spectator_car_index: u8,
+        with_network_game => network_game: bool,
+        with_game_paused => game_paused: bool,
+        with_ai_difficulty => ai_difficulty: Option<u8>,
+    });
+
+    pub fn with_marshal_zones(mut self, zones: Vec<MarshalZone>) -> Self {
+        self.packet.num_marshal_zones = zones.len() as u8;
+        self.packet.marshal_zones = zones;
+        self
+    }
+
+    pub fn with_weather_forecast(
+        mut self,
+        samples: Vec<WeatherForecastSample>,
+        accuracy: ForecastAccuracy,
+    ) -> Self {
+        self.packet.weather_forecast = Some(WeatherForecast {
+            number_of_samples: samples.len() as u8,
+            samples,
+            accuracy,
+        });
+        self
+    }
+
+    pub fn build(mut self) -> PacketSessionData {
+        self.packet.header.packet_type = PacketType::Session;
+        self.packet
+    }
+}
+
+pub struct LapDataBuilder {
+    lap_data: LapData,
+}
+
+impl Default for LapDataBuilder {
+    fn default() -> Self {
+        Self {
+            lap_data: LapData {
+                car_position: 1,
+                current_lap_num: 1,
+                grid_position: 1,
+                pit_status: PitStatus::None,
+                sector: Sector::Sector1,
+                driver_status: DriverStatus::FlyingLap,
+                result_status: ResultStatus::Active,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl LapDataBuilder {
+    setters!(lap_data {
+        with_last_lap_time => last_lap_time: u32,
+        with_current_lap_time => current_lap_time: u32,
+        with_sector_1_time => sector_1_time: u16,
+        with_sector_1_time_minutes => sector_1_time_minutes: u8,
+        with_sector_2_time => sector_2_time: u16,
+        with_sector_2_time_minutes => sector_2_time_minutes: u8,
+        with_delta_to_car_in_front => delta_to_car_in_front: u16,
+        with_delta_to_race_leader => delta_to_race_leader: u16,
+        with_best_lap_time => best_lap_time: u32,
+        with_best_lap_num => best_lap_num: u8,
+        with_lap_distance => lap_distance: f32,
+        with_total_distance => total_distance: f32,
+        with_car_position => car_position: u8,
+        with_current_lap_num => current_lap_num: u8,
+        with_safety_car_delta => safety_car_delta: f32,
+        with_pit_status => pit_status: PitStatus,
+        with_number_pit_stops => number_pit_stops: u8,
+        with_sector => sector: Sector,
+        with_current_lap_invalid => current_lap_invalid: bool,
+        with_penalties => penalties: u8,
+        with_total_warnings => total_warnings: u8,
+        with_corner_cutting_warnings => corner_cutting_warnings: u8,
+        with_number_unserved_drive_through => number_unserved_drive_through: u8,
+        with_number_unserved_stop_go => number_unserved_stop_go: u8,
+        with_grid_position => grid_position: u8,
+        with_driver_status => driver_status: DriverStatus,
+        with_result_status => result_status: ResultStatus,
+        with_pit_lane_timer_active => pit_lane_timer_active: bool,
+        with_pit_lane_time_in_lane => pit_lane_time_in_lane: u16,
+        with_pit_stop_time => pit_stop_time: u16,
+        with_pit_stop_should_serve_penalty => pit_stop_should_serve_penalty: bool,
+    });
+
+    pub fn build(self) -> LapData {
+        self.lap_data
+    }
+}
+
+pub struct PacketLapDataBuilder {
+    packet: PacketLapData,
+}
+
+impl Default for PacketLapDataBuilder {
+    fn default() -> Self {
+        Self {
+            packet: PacketLapData {
+                header: PacketHeaderBuilder::default().build(),
+                lap_data: Vec::new(),
+                time_trial_personal_best_car_idx: None,
+                time_trial_rival_car_idx: None,
+            },
+        }
+    }
+}
+
+impl PacketLapDataBuilder {
+    setters!(packet {
+        with_header => header: PacketHeader,
+        with_lap_data => lap_data: Vec<LapData>,
+        with_time_trial_personal_best_car_idx => time_trial_personal_best_car_idx: Option<u8>,
+        with_time_trial_rival_car_idx => time_trial_rival_car_idx: Option<u8>,
+    });
+
+    /// Appends a car to the lap data, its index being the number of cars added before it.
+    pub fn with_car(mut self, lap_data: LapData) -> Self {
+        self.packet.lap_data.push(lap_data);
+        self
+    }
+
+    /// Appends a car for each item of `cars`, set up by `car` from the default lap data:
+    ///
+    /// ```
+    /// use f1_telemetry::test_util::PacketLapDataBuilder;
+    ///
+    /// let packet = PacketLapDataBuilder::default()
+    ///     .with_cars([2, 1], |car, position| car.with_car_position(position))
+    ///     .build();
+    ///
+    /// assert_eq!(packet.lap_data[1].car_position, 1);
+    /// ```
+    pub fn with_cars<T>(
+        self,
+        cars: impl IntoIterator<Item = T>,
+        car: impl Fn(LapDataBuilder, T) -> LapDataBuilder,
+    ) -> Self {
+        cars.into_iter().fold(self, |builder, item| {
+            builder.with_car(car(LapDataBuilder::default(), item).build())
+        })
+    }
+
+    pub fn build(mut self) -> PacketLapData {
+        self.packet.header.packet_type = PacketType::LapData;
+        self.packet
+    }
+}
+
+pub struct PacketCarStatusDataBuilder {
+    packet: PacketCarStatusData,
+}
+
+impl Default for PacketCarStatusDataBuilder {
+    fn default() -> Self {
+        Self {
+            packet: PacketCarStatusData {
+                header: PacketHeaderBuilder::default().build(),
+                car_status_data: Vec::new(),
+            },
+        }
+    }
+}
+
+impl PacketCarStatusDataBuilder {
+    setters!(packet {
+        with_header => header: PacketHeader,
+        with_car_status_data => car_status_data: Vec<CarStatusData>,
+    });
+
+    /// Appends a car to the car statuses, its index being the number of cars added before it.
+    pub fn with_car(mut self, car_status: CarStatusData) -> Self {
+        self.packet.car_status_data.push(car_status);
+        self
+    }
+
+    pub fn build(mut self) -> PacketCarStatusData {
+        self.packet.header.packet_type = PacketType::CarStatus;
+        self.packet
+    }
+}
+
+pub struct PacketCarTelemetryDataBuilder {
+    packet: PacketCarTelemetryData,
+}
+
+impl Default for PacketCarTelemetryDataBuilder {
+    fn default() -> Self {
+        Self {
+            packet: PacketCarTelemetryData {
+                header: PacketHeaderBuilder::default().build(),
+                car_telemetry_data: Vec::new(),
+                button_status: None,
+                mfd_panel: MFDPanel::Closed,
+                secondary_player_mfd_panel: MFDPanel::NotSet,
+                suggested_gear: Some(0),
+            },
+        }
+    }
+}
+
+impl PacketCarTelemetryDataBuilder {
+    setters!(packet {
+        with_header => header: PacketHeader,
+        with_car_telemetry_data => car_telemetry_data: Vec<CarTelemetryData>,
+        with_mfd_panel => mfd_panel: MFDPanel,
+    });
+
+    /// Appends a car to the telemetry, its index being the number of cars added before it.
+    pub fn with_car(mut self, car_telemetry: CarTelemetryData) -> Self {
+        self.packet.car_telemetry_data.push(car_telemetry);
+        self
+    }
+
+    pub fn build(mut self) -> PacketCarTelemetryData {
+        self.packet.header.packet_type = PacketType::CarTelemetry;
+        self.packet
+    }
+}
+
+pub struct PacketMotionDataBuilder {
+    packet: PacketMotionData,
+}
+
+impl Default for PacketMotionDataBuilder {
+    fn default() -> Self {
+        Self {
+            packet: PacketMotionData {
+                header: PacketHeaderBuilder::default().build(),
+                motion_data: Vec::new(),
+                player_car_data: None,
+            },
+        }
+    }
+}
+
+impl PacketMotionDataBuilder {
+    setters!(packet {
+        with_header => header: PacketHeader,
+        with_motion_data => motion_data: Vec<CarMotionData>,
+    });
+
+    /// Appends a car to the motion data, its index being the number of cars added before it.
+    pub fn with_car(mut self, motion: CarMotionData) -> Self {
+        self.packet.motion_data.push(motion);
+        self
+    }
+
+    pub fn build(mut self) -> PacketMotionData {
+        self.packet.header.packet_type = PacketType::Motion;
+        self.packet
+    }
+}
@@ -1,9 +1,232 @@
+use std::cell::Cell;
+use std::io::{self, BufRead, Read};
+
+use bincode::{BincodeRead, DefaultOptions, Deserializer, Options};
+use serde::de::{DeserializeOwned, Visitor};
+
+use crate::packet::car_status::{ERSDeployMode, FuelMix, TractionControl, DRS};
+use crate::packet::car_telemetry::{MFDPanel, SurfaceType};
+use crate::packet::generic::{
+    Nationality, Platform, ResultStatus, SessionType, Team, TyreCompound, TyreCompoundVisual,
+};
+use crate::packet::lap::{DriverStatus, PitStatus, Sector};
+use crate::packet::lobby_info::ReadyStatus;
+use crate::packet::participants::{Driver, Telemetry};
+use crate::packet::session::{
+    BrakingAssist, DynamicRacingLine, DynamicRacingLineType, ForecastAccuracy, Formula, GameMode,
+    GearboxAssist, RuleSet, SafetyCar, SessionLength, SpeedUnits, TemperatureChange,
+    TemperatureUnits, Track, Weather,
+};
 use crate::packet::UnpackError;
 
-pub(crate) fn unpack_string(chars: &[u8]) -> Result<String, UnpackError> {
-    match std::str::from_utf8(chars) {
-        Ok(v) => Ok(v.trim_end_matches(char::from(0)).to_string()),
-        Err(e) => Err(UnpackError(format!("Error decoding name: {}", e))),
+thread_local! {
+    /// Set while parsing a packet in lossy mode, see [`crate::packet::ParseOptions`]. This avoids
+    /// passing the options down to every unpack function of every format.
+    static LOSSY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with unknown enum values coerced to defaults (`lossy`) or reported as errors.
+pub(crate) fn with_lossy_values<R>(lossy: bool, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous mode when dropped, even if `f` panics.
+    struct Restore(bool);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            LOSSY.with(|l| l.set(self.0));
+        }
+    }
+
+    let _restore = Restore(LOSSY.with(|l| l.replace(lossy)));
+
+    f()
+}
+
+/// Enums parsed from the packets, with the variant lossy parsing falls back to for values unknown
+/// to this library, see [`crate::packet::ParseOptions`].
+pub(crate) trait UnknownValue: Sized {
+    /// Variant standing for an unknown value, e.g. `Unknown` or `Invalid`. `None` when every
+    /// variant is a real value, so that a corrupt byte is not turned into believable data: such
+    /// fields are parsed strictly in lossy mode too.
+    const UNKNOWN: Option<Self>;
+}
+
+macro_rules! unknown_values {
+    ($($ty:ty => $unknown:expr),* $(,)?) => {
+        $(
+            impl UnknownValue for $ty {
+                const UNKNOWN: Option<Self> = $unknown;
+            }
+        )*
+    };
+}
+
+unknown_values! {
+    Nationality => Some(Nationality::Invalid),
+    Platform => Some(Platform::Unknown),
+    ResultStatus => Some(ResultStatus::Invalid),
+    SessionType => Some(SessionType::Unknown),
+    Team => Some(Team::Unknown),
+    TyreCompound => Some(TyreCompound::Invalid),
+    TyreCompoundVisual => Some(TyreCompoundVisual::Invalid),
+    DRS => Some(DRS::Unknown),
+    SurfaceType => Some(SurfaceType::Unknown),
+    Driver => Some(Driver::Unknown),
+    DynamicRacingLineType => Some(DynamicRacingLineType::Unknown),
+    ForecastAccuracy => Some(ForecastAccuracy::Unknown),
+    Formula => Some(Formula::Unknown),
+    GameMode => Some(GameMode::Unknown),
+    GearboxAssist => Some(GearboxAssist::Unknown),
+    RuleSet => Some(RuleSet::Unknown),
+    SpeedUnits => Some(SpeedUnits::Unknown),
+    TemperatureUnits => Some(TemperatureUnits::Unknown),
+    Track => Some(Track::Unknown),
+}
+
+// Every variant of these is a value sent by the game
+unknown_values! {
+    ERSDeployMode => None,
+    FuelMix => None,
+    TractionControl => None,
+    MFDPanel => None,
+    DriverStatus => None,
+    PitStatus => None,
+    Sector => None,
+    ReadyStatus => None,
+    Telemetry => None,
+    BrakingAssist => None,
+    DynamicRacingLine => None,
+    SafetyCar => None,
+    SessionLength => None,
+    TemperatureChange => None,
+    Weather => None,
+}
+
+/// Handles a value that does not map to any variant of `T`: an [`UnpackError::InvalidValue`]
+/// error, or [`UnknownValue::UNKNOWN`] with a warning when parsing in lossy mode.
+pub(crate) fn invalid_value<T: UnknownValue>(
+    field: &'static str,
+    value: impl Into<i64>,
+) -> Result<T, UnpackError> {
+    let value = value.into();
+
+    match T::UNKNOWN {
+        Some(unknown) if LOSSY.with(Cell::get) => {
+            log::warn!("Invalid {} value: {}, using an unknown value", field, value);
+            Ok(unknown)
+        }
+        _ => Err(UnpackError::InvalidValue { field, value }),
+    }
+}
+
+/// Decodes a NUL terminated string sent by the game.
+///
+/// Anything after the first NUL is padding and is ignored, and invalid UTF-8 sequences are
+/// replaced rather than failing the whole packet.
+pub(crate) fn unpack_string(chars: &[u8]) -> String {
+    let end = chars.iter().position(|c| *c == 0).unwrap_or(chars.len());
+
+    String::from_utf8_lossy(&chars[..end]).into_owned()
+}
+
+/// Collects the elements of a packet unpacked by an iterator, stopping at the first error.
+///
+/// `collect::<Result<Vec<_>, _>>()` cannot tell how many elements will be unpacked, and grows the
+/// list as they come, reallocating it several times for the 20 or 22 cars of a packet. The list is
+/// allocated once here, the unpacking iterators having an exact length.
+pub(crate) trait CollectUnpacked<T>:
+    Iterator<Item = Result<T, UnpackError>> + Sized
+{
+    fn collect_unpacked(self) -> Result<Vec<T>, UnpackError> {
+        let mut items = Vec::with_capacity(self.size_hint().0);
+
+        for item in self {
+            items.push(item?);
+        }
+
+        Ok(items)
+    }
+}
+
+impl<T, I: Iterator<Item = Result<T, UnpackError>>> CollectUnpacked<T> for I {}
+
+/// Deserializes a value from the bytes buffered by `reader`, and consumes them.
+///
+/// This is the same as `bincode::deserialize_from`, but reads directly from the buffered slice
+/// instead of going through `Read` for every field. The parsers read from a `Cursor` over the
+/// whole packet, so the buffer always holds the rest of the packet.
+pub(crate) fn deserialize<T: DeserializeOwned, R: BufRead>(
+    reader: &mut R,
+) -> Result<T, UnpackError> {
+    let buf = reader.fill_buf()?;
+    let mut rest = buf;
+
+    let options = DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes();
+    let value = T::deserialize(&mut Deserializer::with_bincode_read(
+        SliceRead(&mut rest),
+        options,
+    ))?;
+
+    let consumed = buf.len() - rest.len();
+    reader.consume(consumed);
+
+    Ok(value)
+}
+
+/// Reads from a slice, advancing it past the bytes read.
+struct SliceRead<'a, 'b>(&'b mut &'a [u8]);
+
+impl SliceRead<'_, '_> {
+    #[inline(always)]
+    fn take(&mut self, length: usize) -> io::Result<&[u8]> {
+        if length > self.0.len() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let (read, rest) = self.0.split_at(length);
+        *self.0 = rest;
+
+        Ok(read)
+    }
+}
+
+impl Read for SliceRead<'_, '_> {
+    #[inline(always)]
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        out.copy_from_slice(self.take(out.len())?);
+
+        Ok(out.len())
+    }
+
+    #[inline(always)]
+    fn read_exact(&mut self, out: &mut [u8]) -> io::Result<()> {
+        self.read(out).map(|_| ())
+    }
+}
+
+impl<'de> BincodeRead<'de> for SliceRead<'_, '_> {
+    fn forward_read_str<V: Visitor<'de>>(
+        &mut self,
+        length: usize,
+        visitor: V,
+    ) -> bincode::Result<V::Value> {
+        match std::str::from_utf8(self.take(length)?) {
+            Ok(s) => visitor.visit_str(s),
+            Err(e) => Err(bincode::ErrorKind::InvalidUtf8Encoding(e).into()),
+        }
+    }
+
+    fn get_byte_buffer(&mut self, length: usize) -> bincode::Result<Vec<u8>> {
+        Ok(self.take(length)?.to_vec())
+    }
+
+    fn forward_read_bytes<V: Visitor<'de>>(
+        &mut self,
+        length: usize,
+        visitor: V,
+    ) -> bincode::Result<V::Value> {
+        visitor.visit_bytes(self.take(length)?)
     }
 }
 
@@ -12,17 +235,18 @@ pub(crate) fn seconds_to_millis(seconds: f64) -> u32 {
     (seconds * 1000.0).floor() as u32
 }
 
+/// Trailing bytes are allowed and ignored, as some relays pad the datagrams they forward.
 pub(crate) fn assert_packet_size(
     actual_size: usize,
     expected_size: usize,
 ) -> Result<(), UnpackError> {
-    if actual_size == expected_size {
+    if actual_size >= expected_size {
         Ok(())
     } else {
-        Err(UnpackError(format!(
-            "Invalid packet size: {} bytes (expected {} bytes)",
-            actual_size, expected_size
-        )))
+        Err(UnpackError::InvalidSize {
+            got: actual_size,
+            expected: expected_size,
+        })
     }
 }
 
@@ -33,10 +257,43 @@ pub(crate) fn assert_packet_at_least_size(
     if actual_size >= minimum_size {
         Ok(())
     } else {
-        Err(UnpackError(format!(
-            "Packet too small: {} bytes (minimum: {} bytes)",
-            actual_size, minimum_size
-        )))
+        Err(UnpackError::TooSmall {
+            got: actual_size,
+            expected: minimum_size,
+        })
+    }
+}
+
+/// Guards against corrupt counts that would index past the fixed-size arrays sent by the game.
+pub(crate) fn assert_count_at_most(
+    field: &'static str,
+    count: u8,
+    max: usize,
+) -> Result<(), UnpackError> {
+    if count as usize <= max {
+        Ok(())
+    } else {
+        Err(UnpackError::InvalidValue {
+            field,
+            value: count.into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_unpack_string {
+    use super::unpack_string;
+
+    #[test]
+    fn test_unpack_string_stops_at_nul() {
+        assert_eq!(unpack_string(b"HAMILTON\0\0\0"), "HAMILTON");
+        assert_eq!(unpack_string(b"SAINZ\0ALONSO\0"), "SAINZ");
+        assert_eq!(unpack_string(b"NORRIS"), "NORRIS");
+    }
+
+    #[test]
+    fn test_unpack_string_replaces_invalid_utf8() {
+        assert_eq!(unpack_string(b"P\xc3\xa9rez\xff\0"), "P\u{e9}rez\u{fffd}");
     }
 }
 
@@ -51,3 +308,35 @@ mod test_seconds_to_millis {
         assert_eq!(seconds_to_millis(1.4999), 1499);
     }
 }
+
+#[cfg(test)]
+mod test_with_lossy_values {
+    use std::panic;
+
+    use crate::packet::generic::Team;
+    use crate::packet::session::Weather;
+
+    use super::{invalid_value, with_lossy_values};
+
+    #[test]
+    fn test_with_lossy_values_restores_the_mode() {
+        let value = with_lossy_values(true, || invalid_value::<Team>("Team", 42));
+        assert_eq!(value.unwrap(), Team::Unknown);
+
+        assert!(invalid_value::<Team>("Team", 42).is_err());
+    }
+
+    #[test]
+    fn test_with_lossy_values_restores_the_mode_on_panic() {
+        let result = panic::catch_unwind(|| with_lossy_values(true, || panic!("decoding failed")));
+        assert!(result.is_err());
+
+        assert!(invalid_value::<Team>("Team", 42).is_err());
+    }
+
+    #[test]
+    fn test_values_without_unknown_variant_stay_strict() {
+        let value = with_lossy_values(true, || invalid_value::<Weather>("Weather", 42));
+        assert!(value.is_err());
+    }
+}
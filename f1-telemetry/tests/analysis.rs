@@ -0,0 +1,986 @@
+use f1_telemetry::analysis::{
+    drs_available_here, weather_change, Battle, DistanceHistory, FollowedDriver, FrameMonitor,
+    LapTelemetry, LapTelemetrySummary, LeaderGap, LeaderGaps, Overtake, ParticipantId,
+    ParticipantsChange, ParticipantsTracker, PersonalBest, PersonalBests, PositionChanges,
+    SessionBoundary, SessionStatus, SessionSummaryBuilder, SessionTracker, StintChange,
+    StintChangesExt, TimeTrial, TimeTrialInfo, TimeTrialLap, WeatherChange, DEFAULT_RAIN_THRESHOLD,
+};
+use f1_telemetry::packet::car_status::CarStatusData;
+use f1_telemetry::packet::car_telemetry::CarTelemetryData;
+use f1_telemetry::packet::final_classification::{
+    FinalClassification, PacketFinalClassificationData,
+};
+use f1_telemetry::packet::generic::{Flag, ResultStatus, SessionType, Team, TyreCompound};
+use f1_telemetry::packet::participants::{PacketParticipantsData, ParticipantData};
+use f1_telemetry::packet::session::{
+    MarshalZone, SafetyCar, Track, Weather, WeatherForecastSample,
+};
+use f1_telemetry::packet::session_history::{LapHistoryData, PacketSessionHistoryData};
+use f1_telemetry::packet::{Packet, PacketType};
+use f1_telemetry::test_util::{
+    LapDataBuilder, PacketCarStatusDataBuilder, PacketCarTelemetryDataBuilder, PacketHeaderBuilder,
+    PacketLapDataBuilder, PacketSessionDataBuilder,
+};
+
+fn car_status(session_uid: u64, compounds: &[TyreCompound]) -> Packet {
+    let mut builder = PacketCarStatusDataBuilder::default().with_header(
+        PacketHeaderBuilder::default()
+            .with_session_uid(session_uid)
+            .build(),
+    );
+
+    for &actual_tyre_compound in compounds {
+        builder = builder.with_car(CarStatusData {
+            actual_tyre_compound,
+            ..Default::default()
+        });
+    }
+
+    Packet::CarStatus(builder.build())
+}
+
+fn lap_data(session_uid: u64, laps: &[u8]) -> Packet {
+    Packet::LapData(
+        PacketLapDataBuilder::default()
+            .with_header(PacketHeaderBuilder::in_session(session_uid, 0).build())
+            .with_cars(laps, |car, &lap| car.with_current_lap_num(lap))
+            .build(),
+    )
+}
+
+#[test]
+fn test_stint_changes() {
+    let packets = [
+        lap_data(1, &[1, 1]),
+        car_status(1, &[TyreCompound::C3, TyreCompound::C4]),
+        lap_data(1, &[18, 17]),
+        car_status(1, &[TyreCompound::C3, TyreCompound::C4]),
+        car_status(1, &[TyreCompound::C2, TyreCompound::C4]),
+    ];
+
+    let changes: Vec<StintChange> = packets.iter().stint_changes().collect();
+
+    assert_eq!(
+        changes,
+        vec![
+            StintChange {
+                car_index: 0,
+                lap: 1,
+                from: None,
+                to: TyreCompound::C3,
+            },
+            StintChange {
+                car_index: 1,
+                lap: 1,
+                from: None,
+                to: TyreCompound::C4,
+            },
+            StintChange {
+                car_index: 0,
+                lap: 18,
+                from: Some(TyreCompound::C3),
+                to: TyreCompound::C2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_stint_changes_ignore_cars_without_data() {
+    let packets = vec![car_status(1, &[TyreCompound::Invalid, TyreCompound::Wet])];
+
+    let changes: Vec<StintChange> = packets.into_iter().stint_changes().collect();
+
+    assert_eq!(
+        changes,
+        vec![StintChange {
+            car_index: 1,
+            lap: 0,
+            from: None,
+            to: TyreCompound::Wet,
+        }]
+    );
+}
+
+#[test]
+fn test_stint_changes_start_over_on_new_session() {
+    let packets = [
+        car_status(1, &[TyreCompound::C3]),
+        car_status(2, &[TyreCompound::C3]),
+    ];
+
+    let changes: Vec<StintChange> = packets.iter().stint_changes().collect();
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[1].from, None);
+}
+
+#[test]
+fn test_stint_changes_ignore_interleaved_sessions() {
+    let packets = [
+        car_status(1, &[TyreCompound::C3]),
+        car_status(2, &[TyreCompound::C3]),
+        car_status(1, &[TyreCompound::C3]),
+        car_status(2, &[TyreCompound::C3]),
+        car_status(2, &[TyreCompound::C4]),
+    ];
+
+    let changes: Vec<StintChange> = packets.iter().stint_changes().collect();
+
+    assert_eq!(
+        changes.iter().map(|c| (c.from, c.to)).collect::<Vec<_>>(),
+        vec![
+            (None, TyreCompound::C3),
+            (None, TyreCompound::C3),
+            (Some(TyreCompound::C3), TyreCompound::C4),
+        ]
+    );
+}
+
+#[test]
+fn test_session_tracker_reports_boundaries() {
+    let mut tracker = SessionTracker::default();
+
+    let statuses: Vec<SessionStatus> = [1, 1, 2, 1, 2, 3]
+        .iter()
+        .map(|&uid| tracker.observe(&car_status(uid, &[])))
+        .collect();
+
+    assert_eq!(
+        statuses,
+        vec![
+            SessionStatus::Started(SessionBoundary {
+                previous: None,
+                session_uid: 1,
+            }),
+            SessionStatus::Current,
+            SessionStatus::Started(SessionBoundary {
+                previous: Some(1),
+                session_uid: 2,
+            }),
+            SessionStatus::Stale,
+            SessionStatus::Current,
+            SessionStatus::Started(SessionBoundary {
+                previous: Some(2),
+                session_uid: 3,
+            }),
+        ]
+    );
+    assert_eq!(tracker.session_uid(), Some(3));
+}
+
+#[test]
+fn test_session_tracker_resets_on_new_sessions() {
+    let mut tracker = SessionTracker::default();
+    let mut resets = 0;
+
+    let current: Vec<bool> = [1, 1, 2, 1, 2]
+        .iter()
+        .map(|&uid| tracker.observe_and_reset(&car_status(uid, &[]), || resets += 1))
+        .collect();
+
+    assert_eq!(current, vec![true, true, true, false, true]);
+    assert_eq!(resets, 2);
+}
+
+fn participants(network_ids: &[u8]) -> PacketParticipantsData {
+    PacketParticipantsData {
+        header: PacketHeaderBuilder::default().build(),
+        num_active_cars: network_ids.len() as u8,
+        participants: network_ids
+            .iter()
+            .map(|&id| ParticipantData {
+                network_id: Some(id),
+                ..Default::default()
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_participants_tracker_maps_indices() {
+    let mut tracker = ParticipantsTracker::default();
+
+    assert_eq!(
+        tracker.update(&participants(&[3, 7, 9])),
+        Some(ParticipantsChange {
+            old_to_new: vec![],
+            joined: vec![0, 1, 2],
+        })
+    );
+    assert_eq!(tracker.update(&participants(&[3, 7, 9])), None);
+
+    // 7 left, 12 joined and 9 moved up
+    assert_eq!(
+        tracker.update(&participants(&[3, 9, 12])),
+        Some(ParticipantsChange {
+            old_to_new: vec![Some(0), None, Some(1)],
+            joined: vec![2],
+        })
+    );
+    assert_eq!(tracker.car_index(&ParticipantId::Network(9)), Some(1));
+    assert_eq!(tracker.id(2), Some(&ParticipantId::Network(12)));
+}
+
+#[test]
+fn test_ai_drivers_are_identified_by_name() {
+    let ai = ParticipantData {
+        ai_controlled: true,
+        network_id: Some(255),
+        name: "HAMILTON".to_string(),
+        team: Team::Mercedes,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        ParticipantId::of(&ai),
+        ParticipantId::Driver { name, team: Team::Mercedes, .. } if name == "HAMILTON"
+    ));
+}
+
+fn telemetry(session_uid: u64, speed: u16, throttle: f32, brake: f32, engine_rpm: u16) -> Packet {
+    Packet::CarTelemetry(
+        PacketCarTelemetryDataBuilder::default()
+            .with_header(
+                PacketHeaderBuilder::default()
+                    .with_session_uid(session_uid)
+                    .build(),
+            )
+            .with_car(CarTelemetryData {
+                speed,
+                throttle,
+                brake,
+                engine_rpm,
+                ..Default::default()
+            })
+            .build(),
+    )
+}
+
+#[test]
+fn test_lap_telemetry_summarizes_each_lap() {
+    let mut lap_telemetry = LapTelemetry::default();
+
+    let packets = [
+        // Dropped, the lap is not known yet
+        telemetry(1, 350, 1.0, 0.0, 13000),
+        lap_data(1, &[1]),
+        telemetry(1, 330, 1.0, 0.0, 12500),
+        lap_data(1, &[2]),
+        telemetry(1, 200, 1.0, 0.0, 11000),
+        telemetry(1, 300, 0.5, 0.8, 12000),
+        telemetry(1, 150, 0.0, 1.0, 9000),
+        telemetry(1, 120, 0.5, 0.0, 8000),
+        telemetry(1, 100, 0.0, 0.5, 7000),
+        lap_data(1, &[2]),
+    ];
+
+    for packet in &packets {
+        assert_eq!(lap_telemetry.observe(packet), None);
+    }
+
+    let lap_2 = LapTelemetrySummary {
+        lap: 2,
+        samples: 5,
+        max_speed: 300,
+        avg_throttle: 0.4,
+        brake_applications: 2,
+        max_rpm: 12000,
+    };
+    assert_eq!(lap_telemetry.current(), Some(&lap_2));
+
+    assert_eq!(lap_telemetry.observe(&lap_data(1, &[3])), Some(lap_2));
+    assert_eq!(
+        lap_telemetry.current().map(|c| (c.lap, c.samples)),
+        Some((3, 0))
+    );
+
+    lap_telemetry.observe(&telemetry(1, 250, 1.0, 0.0, 10000));
+    let lap_3 = lap_telemetry.observe(&lap_data(1, &[4])).unwrap();
+    assert_eq!(
+        (lap_3.lap, lap_3.max_speed, lap_3.avg_throttle),
+        (3, 250, 1.0)
+    );
+}
+
+#[test]
+fn test_lap_telemetry_skips_the_lap_joined_midway() {
+    let mut lap_telemetry = LapTelemetry::default();
+
+    lap_telemetry.observe(&lap_data(1, &[3]));
+    lap_telemetry.observe(&telemetry(1, 300, 1.0, 0.0, 12000));
+    assert_eq!(lap_telemetry.observe(&lap_data(1, &[4])), None);
+
+    lap_telemetry.observe(&telemetry(1, 280, 1.0, 0.0, 11500));
+    let lap_4 = lap_telemetry.observe(&lap_data(1, &[5])).unwrap();
+    assert_eq!((lap_4.lap, lap_4.max_speed), (4, 280));
+}
+
+#[test]
+fn test_lap_telemetry_skips_laps_resumed_after_a_flashback() {
+    let mut lap_telemetry = LapTelemetry::default();
+
+    lap_telemetry.observe(&lap_data(1, &[1]));
+    lap_telemetry.observe(&lap_data(1, &[2]));
+    lap_telemetry.observe(&telemetry(1, 300, 1.0, 0.0, 12000));
+
+    // A flashback to the previous lap, which resumes midway
+    assert_eq!(lap_telemetry.observe(&lap_data(1, &[1])), None);
+    lap_telemetry.observe(&telemetry(1, 200, 1.0, 0.0, 10000));
+    assert_eq!(lap_telemetry.observe(&lap_data(1, &[2])), None);
+
+    lap_telemetry.observe(&telemetry(1, 310, 1.0, 0.0, 12000));
+    let lap_2 = lap_telemetry.observe(&lap_data(1, &[3])).unwrap();
+    assert_eq!((lap_2.lap, lap_2.samples, lap_2.max_speed), (2, 1, 310));
+}
+
+#[test]
+fn test_lap_telemetry_starts_over_on_new_session() {
+    let mut lap_telemetry = LapTelemetry::default();
+
+    lap_telemetry.observe(&lap_data(1, &[4]));
+    lap_telemetry.observe(&telemetry(1, 300, 1.0, 0.0, 12000));
+
+    // The lap in progress is discarded rather than reported
+    assert_eq!(lap_telemetry.observe(&lap_data(2, &[1])), None);
+    assert_eq!(
+        lap_telemetry.current().map(|c| (c.lap, c.samples)),
+        Some((1, 0))
+    );
+
+    // Late packets of the previous session are ignored
+    assert_eq!(lap_telemetry.observe(&lap_data(1, &[5])), None);
+    lap_telemetry.observe(&telemetry(1, 340, 1.0, 0.0, 13000));
+    assert_eq!(
+        lap_telemetry.current().map(|c| (c.lap, c.samples)),
+        Some((1, 0))
+    );
+
+    lap_telemetry.observe(&telemetry(2, 100, 1.0, 0.0, 9000));
+    assert_eq!(lap_telemetry.current().map(|c| c.max_speed), Some(100));
+}
+
+fn lap(session_uid: u64, lap: u8, last_lap_time: u32, lap_invalid: bool) -> Packet {
+    Packet::LapData(
+        PacketLapDataBuilder::default()
+            .with_header(PacketHeaderBuilder::in_session(session_uid, 0).build())
+            .with_cars([lap], |car, lap| {
+                car.with_current_lap_num(lap)
+                    .with_last_lap_time(last_lap_time)
+                    .with_current_lap_invalid(lap_invalid)
+            })
+            .build(),
+    )
+}
+
+#[test]
+fn test_personal_bests() {
+    let mut personal_bests = PersonalBests::default();
+
+    let packets = [
+        // The lap in progress when the tracking starts is ignored
+        lap(1, 2, 0, false),
+        lap(1, 3, 91000, false),
+        lap(1, 4, 90000, false),
+        lap(1, 4, 90000, true),
+        lap(1, 5, 88000, false),
+        lap(1, 6, 91500, false),
+        lap(1, 7, 87500, false),
+    ];
+
+    let found: Vec<PersonalBest> = packets
+        .iter()
+        .flat_map(|p| personal_bests.observe(p))
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![
+            PersonalBest {
+                car_index: 0,
+                lap: 3,
+                lap_time: 90000,
+                previous: None,
+            },
+            PersonalBest {
+                car_index: 0,
+                lap: 6,
+                lap_time: 87500,
+                previous: Some(90000),
+            },
+        ]
+    );
+    assert_eq!(personal_bests.best(0), Some(87500));
+}
+
+#[test]
+fn test_personal_bests_start_over_on_new_session() {
+    let mut personal_bests = PersonalBests::default();
+
+    personal_bests.observe(&lap(1, 1, 0, false));
+    personal_bests.observe(&lap(1, 2, 92000, false));
+    assert_eq!(personal_bests.observe(&lap(1, 3, 90000, false)).len(), 1);
+
+    personal_bests.observe(&lap(2, 1, 0, false));
+    assert_eq!(personal_bests.best(0), None);
+
+    // Late packets of the previous session are ignored
+    assert!(personal_bests.observe(&lap(1, 4, 85000, false)).is_empty());
+    personal_bests.observe(&lap(2, 2, 96000, false));
+    assert_eq!(personal_bests.observe(&lap(2, 3, 95000, false)).len(), 1);
+}
+
+#[test]
+fn test_personal_bests_survive_lap_number_wrapping() {
+    let mut personal_bests = PersonalBests::default();
+
+    personal_bests.observe(&lap(1, 254, 0, false));
+    personal_bests.observe(&lap(1, 255, 90000, false));
+    assert!(personal_bests.observe(&lap(1, 0, 89000, false)).is_empty());
+}
+
+fn positions(session_uid: u64, session_time: u32, cars: &[(u8, ResultStatus)]) -> Packet {
+    Packet::LapData(
+        PacketLapDataBuilder::default()
+            .with_header(PacketHeaderBuilder::in_session(session_uid, session_time).build())
+            .with_cars(cars, |car, &(position, status)| {
+                car.with_car_position(position).with_result_status(status)
+            })
+            .build(),
+    )
+}
+
+#[test]
+fn test_position_changes() {
+    use ResultStatus::*;
+
+    let mut changes = PositionChanges::default();
+
+    assert!(changes
+        .observe(&positions(
+            1,
+            1000,
+            &[(1, Active), (2, Active), (3, Active)]
+        ))
+        .is_empty());
+    assert_eq!(
+        changes.observe(&positions(
+            1,
+            2000,
+            &[(1, Active), (3, Active), (2, Active)]
+        )),
+        vec![Overtake {
+            car_index: 2,
+            passed_car_index: 1,
+            session_time: 2000,
+        }]
+    );
+
+    // Retirements are not overtakes
+    assert!(changes
+        .observe(&positions(
+            1,
+            3000,
+            &[(3, Retired), (2, Active), (1, Active)]
+        ))
+        .is_empty());
+
+    assert!(changes.swapped_since(1, 2, 2000));
+    assert!(changes.swapped_since(2, 1, 1500));
+    assert!(!changes.swapped_since(1, 2, 2500));
+    assert!(!changes.swapped_since(0, 1, 0));
+    assert_eq!(changes.overtakes().count(), 1);
+
+    // Tracking starts over with a new session
+    changes.observe(&positions(2, 0, &[(2, Active), (1, Active)]));
+    assert_eq!(changes.overtakes().count(), 0);
+}
+
+#[test]
+fn test_battles() {
+    use ResultStatus::*;
+
+    let mut changes = PositionChanges::default();
+    let gaps = |ahead: usize, behind: usize| match (ahead, behind) {
+        (2, 1) => Some(0.4),
+        (1, 0) => Some(2.5),
+        _ => None,
+    };
+
+    changes.observe(&positions(
+        1,
+        1000,
+        &[(1, Active), (2, Active), (3, Active)],
+    ));
+    assert!(changes.battles(gaps).is_empty());
+
+    // The second and third cars pass the first one, then the third car passes the second one
+    changes.observe(&positions(
+        1,
+        2000,
+        &[(3, Active), (1, Active), (2, Active)],
+    ));
+    changes.observe(&positions(
+        1,
+        3000,
+        &[(3, Active), (2, Active), (1, Active)],
+    ));
+
+    // The first car is too far behind to be battling
+    assert_eq!(
+        changes.battles(gaps),
+        vec![Battle {
+            car_ahead_index: 2,
+            car_behind_index: 1,
+            gap: 0.4,
+        }]
+    );
+
+    // The swap is too old
+    changes.observe(&positions(
+        1,
+        70000,
+        &[(3, Active), (2, Active), (1, Active)],
+    ));
+    assert!(changes.battles(gaps).is_empty());
+}
+
+fn time_trial_cars(session_uid: u64, ghosts: Option<(u8, u8)>) -> Packet {
+    Packet::LapData(
+        PacketLapDataBuilder::default()
+            .with_header(
+                PacketHeaderBuilder::default()
+                    .with_session_uid(session_uid)
+                    .with_player_car_index(0)
+                    .build(),
+            )
+            .with_time_trial_personal_best_car_idx(ghosts.map(|(pb, _)| pb))
+            .with_time_trial_rival_car_idx(ghosts.map(|(_, rival)| rival))
+            .build(),
+    )
+}
+
+fn session_history(session_uid: u64, car_index: u8, lap_times: &[u32], best: u8) -> Packet {
+    Packet::SessionHistory(PacketSessionHistoryData {
+        header: PacketHeaderBuilder::default()
+            .with_session_uid(session_uid)
+            .build(),
+        car_index,
+        number_of_laps: lap_times.len() as u8,
+        number_of_tyre_stints: 0,
+        best_lap_time_lap_number: best,
+        best_sector_1_lap_number: best,
+        best_sector_2_lap_number: best,
+        best_sector_3_lap_number: best,
+        lap_history: lap_times
+            .iter()
+            .map(|lap_time| LapHistoryData {
+                lap_time: *lap_time,
+                sector_1_time: 30_000,
+                sector_2_time: 10_000,
+                sector_2_time_minutes: 1,
+                sector_3_time: lap_time.saturating_sub(100_000) as u16,
+                ..Default::default()
+            })
+            .collect(),
+        tyre_stints: Vec::new(),
+    })
+}
+
+#[test]
+fn test_time_trial() {
+    let mut time_trial = TimeTrial::default();
+
+    // Outside of time trial, the game names no ghost car
+    time_trial.observe(&time_trial_cars(1, None));
+    time_trial.observe(&session_history(1, 0, &[130_000], 1));
+    assert_eq!(time_trial.info(), None);
+
+    time_trial.observe(&time_trial_cars(2, Some((1, 2))));
+    assert_eq!(time_trial.info(), Some(TimeTrialInfo::default()));
+
+    time_trial.observe(&session_history(2, 0, &[131_000, 129_500, 0], 2));
+    time_trial.observe(&session_history(2, 1, &[129_000], 1));
+    time_trial.observe(&session_history(2, 2, &[128_000], 1));
+
+    let lap = |car_index, lap_time| {
+        Some(TimeTrialLap {
+            car_index,
+            lap_time,
+            sector_times: [30_000, 70_000, lap_time - 100_000],
+        })
+    };
+
+    assert_eq!(
+        time_trial.info(),
+        Some(TimeTrialInfo {
+            player_best: lap(0, 129_500),
+            personal_best: lap(1, 129_000),
+            rival: lap(2, 128_000),
+        })
+    );
+
+    // Tracking starts over with a new session
+    time_trial.observe(&time_trial_cars(3, Some((1, 2))));
+    assert_eq!(time_trial.info(), Some(TimeTrialInfo::default()));
+}
+
+fn frame(
+    session_uid: u64,
+    frame: u32,
+    overall_frame: Option<u32>,
+    packet_type: PacketType,
+) -> Packet {
+    let header = PacketHeaderBuilder::default()
+        .with_session_uid(session_uid)
+        .with_frame_identifier(frame)
+        .with_overall_frame_identifier(overall_frame)
+        .build();
+
+    match packet_type {
+        PacketType::LapData => {
+            Packet::LapData(PacketLapDataBuilder::default().with_header(header).build())
+        }
+        _ => Packet::CarTelemetry(
+            PacketCarTelemetryDataBuilder::default()
+                .with_header(header)
+                .build(),
+        ),
+    }
+}
+
+#[test]
+fn test_frame_monitor_counts_gaps() {
+    use PacketType::*;
+
+    let mut monitor = FrameMonitor::default();
+
+    // Packets are sent every 3 frames, each type being checked on its own
+    for (f, expected) in [(0, 0), (3, 0), (6, 0), (12, 1), (15, 0), (27, 3)] {
+        assert_eq!(monitor.observe(&frame(1, f, None, LapData)), expected);
+        assert_eq!(monitor.observe(&frame(1, f, None, CarTelemetry)), expected);
+    }
+    assert_eq!(monitor.dropped(), 8);
+
+    // Going back on a flashback is not a gap
+    assert_eq!(monitor.observe(&frame(1, 9, None, LapData)), 0);
+    assert_eq!(monitor.observe(&frame(1, 12, None, LapData)), 0);
+    assert_eq!(monitor.dropped(), 8);
+
+    // Counting starts over with a new session
+    assert_eq!(monitor.observe(&frame(2, 0, None, LapData)), 0);
+    assert_eq!(monitor.dropped(), 0);
+}
+
+#[test]
+fn test_frame_monitor_prefers_overall_frame_identifier() {
+    let mut monitor = FrameMonitor::default();
+
+    monitor.observe(&frame(1, 100, Some(100), PacketType::LapData));
+    monitor.observe(&frame(1, 102, Some(102), PacketType::LapData));
+    // Flashback to frame 50, the overall frame identifier keeps going
+    assert_eq!(
+        monitor.observe(&frame(1, 50, Some(106), PacketType::LapData)),
+        1
+    );
+}
+
+fn named_participants(session_uid: u64, names: &[&str]) -> Packet {
+    Packet::Participants(PacketParticipantsData {
+        header: PacketHeaderBuilder::default()
+            .with_session_uid(session_uid)
+            .build(),
+        num_active_cars: names.len() as u8,
+        participants: names
+            .iter()
+            .map(|name| ParticipantData {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect(),
+    })
+}
+
+#[test]
+fn test_followed_driver() {
+    let mut driver = FollowedDriver::new("Verstappen");
+
+    let lap_data = Packet::LapData(
+        PacketLapDataBuilder::default()
+            .with_car(LapDataBuilder::default().with_car_position(1).build())
+            .with_car(LapDataBuilder::default().with_car_position(2).build())
+            .build(),
+    );
+
+    driver.observe(&named_participants(1, &["HAMILTON", "VERSTAPPEN"]));
+    assert_eq!(driver.car_index(), Some(1));
+    assert_eq!(driver.lap_data(&lap_data).unwrap().car_position, 2);
+    assert_eq!(driver.telemetry(&lap_data), None);
+
+    // Car indices shift when the participants change
+    driver.observe(&named_participants(1, &["VERSTAPPEN", "HAMILTON"]));
+    assert_eq!(driver.lap_data(&lap_data).unwrap().car_position, 1);
+
+    driver.observe(&named_participants(1, &["HAMILTON"]));
+    assert_eq!(driver.car_index(), None);
+    assert_eq!(driver.lap_data(&lap_data), None);
+
+    // The driver is looked up again in a new session
+    driver.observe(&named_participants(1, &["VERSTAPPEN"]));
+    driver.observe(&car_status(2, &[TyreCompound::C3]));
+    assert_eq!(driver.car_index(), None);
+}
+
+#[test]
+fn test_followed_driver_name_is_unicode_case_insensitive() {
+    let mut driver = FollowedDriver::new("Pérez");
+
+    driver.observe(&named_participants(1, &["HAMILTON", "PÉREZ"]));
+    assert_eq!(driver.car_index(), Some(1));
+}
+
+#[test]
+fn test_followed_driver_keeps_following_the_same_participant() {
+    let participants = |network_ids: &[u8]| {
+        Packet::Participants(PacketParticipantsData {
+            header: PacketHeaderBuilder::default().with_session_uid(1).build(),
+            num_active_cars: network_ids.len() as u8,
+            participants: network_ids
+                .iter()
+                .map(|id| ParticipantData {
+                    name: "Player".to_string(),
+                    network_id: Some(*id),
+                    ..Default::default()
+                })
+                .collect(),
+        })
+    };
+
+    let mut driver = FollowedDriver::new("player");
+
+    driver.observe(&participants(&[4, 7]));
+    assert_eq!(driver.car_index(), Some(0));
+
+    // The other player with the same name moved to the first car
+    driver.observe(&participants(&[7, 4]));
+    assert_eq!(driver.car_index(), Some(1));
+
+    // Followed by name again once the driver left
+    driver.observe(&participants(&[7]));
+    assert_eq!(driver.car_index(), Some(0));
+}
+
+/// `(position, lap, lap distance)` of each car, on a 1000 m track
+fn field(session_time: u32, cars: &[(u8, u8, f32)]) -> Packet {
+    Packet::LapData(
+        PacketLapDataBuilder::default()
+            .with_header(PacketHeaderBuilder::in_session(1, session_time).build())
+            .with_cars(cars, |car, &(position, lap, lap_distance)| {
+                car.with_car_position(position)
+                    .with_current_lap_num(lap)
+                    .with_lap_distance(lap_distance)
+                    .with_total_distance((lap - 1) as f32 * 1000.0 + lap_distance)
+            })
+            .build(),
+    )
+}
+
+#[test]
+fn test_distance_history_interpolates_between_samples() {
+    let mut history = DistanceHistory::default();
+    for (time, distance) in [(1000, 100.0), (2000, 200.0), (3000, 400.0)] {
+        history.push(time, distance);
+    }
+
+    assert_eq!(history.last_time(), Some(3000));
+    assert_eq!(history.time_at(150.0), Some(1500.0));
+    assert_eq!(history.time_at(300.0), Some(2500.0));
+    assert_eq!(history.time_at(400.0), Some(3000.0));
+    assert_eq!(history.time_at(50.0), None);
+    assert_eq!(history.time_at(500.0), None);
+}
+
+#[test]
+fn test_distance_history_is_reset_on_flashback() {
+    let mut history = DistanceHistory::default();
+    for (time, distance) in [(1000, 100.0), (2000, 200.0), (1500, 150.0)] {
+        history.push(time, distance);
+    }
+
+    assert_eq!(history.time_at(120.0), None);
+    assert_eq!(history.time_at(150.0), Some(1500.0));
+}
+
+#[test]
+fn test_leader_gaps() {
+    let mut gaps = LeaderGaps::default();
+
+    // All cars run at 50 m/s: the leader, a car 75 m behind and a car a lap and 100 m behind
+    for step in 0..=40u32 {
+        let distance = step as f32 * 25.0;
+        let lap_distance = |d: f32| d.rem_euclid(1000.0);
+        let lap = |d: f32| (d / 1000.0).floor() as u8 + 1;
+
+        let cars = [1100.0 + distance, 1025.0 + distance, distance];
+        gaps.observe(&field(
+            step * 500,
+            &[
+                (1, lap(cars[0]), lap_distance(cars[0])),
+                (3, lap(cars[2]), lap_distance(cars[2])),
+                (2, lap(cars[1]), lap_distance(cars[1])),
+            ],
+        ));
+    }
+
+    assert_eq!(
+        gaps.gaps(),
+        &[
+            LeaderGap {
+                car_index: 0,
+                position: 1,
+                gap: Some(0.0),
+                laps_down: 0,
+            },
+            LeaderGap {
+                car_index: 2,
+                position: 2,
+                gap: Some(1.5),
+                laps_down: 0,
+            },
+            LeaderGap {
+                car_index: 1,
+                position: 3,
+                gap: None,
+                laps_down: 1,
+            },
+        ]
+    );
+}
+
+fn final_classification(session_uid: u64, positions: &[u8]) -> Packet {
+    Packet::FinalClassification(PacketFinalClassificationData {
+        header: PacketHeaderBuilder::default()
+            .with_session_uid(session_uid)
+            .build(),
+        num_cars: positions.len() as u8,
+        final_classifications: positions
+            .iter()
+            .map(|&position| FinalClassification {
+                position,
+                result_status: ResultStatus::Finished,
+                ..Default::default()
+            })
+            .chain(std::iter::once(FinalClassification::default()))
+            .collect(),
+    })
+}
+
+#[test]
+fn test_session_summary() {
+    let mut builder = SessionSummaryBuilder::default();
+
+    let session = Packet::Session(
+        PacketSessionDataBuilder::default()
+            .with_header(PacketHeaderBuilder::default().with_session_uid(1).build())
+            .with_track(Track::Monza)
+            .with_weather(Weather::LightRain)
+            .build(),
+    );
+
+    // The drivers are not known yet
+    assert_eq!(builder.observe(&session), None);
+    assert_eq!(builder.observe(&final_classification(1, &[2, 1])), None);
+
+    builder.observe(&named_participants(1, &["HAMILTON", "VERSTAPPEN"]));
+    let summary = builder.observe(&final_classification(1, &[2, 1])).unwrap();
+
+    assert_eq!(summary.session_uid, 1);
+    assert_eq!(summary.track, Track::Monza);
+    assert_eq!(summary.weather, Weather::LightRain);
+    assert_eq!(
+        summary
+            .classification
+            .iter()
+            .map(|d| (d.car_index, d.name.as_str(), d.result.position))
+            .collect::<Vec<_>>(),
+        vec![(1, "VERSTAPPEN", 1), (0, "HAMILTON", 2)]
+    );
+
+    // Packets of the previous session are not used for the next one
+    assert_eq!(builder.observe(&final_classification(2, &[1])), None);
+}
+
+#[test]
+fn test_drs_available_here() {
+    let zones = vec![
+        MarshalZone {
+            zone_start: 0.0,
+            zone_flag: Flag::None,
+        },
+        MarshalZone {
+            zone_start: 0.5,
+            zone_flag: Flag::Yellow,
+        },
+    ];
+    let session = || {
+        PacketSessionDataBuilder::default()
+            .with_track_length(1000)
+            .with_marshal_zones(zones.clone())
+    };
+
+    let dry = session().build();
+    assert_eq!(drs_available_here(200.0, &dry), Some(true));
+    assert_eq!(drs_available_here(700.0, &dry), Some(false));
+    // The car has not crossed the line yet
+    assert_eq!(drs_available_here(-50.0, &dry), None);
+
+    let wet = session().with_weather(Weather::LightRain).build();
+    assert_eq!(drs_available_here(200.0, &wet), Some(false));
+
+    let safety_car = session().with_safety_car_status(SafetyCar::Virtual).build();
+    assert_eq!(drs_available_here(200.0, &safety_car), Some(false));
+
+    // The marshal zones are not known
+    let session = PacketSessionDataBuilder::default().build();
+    assert_eq!(drs_available_here(200.0, &session), None);
+}
+
+#[test]
+fn test_weather_change() {
+    let sample = |time_offset, weather, rain_percentage| WeatherForecastSample {
+        session_type: SessionType::Race,
+        time_offset,
+        weather,
+        rain_percentage,
+        ..Default::default()
+    };
+
+    let forecast = [
+        sample(0, Weather::Overcast, 20),
+        sample(5, Weather::Overcast, 40),
+        sample(10, Weather::Overcast, 60),
+        sample(15, Weather::LightRain, 80),
+        sample(30, Weather::Overcast, 30),
+    ];
+
+    assert_eq!(
+        weather_change(Weather::Overcast, &forecast, DEFAULT_RAIN_THRESHOLD),
+        Some(WeatherChange::Rain(10))
+    );
+    assert_eq!(
+        weather_change(Weather::Overcast, &forecast, 70),
+        Some(WeatherChange::Rain(15))
+    );
+    assert_eq!(
+        weather_change(Weather::LightRain, &forecast, DEFAULT_RAIN_THRESHOLD),
+        Some(WeatherChange::Drying(5))
+    );
+    assert_eq!(
+        weather_change(Weather::LightRain, &forecast[2..4], DEFAULT_RAIN_THRESHOLD),
+        None
+    );
+    assert_eq!(
+        weather_change(Weather::Clear, &[], DEFAULT_RAIN_THRESHOLD),
+        None
+    );
+}
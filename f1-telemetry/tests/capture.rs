@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use f1_telemetry::capture::FileSource;
+use f1_telemetry::packet::event::{Event, SpeedTrap};
+use f1_telemetry::packet::Packet;
+use f1_telemetry::PacketSource;
+
+mod utils;
+
+const EVENT_PACKET: &str = "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000";
+
+fn write_capture(name: &str, packets: &[&str]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}.bin", name, std::process::id()));
+    let mut content = Vec::new();
+
+    for (i, packet) in packets.iter().enumerate() {
+        let data = hex::decode(packet).unwrap();
+
+        content.extend_from_slice(&(i as u64).to_le_bytes());
+        content.extend_from_slice(&(data.len() as u16).to_le_bytes());
+        content.extend_from_slice(&data);
+    }
+
+    std::fs::write(&path, content).expect("Unable to write capture file");
+
+    path
+}
+
+#[tokio::test]
+async fn test_replay_capture_file() {
+    let path = write_capture("f1-telemetry-replay", &[EVENT_PACKET, EVENT_PACKET]);
+    let mut source = FileSource::open(&path).await.unwrap();
+
+    for _ in 0..2 {
+        let p = source.next().await.unwrap();
+
+        match p {
+            Some(Packet::Event(e)) => assert_eq!(
+                e.event,
+                Event::SpeedTrap(SpeedTrap {
+                    vehicle_idx: 13,
+                    speed: 341.42874,
+                    ..Default::default()
+                })
+            ),
+            _ => panic!("Invalid packet. Expected Event, got {:?}", &p),
+        }
+    }
+
+    assert!(source.next().await.unwrap().is_none());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_replay_from_reader() {
+    let data = hex::decode(EVENT_PACKET).unwrap();
+    let mut content = Vec::new();
+    content.extend_from_slice(&0u64.to_le_bytes());
+    content.extend_from_slice(&(data.len() as u16).to_le_bytes());
+    content.extend_from_slice(&data);
+
+    let mut source = FileSource::from_reader(content.as_slice());
+
+    assert!(matches!(source.next().await, Ok(Some(Packet::Event(_)))));
+    assert!(source.next().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_replay_as_packet_source() {
+    let path = write_capture("f1-telemetry-source", &[EVENT_PACKET]);
+    let mut source = FileSource::open(&path).await.unwrap();
+
+    assert!(matches!(
+        source.next_packet().await,
+        Ok(Some(Packet::Event(_)))
+    ));
+    assert!(source.next_packet().await.unwrap().is_none());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn test_zero_flush_interval_is_rejected() {
+    let path = std::env::temp_dir().join(format!("f1-telemetry-zero-{}.bin", std::process::id()));
+
+    let mut stream = utils::get_stream().await;
+    let err = stream.record_to(&path, Duration::ZERO).await.unwrap_err();
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_record_and_replay() {
+    let path = std::env::temp_dir().join(format!("f1-telemetry-record-{}.bin", std::process::id()));
+
+    let mut stream = utils::get_stream().await;
+    stream
+        .record_to(&path, Duration::from_millis(10))
+        .await
+        .unwrap();
+
+    utils::send_raw_data(&stream, EVENT_PACKET).await;
+    let recorded = stream.next().await.unwrap();
+
+    stream.stop_recording().await.unwrap();
+
+    let mut source = FileSource::open(&path).await.unwrap();
+    let replayed = source.next().await.unwrap().unwrap();
+
+    assert_eq!(replayed.header(), recorded.header());
+    assert!(source.next().await.unwrap().is_none());
+
+    std::fs::remove_file(path).unwrap();
+}
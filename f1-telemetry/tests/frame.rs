@@ -0,0 +1,127 @@
+use f1_telemetry::frame::{FrameAssembler, FrameSource};
+use f1_telemetry::packet::car_status::CarStatusData;
+use f1_telemetry::packet::car_telemetry::CarTelemetryData;
+use f1_telemetry::packet::motion::CarMotionData;
+use f1_telemetry::packet::Packet;
+use f1_telemetry::test_util::{
+    PacketCarStatusDataBuilder, PacketCarTelemetryDataBuilder, PacketHeaderBuilder,
+    PacketLapDataBuilder, PacketMotionDataBuilder,
+};
+use f1_telemetry::VecSource;
+
+fn motion(frame: u32) -> Packet {
+    let header = PacketHeaderBuilder::default()
+        .with_frame_identifier(frame)
+        .with_session_time(frame * 50)
+        .build();
+
+    Packet::Motion(
+        PacketMotionDataBuilder::default()
+            .with_header(header)
+            .with_car(CarMotionData {
+                world_position_x: frame as f32,
+                ..Default::default()
+            })
+            .with_car(CarMotionData {
+                g_force_lateral: 2.5,
+                ..Default::default()
+            })
+            .build(),
+    )
+}
+
+fn telemetry(frame: u32) -> Packet {
+    let header = PacketHeaderBuilder::default()
+        .with_frame_identifier(frame)
+        .build();
+
+    Packet::CarTelemetry(
+        PacketCarTelemetryDataBuilder::default()
+            .with_header(header)
+            .with_car(CarTelemetryData {
+                speed: 250,
+                ..Default::default()
+            })
+            .with_car(CarTelemetryData {
+                gear: 7,
+                ..Default::default()
+            })
+            .build(),
+    )
+}
+
+fn status(frame: u32) -> Packet {
+    let header = PacketHeaderBuilder::default()
+        .with_frame_identifier(frame)
+        .build();
+
+    Packet::CarStatus(
+        PacketCarStatusDataBuilder::default()
+            .with_header(header)
+            .with_car(CarStatusData {
+                fuel_in_tank: 12.5,
+                ..Default::default()
+            })
+            .with_car(CarStatusData::default())
+            .build(),
+    )
+}
+
+#[test]
+fn test_frame_is_emitted_once_complete() {
+    let mut assembler = FrameAssembler::new();
+
+    assert!(assembler.observe(&motion(10)).is_empty());
+    assert!(assembler
+        .observe(&Packet::LapData(PacketLapDataBuilder::default().build()))
+        .is_empty());
+    assert!(assembler.observe(&telemetry(10)).is_empty());
+
+    let frames = assembler.observe(&status(10));
+
+    assert_eq!(frames.len(), 2);
+    assert_eq!((frames[0].car_index, frames[0].frame_identifier), (0, 10));
+    assert_eq!(frames[0].session_time, 500);
+    assert_eq!(frames[0].motion.world_position_x, 10.0);
+    assert_eq!(frames[0].telemetry.speed, 250);
+    assert_eq!(frames[0].status.fuel_in_tank, 12.5);
+    assert_eq!(frames[1].car_index, 1);
+    assert_eq!(frames[1].motion.g_force_lateral, 2.5);
+    assert_eq!(frames[1].telemetry.gear, 7);
+
+    // A repeated packet does not emit the frame again
+    assert!(assembler.observe(&status(10)).is_empty());
+}
+
+#[test]
+fn test_frames_missing_a_packet_are_skipped() {
+    let mut assembler = FrameAssembler::new();
+
+    assembler.observe(&status(9));
+    assert!(assembler.observe(&motion(10)).is_empty());
+    assert!(assembler.observe(&telemetry(10)).is_empty());
+
+    assert!(assembler.observe(&motion(11)).is_empty());
+    assert!(assembler.observe(&telemetry(11)).is_empty());
+    assert_eq!(assembler.observe(&status(11)).len(), 2);
+}
+
+#[tokio::test]
+async fn test_frame_source() {
+    let source = VecSource::new(vec![
+        motion(1),
+        telemetry(1),
+        status(1),
+        motion(2),
+        telemetry(2),
+    ]);
+    let mut frames = FrameSource::new(source);
+
+    for car_index in 0..2 {
+        let frame = frames.next_frame().await.unwrap().unwrap();
+        assert_eq!((frame.frame_identifier, frame.car_index), (1, car_index));
+    }
+
+    assert!(frames.next_frame().await.unwrap().is_none());
+    assert!(frames.into_inner().is_empty());
+}
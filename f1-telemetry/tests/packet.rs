@@ -0,0 +1,463 @@
+use f1_telemetry::packet::event::{Event, InfringementType, PenaltyType, UnknownEvent};
+use f1_telemetry::packet::generic::{Flag, ResultStatus, WheelData};
+use f1_telemetry::packet::header::GameVersion;
+use f1_telemetry::packet::motion::PlayerCarData;
+use f1_telemetry::packet::session::Track;
+use f1_telemetry::packet::{
+    expected_size, hex_dump, packet_length, parse_packet, parse_packet_with, Packet, PacketType,
+    ParseOptions, UnpackError,
+};
+use f1_telemetry::test_util::{LapDataBuilder, PacketLapDataBuilder};
+
+#[test]
+fn test_truncated_packet_returns_an_error() {
+    let data = vec![0xe4];
+
+    assert!(matches!(
+        parse_packet(data.len(), &data).unwrap_err(),
+        UnpackError::TooSmall {
+            got: 1,
+            expected: 2
+        }
+    ));
+}
+
+#[test]
+fn test_error_is_displayable() {
+    let data = vec![0xe4];
+    let err: Box<dyn std::error::Error> = Box::new(parse_packet(data.len(), &data).unwrap_err());
+
+    assert_eq!(
+        err.to_string(),
+        "Packet too small: 1 bytes (minimum: 2 bytes)"
+    );
+}
+
+#[test]
+fn test_unknown_format_returns_an_error() {
+    let data = vec![0xe2, 0x07, 0x00, 0x00];
+    let err = parse_packet(data.len(), &data).unwrap_err();
+
+    assert!(matches!(err, UnpackError::UnknownFormat(2018)));
+    assert_eq!(err.to_string(), "Invalid packet: unknown format (2018)");
+}
+
+#[test]
+fn test_foreign_datagram_is_not_an_f1_packet() {
+    let data = b"GET / HTTP/1.1\r\n".to_vec();
+    let err = parse_packet(data.len(), &data).unwrap_err();
+
+    assert!(matches!(err, UnpackError::NotF1Packet));
+}
+
+#[test]
+fn test_out_of_range_packet_id_is_not_an_f1_packet() {
+    let mut data = hex::decode(EVENT_PACKET_2020).unwrap();
+
+    data[5] = 42;
+    assert!(matches!(
+        parse_packet(data.len(), &data).unwrap_err(),
+        UnpackError::NotF1Packet
+    ));
+}
+
+#[test]
+fn test_packet_type_of_a_later_game_is_unsupported() {
+    let mut data = hex::decode(EVENT_PACKET_2020).unwrap();
+
+    // Motion ex packets only exist since F1 23
+    data[5] = 13;
+    let err = parse_packet(data.len(), &data).unwrap_err();
+
+    assert!(matches!(
+        err,
+        UnpackError::UnsupportedPacketType(PacketType::MotionEx)
+    ));
+}
+
+// F1 2022 session packet with 17 marshal zones and 3 weather forecast samples
+const SESSION_PACKET_2022: &str = "e60701020101321aca82d6efd8a364291442fb02000013ff011f190520150a0300001c201c500000ff0011b14c743f006347dc3d03fde0303e0178ee813e007027a43e00676fb43e00389ac13e00c0fbd23e008601e43e00ef84f73e000e66033f00e234203f007d3d293f004a8f313f007e01403f0002694d3f005cfd633f0000000000000000000000000000000000000000000000030a00011f021902070a05011f021902070a0a011f0219020700000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000005a24477a5824477a5824477a5800001400000100000101000003016a04000003";
+const NUM_MARSHAL_ZONES_OFFSET: usize = 42;
+const NUM_WEATHER_FORECAST_SAMPLES_OFFSET: usize = 150;
+const WEATHER_OFFSET: usize = 24;
+const TRACK_OFFSET: usize = 31;
+
+#[test]
+fn test_out_of_range_marshal_zone_count_returns_an_error() {
+    let mut data = hex::decode(SESSION_PACKET_2022).unwrap();
+    assert!(parse_packet(data.len(), &data).is_ok());
+
+    data[NUM_MARSHAL_ZONES_OFFSET] = 22;
+    let err = parse_packet(data.len(), &data).unwrap_err();
+
+    assert!(matches!(
+        err,
+        UnpackError::InvalidValue {
+            field: "num_marshal_zones",
+            value: 22
+        }
+    ));
+}
+
+#[test]
+fn test_unknown_enum_value_is_coerced_in_lossy_mode() {
+    let mut data = hex::decode(SESSION_PACKET_2022).unwrap();
+    data[TRACK_OFFSET] = 42;
+
+    let err = parse_packet(data.len(), &data).unwrap_err();
+    assert!(matches!(
+        err,
+        UnpackError::InvalidValue {
+            field: "Track",
+            value: 42
+        }
+    ));
+
+    let lossy = ParseOptions { strict: false };
+    match parse_packet_with(data.len(), &data, lossy).unwrap() {
+        Packet::Session(session) => assert_eq!(session.track, Track::Unknown),
+        p => panic!("Unexpected packet: {:?}", p),
+    }
+
+    // Lossy mode only applies to the packet it was requested for
+    assert!(parse_packet(data.len(), &data).is_err());
+}
+
+#[test]
+fn test_enum_without_unknown_variant_is_strict_in_lossy_mode() {
+    let mut data = hex::decode(SESSION_PACKET_2022).unwrap();
+    data[WEATHER_OFFSET] = 42;
+
+    let lossy = ParseOptions { strict: false };
+    assert!(matches!(
+        parse_packet_with(data.len(), &data, lossy).unwrap_err(),
+        UnpackError::InvalidValue {
+            field: "Weather",
+            value: 42
+        }
+    ));
+}
+
+#[test]
+fn test_out_of_range_weather_forecast_count_returns_an_error() {
+    let mut data = hex::decode(SESSION_PACKET_2022).unwrap();
+    data[NUM_WEATHER_FORECAST_SAMPLES_OFFSET] = 255;
+
+    let err = parse_packet(data.len(), &data).unwrap_err();
+
+    assert!(matches!(
+        err,
+        UnpackError::InvalidValue {
+            field: "num_weather_forecast_samples",
+            value: 255
+        }
+    ));
+}
+
+#[test]
+fn test_expected_size() {
+    assert_eq!(expected_size(PacketType::Session, 2022), Some(632));
+    assert_eq!(expected_size(PacketType::Motion, 2021), Some(1464));
+    assert_eq!(expected_size(PacketType::CarDamage, 2020), None);
+    assert_eq!(expected_size(PacketType::Session, 2000), None);
+}
+
+#[test]
+fn test_packet_length() {
+    assert_eq!(
+        packet_length(PacketType::CarTelemetry, GameVersion::F1_2020),
+        Some(1307)
+    );
+    assert_eq!(
+        packet_length(PacketType::TyreSets, GameVersion::F1_2022),
+        None
+    );
+    assert_eq!(
+        packet_length(PacketType::Motion, GameVersion::Unknown(2030)),
+        None
+    );
+
+    const SESSION_2022: Option<usize> = packet_length(PacketType::Session, GameVersion::F1_2022);
+    assert_eq!(SESSION_2022, expected_size(PacketType::Session, 2022));
+}
+
+#[test]
+fn test_wrong_size_reports_expected_size() {
+    let mut data = hex::decode(SESSION_PACKET_2022).unwrap();
+    data.truncate(600);
+
+    assert!(matches!(
+        parse_packet(data.len(), &data).unwrap_err(),
+        UnpackError::InvalidSize {
+            got: 600,
+            expected: 632
+        }
+    ));
+}
+
+#[test]
+fn test_trailing_bytes_are_ignored() {
+    let data = hex::decode(SESSION_PACKET_2022).unwrap();
+    let expected = parse_packet(data.len(), &data).unwrap();
+
+    let mut padded = data.clone();
+    padded.extend_from_slice(&[0xff; 16]);
+
+    assert_eq!(parse_packet(padded.len(), &padded).unwrap(), expected);
+}
+
+// F1 2022 car status packet, 47 bytes per car after the 24 bytes header
+const CAR_STATUS_PACKET_2022: &str = "e60701020107321aca82d6efd8a364291442fb02000013ff0001013600c05730410000dc427400b03fc832ab0d090000001210000045b14e4a019ac4f3478467b848c8348849000001013600045037410000dc42e45dcc3fc832ab0d09000000121000002d4f494a011867f34799efb648ff909249000001013600db132d410000dc420c44a13fc832ab0d09000000121000005d54494a011879f9478510bb48aa069349000001013600a4162b410000dc42806a963fc832ab0d09000000121000009f004b4a016cc80848a787b24876f38f49000001013600328f2f410000dc428418aa3fc832ab0d090000001210000062c0554a01ab1cf0472527bb48c209754900000101360094d336410000dc422476d03fc832ab0d0900000012100000de6d2d4a0332ca0b485b019e48bb54c6490000010136004a8138410000dc420c4fd13fc832ab0d09000000121000008223474a010c6bef474310af48deab944900000101360074b231410000dc42f8a0b83fc832ab0d0900000012100000de50354a0361c2f047dd6aa84843bbb649000001013600177831410000dc42ecd7b33fc832ab0d0900000012100000df5d4b4a01b7df0d483371b5488d949049000001013600d3292d410000dc426864a13fc832ab0d0900000012100000078d484a01db0df6477fd2bb48927c95490000010136000a0639410000dc42c443d43fc832ab0d090000001210000044e64b4a0107b116486a55b548ae9e9049000001013600866b30410000dc42bc7bb33fc832ab0d0900000012100000c668314a0348f00148ee549b488777bc49000001013600863335410000dc42b037c43fc832ab0d09000000121000000534504a011fb4114870c4bc481a958849000001013600221538410000dc426075d03fc832ab0d090000001210000023cb4b4a01104b0948c002b748a6938f49000001013600be9736410000dc42c0e0c93fc832ab0d0900000012100000d70a454a01d2691548c063c348d0a5a1490000010136002b3f2e410000dc4250faa73fc832ab0d0900000012100000ed683c4a033d1217481d50ad48fb9fad4900000101360062d230410000dc425499b03fc832ab0d0900000012100000947c484a0198140d48933fb748d2b196490000010136001f8c34410000dc42f830c33fc832ab0d09000000121000004605454a03a60412488698b44811989d49000001013600e73b31410000dc42ccada93fc832ab0d09000000121000015aa1674a03b7e283472f795f48d723f448000101013600854f2d410000dc424c748d3fc832ab0d090000001210000324b76d4a01d1e32e477cd6c54758c26d480000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+const VEHICLE_FIA_FLAGS_OFFSET: usize = 24 + 28;
+const CAR_STATUS_DATA_SIZE: usize = 47;
+
+#[test]
+fn test_unknown_fia_flag_does_not_discard_car_status() {
+    let mut data = hex::decode(CAR_STATUS_PACKET_2022).unwrap();
+    data[VEHICLE_FIA_FLAGS_OFFSET] = 0xff; // -1
+    data[VEHICLE_FIA_FLAGS_OFFSET + CAR_STATUS_DATA_SIZE] = 0x2a;
+
+    let car_status = match parse_packet(data.len(), &data).unwrap() {
+        Packet::CarStatus(p) => p,
+        p => panic!("Invalid packet. Expected CarStatus, got {:?}", p),
+    };
+
+    assert_eq!(
+        car_status.car_status_data[0].vehicle_fia_flag,
+        Flag::Invalid
+    );
+    assert_eq!(
+        car_status.car_status_data[1].vehicle_fia_flag,
+        Flag::Invalid
+    );
+    assert_eq!(car_status.car_status_data[18].vehicle_fia_flag, Flag::Green);
+    assert_eq!(
+        car_status.car_status_data[19].vehicle_fia_flag,
+        Flag::Yellow
+    );
+}
+
+const PLAYER_CAR_INDEX_OFFSET: usize = 22;
+
+#[test]
+fn test_player_data_uses_player_car_index() {
+    let mut data = hex::decode(CAR_STATUS_PACKET_2022).unwrap();
+
+    for (index, expected) in [(18, Some(18)), (255, None)] {
+        data[PLAYER_CAR_INDEX_OFFSET] = index;
+
+        let car_status = match parse_packet(data.len(), &data).unwrap() {
+            Packet::CarStatus(p) => p,
+            p => panic!("Invalid packet. Expected CarStatus, got {:?}", p),
+        };
+
+        assert_eq!(
+            car_status.player_data(),
+            expected.map(|i| &car_status.car_status_data[i])
+        );
+    }
+}
+
+#[test]
+fn test_cloned_packets_compare_equal() {
+    let data = hex::decode(CAR_STATUS_PACKET_2022).unwrap();
+    let packet = parse_packet(data.len(), &data).unwrap();
+
+    let mut snapshot = packet.clone();
+    assert_eq!(snapshot, packet);
+
+    if let Packet::CarStatus(p) = &mut snapshot {
+        p.car_status_data[0].fuel_in_tank += 1.0;
+    }
+    assert_ne!(snapshot, packet);
+}
+
+#[test]
+fn test_lap_data_by_position() {
+    let car = |position, status| {
+        LapDataBuilder::default()
+            .with_car_position(position)
+            .with_result_status(status)
+            .build()
+    };
+
+    let lap_data = PacketLapDataBuilder::default()
+        .with_car(car(3, ResultStatus::Active))
+        .with_car(car(1, ResultStatus::Retired))
+        .with_car(car(2, ResultStatus::Active))
+        .with_car(car(0, ResultStatus::Invalid))
+        .with_car(car(2, ResultStatus::Active))
+        .with_car(car(1, ResultStatus::Finished))
+        .build();
+
+    let indices: Vec<usize> = lap_data
+        .by_position()
+        .into_iter()
+        .map(|(idx, _)| idx)
+        .collect();
+
+    assert_eq!(indices, vec![5, 2, 4, 0, 1]);
+}
+
+#[test]
+fn test_lap_data_grid_order() {
+    let car = |grid_position, position, lap| {
+        LapDataBuilder::default()
+            .with_grid_position(grid_position)
+            .with_car_position(position)
+            .with_current_lap_num(lap)
+            .build()
+    };
+
+    let lap_data = PacketLapDataBuilder::default()
+        .with_car(car(2, 2, 1))
+        .with_car(car(3, 3, 1))
+        .with_car(car(1, 1, 1))
+        .build();
+
+    assert_eq!(lap_data.grid_order(), Some(vec![(1, 2), (2, 0), (3, 1)]));
+
+    // Overtakes on the first lap do not change the grid
+    let lap_data = PacketLapDataBuilder::default()
+        .with_car(car(2, 1, 1))
+        .with_car(car(1, 2, 2))
+        .build();
+
+    assert_eq!(lap_data.grid_order(), Some(vec![(1, 1), (2, 0)]));
+
+    let lap_data = PacketLapDataBuilder::default()
+        .with_car(car(0, 1, 3))
+        .build();
+
+    assert_eq!(lap_data.grid_order(), None);
+    assert_eq!(PacketLapDataBuilder::default().build().grid_order(), None);
+}
+
+const EVENT_PACKET_2020: &str =
+    "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000";
+const EVENT_CODE_OFFSET: usize = 24;
+
+#[test]
+fn test_game_version_is_detected() {
+    let data = hex::decode(EVENT_PACKET_2020).unwrap();
+    let packet = parse_packet(data.len(), &data).unwrap();
+
+    assert_eq!(packet.header().game_version(), GameVersion::F1_2020);
+    assert_eq!(GameVersion::from_format(2023), GameVersion::F1_2023);
+    assert_eq!(GameVersion::from_format(2018), GameVersion::Unknown(2018));
+    assert_eq!(GameVersion::Unknown(2018).format(), 2018);
+}
+
+#[test]
+fn test_unknown_event_code_is_reported_as_other() {
+    let mut data = hex::decode(EVENT_PACKET_2020).unwrap();
+    data[EVENT_CODE_OFFSET..EVENT_CODE_OFFSET + 4].copy_from_slice(b"NEWE");
+
+    let event = match parse_packet(data.len(), &data).unwrap() {
+        Packet::Event(p) => p.event,
+        p => panic!("Invalid packet. Expected Event, got {:?}", p),
+    };
+
+    assert_eq!(event, Event::Other(UnknownEvent { code: *b"NEWE" }));
+    assert_eq!(event.vehicle_idx(), None);
+}
+
+#[test]
+fn test_parse_2023_red_flag_event() {
+    let data = hex::decode(
+        "e7071701020103000000000000000000000000000000000000000000ff5244464c000000000000000000000000",
+    )
+    .unwrap();
+
+    let event = match parse_packet(data.len(), &data).unwrap() {
+        Packet::Event(p) => p.event,
+        p => panic!("Invalid packet. Expected Event, got {:?}", p),
+    };
+
+    assert_eq!(event, Event::RedFlag);
+}
+
+#[test]
+fn test_hex_dump() {
+    let data: Vec<u8> = (0x40..0x52).collect();
+
+    assert_eq!(
+        hex_dump(&data),
+        "00000000  40 41 42 43 44 45 46 47 48 49 4a 4b 4c 4d 4e 4f  |@ABCDEFGHIJKLMNO|\n\
+         00000010  50 51                                            |PQ|\n"
+    );
+    assert_eq!(hex_dump(&[]), "");
+}
+
+#[test]
+fn test_penalty_types_are_decoded() {
+    assert_eq!(PenaltyType::from(4), PenaltyType::TimePenalty);
+    assert_eq!(PenaltyType::from(200), PenaltyType::Other(200));
+    assert_eq!(PenaltyType::TimePenalty.to_string(), "Time penalty");
+
+    assert_eq!(
+        InfringementType::from(11),
+        InfringementType::IgnoringBlueFlags
+    );
+    assert_eq!(
+        InfringementType::from(49),
+        InfringementType::ParcFermeChange
+    );
+    assert_eq!(InfringementType::from(55), InfringementType::Other(55));
+    assert_eq!(
+        InfringementType::IgnoringBlueFlags.to_string(),
+        "Ignoring blue flags"
+    );
+    assert_eq!(InfringementType::Other(55).to_string(), "Infringement 55");
+}
+
+/// F1 22 motion packet whose 22 cars are zeroed, followed by the player car fields set to 1.0,
+/// 2.0, ... in packet order, so that a field read from the wrong offset is noticed.
+fn crafted_motion_packet_2022() -> Vec<u8> {
+    let mut data = vec![0xe6, 0x07, 0x01, 0x02, 0x01, 0x00];
+    data.extend(1u64.to_le_bytes());
+    data.extend(12.5f32.to_le_bytes());
+    data.extend(42u32.to_le_bytes());
+    data.extend([0x00, 0xff]);
+
+    data.resize(data.len() + 22 * 60, 0);
+
+    for value in 1..=30 {
+        data.extend((value as f32).to_le_bytes());
+    }
+
+    data
+}
+
+#[test]
+fn test_motion_player_car_data_is_parsed() {
+    let data = crafted_motion_packet_2022();
+    assert_eq!(Some(data.len()), expected_size(PacketType::Motion, 2022));
+
+    let motion = match parse_packet(data.len(), &data).unwrap() {
+        Packet::Motion(p) => p,
+        p => panic!("Invalid packet. Expected Motion, got {:?}", p),
+    };
+
+    assert_eq!(motion.motion_data.len(), 22);
+    assert_eq!(
+        motion.player_car_data,
+        Some(PlayerCarData {
+            suspension_position: WheelData::new(1.0, 2.0, 3.0, 4.0),
+            suspension_velocity: WheelData::new(5.0, 6.0, 7.0, 8.0),
+            suspension_acceleration: WheelData::new(9.0, 10.0, 11.0, 12.0),
+            wheel_speed: WheelData::new(13.0, 14.0, 15.0, 16.0),
+            wheel_slip: WheelData::new(17.0, 18.0, 19.0, 20.0),
+            local_velocity_x: 21.0,
+            local_velocity_y: 22.0,
+            local_velocity_z: 23.0,
+            angular_velocity_x: 24.0,
+            angular_velocity_y: 25.0,
+            angular_velocity_z: 26.0,
+            angular_acceleration_x: 27.0,
+            angular_acceleration_y: 28.0,
+            angular_acceleration_z: 29.0,
+            front_wheels_angle: 30.0,
+        })
+    );
+}
@@ -0,0 +1,43 @@
+use f1_telemetry::packet::parse_packet;
+
+#[test]
+fn test_packet_serializes_to_json() {
+    let data =
+        hex::decode("e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000")
+            .unwrap();
+    let packet = parse_packet(data.len(), &data).unwrap();
+
+    let json = serde_json::to_value(&packet).unwrap();
+
+    assert_eq!(json["packet_type"], "Event");
+    assert_eq!(json["header"]["packet_type"], "Event");
+    assert_eq!(json["event"]["event_type"], "SpeedTrap");
+    assert_eq!(json["event"]["vehicle_idx"], 13);
+}
+
+#[test]
+fn test_unknown_event_code_serializes_as_text() {
+    let mut data =
+        hex::decode("e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000")
+            .unwrap();
+    data[24..28].copy_from_slice(b"NEWE");
+    let packet = parse_packet(data.len(), &data).unwrap();
+
+    let json = serde_json::to_value(&packet).unwrap();
+
+    assert_eq!(json["event"]["event_type"], "Other");
+    assert_eq!(json["event"]["code"], "NEWE");
+}
+
+#[test]
+fn test_parse_whole_datagram() {
+    let data =
+        hex::decode("e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000")
+            .unwrap();
+
+    assert_eq!(
+        parse_packet(data.len(), &data).unwrap(),
+        f1_telemetry::parse_packet(&data).unwrap()
+    );
+    assert!(f1_telemetry::parse_packet(&data[..20]).is_err());
+}
@@ -0,0 +1,128 @@
+use f1_telemetry::packet::generic::{Flag, SessionType};
+use f1_telemetry::packet::session::{MarshalZone, SafetyCar, SessionChange, Track, Weather};
+use f1_telemetry::test_util::PacketSessionDataBuilder;
+
+#[test]
+fn test_changes_since_weather_transition() {
+    let prev = PacketSessionDataBuilder::default()
+        .with_weather(Weather::Overcast)
+        .build();
+    let session = PacketSessionDataBuilder::default()
+        .with_weather(Weather::LightRain)
+        .build();
+
+    assert_eq!(
+        session.changes_since(&prev),
+        vec![SessionChange::WeatherChanged {
+            from: Weather::Overcast,
+            to: Weather::LightRain,
+        }]
+    );
+}
+
+#[test]
+fn test_changes_since_lists_every_transition() {
+    let prev = PacketSessionDataBuilder::default()
+        .with_session_type(SessionType::Qualifying1)
+        .with_ai_difficulty(Some(90))
+        .build();
+    let session = PacketSessionDataBuilder::default()
+        .with_session_type(SessionType::Qualifying2)
+        .with_safety_car_status(SafetyCar::Virtual)
+        .with_ai_difficulty(Some(95))
+        .with_game_paused(true)
+        .build();
+
+    assert_eq!(
+        session.changes_since(&prev),
+        vec![
+            SessionChange::SafetyCarChanged {
+                from: SafetyCar::None,
+                to: SafetyCar::Virtual,
+            },
+            SessionChange::SessionTypeChanged {
+                from: SessionType::Qualifying1,
+                to: SessionType::Qualifying2,
+            },
+            SessionChange::AiDifficultyChanged {
+                from: Some(90),
+                to: Some(95),
+            },
+            SessionChange::GamePausedChanged { paused: true },
+        ]
+    );
+}
+
+#[test]
+fn test_no_changes_since_identical_session() {
+    let session = PacketSessionDataBuilder::default().build();
+
+    assert!(session.changes_since(&session.clone()).is_empty());
+}
+
+#[test]
+fn test_elapsed_time() {
+    let session = PacketSessionDataBuilder::default()
+        .with_session_duration(3600)
+        .with_session_time_left(3000)
+        .build();
+
+    assert_eq!(session.elapsed_time(), 600);
+}
+
+#[test]
+fn test_elapsed_time_is_zero_when_time_left_exceeds_duration() {
+    let session = PacketSessionDataBuilder::default()
+        .with_session_duration(3600)
+        .with_session_time_left(3602)
+        .build();
+
+    assert_eq!(session.elapsed_time(), 0);
+}
+
+#[test]
+fn test_track_ids_round_trip() {
+    for id in (0..=32).chain([255]) {
+        assert_eq!(Track::try_from(id).unwrap().as_u8(), id);
+    }
+}
+
+#[test]
+fn test_session_type_ids_round_trip() {
+    for id in 0..=13 {
+        assert_eq!(SessionType::try_from(id).unwrap().as_u8(), id);
+    }
+}
+
+#[test]
+fn test_marshal_zone_at() {
+    let zone = |zone_start, zone_flag| MarshalZone {
+        zone_start,
+        zone_flag,
+    };
+    let session = PacketSessionDataBuilder::default()
+        .with_track_length(1000)
+        .with_marshal_zones(vec![
+            zone(0.1, Flag::None),
+            zone(0.5, Flag::Yellow),
+            zone(0.8, Flag::Green),
+        ])
+        .build();
+
+    assert_eq!(
+        session.marshal_zone_at(600.0).unwrap().zone_flag,
+        Flag::Yellow
+    );
+    assert_eq!(
+        session.marshal_zone_at(500.0).unwrap().zone_flag,
+        Flag::Yellow
+    );
+    // Before the first zone, the car is still in the last one of the lap
+    assert_eq!(
+        session.marshal_zone_at(50.0).unwrap().zone_flag,
+        Flag::Green
+    );
+
+    let session = PacketSessionDataBuilder::default().build();
+    assert_eq!(session.marshal_zone_at(50.0), None);
+}
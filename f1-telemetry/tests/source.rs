@@ -0,0 +1,38 @@
+use f1_telemetry::packet::{Packet, UnpackError};
+use f1_telemetry::test_util::{PacketLapDataBuilder, PacketSessionDataBuilder};
+use f1_telemetry::{PacketSource, VecSource};
+
+async fn collect<S: PacketSource>(mut source: S) -> Result<Vec<Packet>, UnpackError> {
+    let mut packets = Vec::new();
+
+    while let Some(packet) = source.next_packet().await? {
+        packets.push(packet);
+    }
+
+    Ok(packets)
+}
+
+#[tokio::test]
+async fn test_vec_source() {
+    let source = VecSource::new(vec![
+        Packet::Session(PacketSessionDataBuilder::default().build()),
+        Packet::LapData(PacketLapDataBuilder::default().build()),
+    ]);
+    assert_eq!(source.len(), 2);
+
+    let packets = collect(source).await.unwrap();
+
+    assert!(matches!(
+        packets.as_slice(),
+        [Packet::Session(_), Packet::LapData(_)]
+    ));
+}
+
+#[tokio::test]
+async fn test_empty_vec_source() {
+    let mut source: VecSource = std::iter::empty().collect();
+
+    assert!(source.is_empty());
+    assert!(source.next_packet().await.unwrap().is_none());
+    assert!(source.recover().await.is_ok());
+}
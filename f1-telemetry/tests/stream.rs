@@ -1,4 +1,13 @@
-use f1_telemetry::packet::UnpackError;
+use std::future::poll_fn;
+use std::io::ErrorKind;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use f1_telemetry::packet::{Packet, PacketType, UnpackError};
+use f1_telemetry::SyncStream;
+use futures_core::Stream as _;
+use tokio::net::UdpSocket;
 
 mod utils;
 
@@ -15,10 +24,279 @@ async fn test_invalid_packet_returns_an_error() {
 
     let p = stream.next().await;
 
-    assert_eq!(
+    assert!(matches!(
         p.unwrap_err(),
-        UnpackError(String::from(
-            "Packet too small: 2 bytes (minimum: 24 bytes)"
-        ))
+        UnpackError::TooSmall {
+            got: 2,
+            expected: 24
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_parse_errors_are_passed_to_the_hook() {
+    let mut stream = utils::get_stream().await;
+    let failed = Arc::new(Mutex::new(Vec::new()));
+
+    let hook_failed = Arc::clone(&failed);
+    stream.on_parse_error(move |data, e| {
+        hook_failed
+            .lock()
+            .unwrap()
+            .push((data.to_vec(), e.to_string()));
+    });
+
+    let socket = utils::get_connected_socket(&stream).await;
+    socket.send(&[0xe4, 0x07]).await.unwrap();
+
+    assert!(stream.next().await.is_err());
+    assert_eq!(
+        *failed.lock().unwrap(),
+        vec![(
+            vec![0xe4, 0x07],
+            "Packet too small: 2 bytes (minimum: 24 bytes)".to_string()
+        )]
     );
 }
+
+#[tokio::test]
+async fn test_skipped_parse_errors_are_not_returned() {
+    let mut stream = utils::get_stream().await;
+    stream.set_skip_errors(true);
+    stream.set_max_skipped_errors(2);
+
+    let socket = utils::get_connected_socket(&stream).await;
+    for _ in 0..2 {
+        socket.send(&[0xe4, 0x07]).await.unwrap();
+    }
+    utils::send_raw_data(
+        &stream,
+        "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000",
+    )
+    .await;
+
+    assert!(matches!(stream.next().await, Ok(Packet::Event(_))));
+}
+
+#[tokio::test]
+async fn test_too_many_consecutive_parse_errors_are_returned() {
+    let mut stream = utils::get_stream().await;
+    stream.set_skip_errors(true);
+    stream.set_max_skipped_errors(2);
+    stream.set_read_timeout(Some(Duration::from_millis(100)));
+
+    let socket = utils::get_connected_socket(&stream).await;
+    for _ in 0..3 {
+        socket.send(&[0xe4, 0x07]).await.unwrap();
+    }
+
+    assert!(matches!(
+        stream.next().await,
+        Err(UnpackError::TooSmall { got: 2, .. })
+    ));
+
+    match stream.next().await {
+        Err(UnpackError::Io(e)) => assert_eq!(e.kind(), ErrorKind::TimedOut),
+        p => panic!("Expected a timeout, got {:?}", p),
+    }
+}
+
+#[tokio::test]
+async fn test_datagrams_are_forwarded() {
+    let mut stream = utils::get_stream().await;
+    let target = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let unreachable = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+    let unreachable_addr = unreachable.local_addr().unwrap();
+    drop(unreachable);
+    stream
+        .forward_to(&[unreachable_addr, target.local_addr().unwrap()])
+        .unwrap();
+
+    let data =
+        hex::decode("e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000")
+            .unwrap();
+    let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+    // The unreachable target must not cause errors when receiving the next packets
+    for _ in 0..2 {
+        sender
+            .send_to(&data, stream.socket().local_addr().unwrap())
+            .await
+            .unwrap();
+
+        assert!(matches!(stream.next().await.unwrap(), Packet::Event(_)));
+
+        let mut buf = [0; 2048];
+        let len = target.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], &data[..]);
+    }
+}
+
+#[tokio::test]
+async fn test_rebind_keeps_the_address() {
+    let mut stream = utils::get_stream().await;
+    let addr = stream.socket().local_addr().unwrap();
+
+    stream.rebind().await.expect("Unable to rebind socket");
+    assert_eq!(stream.socket().local_addr().unwrap(), addr);
+
+    utils::send_raw_data(
+        &stream,
+        "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000",
+    )
+    .await;
+
+    assert!(matches!(stream.next().await.unwrap(), Packet::Event(_)));
+}
+
+#[test]
+fn test_socket_errors_are_fatal() {
+    let err = UnpackError::Io(std::io::Error::from(ErrorKind::NetworkDown));
+    assert!(err.is_fatal());
+
+    let err = UnpackError::Io(std::io::Error::from(ErrorKind::TimedOut));
+    assert!(!err.is_fatal());
+
+    assert!(!UnpackError::NotF1Packet.is_fatal());
+}
+
+#[test]
+fn test_sync_stream_packets_iterator() {
+    let stream = SyncStream::new("127.0.0.1:0").expect("Unable to bind socket");
+    let addr = stream.socket().local_addr().unwrap();
+
+    let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let data =
+        hex::decode("e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000")
+            .unwrap();
+    for _ in 0..3 {
+        socket.send_to(&data, addr).unwrap();
+    }
+
+    let packets: Vec<Packet> = stream.packets().take(3).map(|p| p.unwrap()).collect();
+
+    assert_eq!(packets.len(), 3);
+    assert!(packets.iter().all(|p| matches!(p, Packet::Event(_))));
+}
+
+#[test]
+fn test_sync_stream_try_next() {
+    let stream = SyncStream::new("127.0.0.1:0").expect("Unable to bind socket");
+    let addr = stream.socket().local_addr().unwrap();
+
+    assert!(stream.try_next().unwrap().is_none());
+
+    let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let data =
+        hex::decode("e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000")
+            .unwrap();
+    socket.send_to(&data, addr).unwrap();
+
+    // The runtime of the stream notices the datagram in the background
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let packet = loop {
+        match stream.try_next().unwrap() {
+            Some(p) => break p,
+            None if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(1)),
+            None => panic!("No packet received"),
+        }
+    };
+
+    assert!(matches!(packet, Packet::Event(_)));
+    assert!(stream.try_next().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_subscription_skips_other_packet_types() {
+    let mut stream = utils::get_stream().await;
+    stream.subscribe(&[PacketType::Session]);
+
+    utils::send_raw_data(
+        &stream,
+        "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000",
+    )
+    .await;
+    utils::send_raw_data(&stream, "e407").await;
+
+    // The event packet is skipped, the invalid packet is let through to report the error
+    let p = stream.next().await;
+
+    assert!(matches!(
+        p.unwrap_err(),
+        UnpackError::TooSmall {
+            got: 2,
+            expected: 24
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_try_next_does_not_wait() {
+    let stream = utils::get_stream().await;
+
+    assert!(stream.try_next().unwrap().is_none());
+
+    utils::send_raw_data(
+        &stream,
+        "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000",
+    )
+    .await;
+    stream.socket().readable().await.unwrap();
+
+    assert!(matches!(stream.try_next(), Ok(Some(Packet::Event(_)))));
+    assert!(stream.try_next().unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_read_timeout() {
+    let mut stream = utils::get_stream().await;
+    stream.set_read_timeout(Some(Duration::from_millis(10)));
+
+    match stream.next().await {
+        Err(UnpackError::Io(e)) => assert_eq!(e.kind(), ErrorKind::TimedOut),
+        p => panic!("Expected a timeout, got {:?}", p),
+    }
+}
+
+#[tokio::test]
+async fn test_packet_stream_yields_packets() {
+    let stream = utils::get_stream().await;
+
+    for _ in 0..2 {
+        utils::send_raw_data(
+            &stream,
+            "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000",
+        )
+        .await;
+    }
+
+    let mut packets = stream.into_stream();
+
+    for _ in 0..2 {
+        let p = poll_fn(|cx| Pin::new(&mut packets).poll_next(cx)).await;
+        assert!(matches!(p, Some(Ok(Packet::Event(_)))));
+    }
+}
+
+#[tokio::test]
+async fn test_next_received_timestamps_packets() {
+    let stream = utils::get_stream().await;
+
+    for _ in 0..2 {
+        utils::send_raw_data(
+            &stream,
+            "e407010e0103599832db55618ae1e143e142da0c000013ff535054500de1b6aa430000",
+        )
+        .await;
+    }
+
+    let before = (SystemTime::now(), Instant::now());
+    let first = stream.next_received().await.unwrap();
+    let second = stream.next_received().await.unwrap();
+
+    assert!(matches!(first.packet, Packet::Event(_)));
+    assert!(first.received_at >= before.0);
+    assert!(first.instant >= before.1);
+    assert!(second.instant >= first.instant);
+}
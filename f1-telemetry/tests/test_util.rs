@@ -0,0 +1,87 @@
+use f1_telemetry::packet::generic::SessionType;
+use f1_telemetry::packet::lap::{DriverStatus, PitStatus};
+use f1_telemetry::packet::session::{ForecastAccuracy, Weather, WeatherForecastSample};
+use f1_telemetry::packet::PacketType;
+use f1_telemetry::test_util::{
+    LapDataBuilder, PacketHeaderBuilder, PacketLapDataBuilder, PacketSessionDataBuilder,
+};
+
+#[test]
+fn test_build_session_packet() {
+    let sample = WeatherForecastSample {
+        session_type: SessionType::Race,
+        time_offset: 5,
+        weather: Weather::LightRain,
+        rain_percentage: 60,
+        ..Default::default()
+    };
+
+    let session = PacketSessionDataBuilder::default()
+        .with_header(PacketHeaderBuilder::default().with_session_uid(42).build())
+        .with_weather(Weather::HeavyRain)
+        .with_total_laps(50)
+        .with_weather_forecast(vec![sample], ForecastAccuracy::Approximate)
+        .build();
+
+    assert_eq!(session.header.packet_type, PacketType::Session);
+    assert_eq!(session.header.session_uid, 42);
+    assert_eq!(session.weather, Weather::HeavyRain);
+    assert_eq!(session.total_laps, 50);
+
+    let forecast = session.weather_forecast.unwrap();
+    assert_eq!(forecast.number_of_samples, 1);
+    assert_eq!(forecast.samples, vec![sample]);
+    assert_eq!(forecast.accuracy, ForecastAccuracy::Approximate);
+}
+
+#[test]
+fn test_build_lap_data_packet() {
+    let lap_data = PacketLapDataBuilder::default()
+        .with_car(
+            LapDataBuilder::default()
+                .with_car_position(2)
+                .with_total_distance(1200.0)
+                .build(),
+        )
+        .with_car(
+            LapDataBuilder::default()
+                .with_car_position(1)
+                .with_total_distance(1500.0)
+                .build(),
+        )
+        .build();
+
+    assert_eq!(lap_data.header.packet_type, PacketType::LapData);
+    assert_eq!(lap_data.lap_data.len(), 2);
+    assert_eq!(lap_data.lap_data[0].car_position, 2);
+    assert_eq!(lap_data.lap_data[1].total_distance, 1500.0);
+}
+
+#[test]
+fn test_build_lap_data_with_timers_and_warnings() {
+    let lap_data = LapDataBuilder::default()
+        .with_sector_1_time(5_432)
+        .with_sector_1_time_minutes(1)
+        .with_sector_2_time(31_000)
+        .with_pit_status(PitStatus::PitLane)
+        .with_number_pit_stops(1)
+        .with_pit_lane_timer_active(true)
+        .with_pit_lane_time_in_lane(12_500)
+        .with_total_warnings(3)
+        .with_corner_cutting_warnings(2)
+        .with_grid_position(7)
+        .with_driver_status(DriverStatus::InLap)
+        .build();
+
+    assert_eq!(lap_data.sector_1_total_time(), 65_432);
+    assert_eq!(lap_data.sector_2_total_time(), 31_000);
+    assert_eq!(lap_data.number_pit_stops, 1);
+    assert!(lap_data.pit_lane_timer_active);
+    assert_eq!(lap_data.pit_lane_time_in_lane, 12_500);
+    assert_eq!(
+        (lap_data.total_warnings, lap_data.corner_cutting_warnings),
+        (3, 2)
+    );
+    assert_eq!(lap_data.grid_position, 7);
+    assert_eq!(lap_data.driver_status, DriverStatus::InLap);
+}
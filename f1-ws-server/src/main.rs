@@ -11,6 +11,7 @@ use tokio::sync::broadcast::Receiver;
 use tokio_tungstenite::accept_async;
 use tokio_tungstenite::tungstenite::{Error, Message, Result};
 
+use f1_telemetry::packet::UnpackError;
 use f1_telemetry::Stream;
 use f1_telemetry_common::logging::LogBuilder;
 
@@ -59,6 +60,7 @@ async fn main() {
                     let value = serde_json::to_string(&p).unwrap();
                     let _ = packet_tx.send(value);
                 }
+                Err(UnpackError::NotF1Packet) => {}
                 Err(err) => {
                     error!("{:?}", err);
                 }